@@ -0,0 +1,5 @@
+-- 迁移脚本: V1.5.0__add_notify_options.sql
+-- 描述: 为待办与循环提醒增加提醒附加配置（通知按钮等，JSON 格式）
+
+ALTER TABLE tasks ADD COLUMN notify_options TEXT NOT NULL DEFAULT '{}';
+ALTER TABLE recurring_tasks ADD COLUMN notify_options TEXT NOT NULL DEFAULT '{}';
@@ -0,0 +1,9 @@
+-- 迁移脚本: V1.5.1__add_notification_placement.sql
+-- 描述: 增加提醒弹窗位置、显示器、尺寸与边距设置字段
+
+ALTER TABLE settings ADD COLUMN notification_corner TEXT NOT NULL DEFAULT 'BOTTOM_RIGHT';
+ALTER TABLE settings ADD COLUMN notification_monitor TEXT NOT NULL DEFAULT 'PRIMARY';
+ALTER TABLE settings ADD COLUMN notification_monitor_name TEXT NOT NULL DEFAULT '';
+ALTER TABLE settings ADD COLUMN notification_width REAL;
+ALTER TABLE settings ADD COLUMN notification_height REAL;
+ALTER TABLE settings ADD COLUMN notification_margin REAL NOT NULL DEFAULT 16;
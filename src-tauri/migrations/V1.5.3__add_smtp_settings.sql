@@ -0,0 +1,13 @@
+-- 迁移脚本: V1.5.3__add_smtp_settings.sql
+-- 描述: 增加 SMTP 邮件提醒设置，并在提醒记录上保存外部渠道的发送错误
+
+ALTER TABLE settings ADD COLUMN smtp_enabled INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE settings ADD COLUMN smtp_host TEXT NOT NULL DEFAULT '';
+ALTER TABLE settings ADD COLUMN smtp_port INTEGER NOT NULL DEFAULT 587;
+ALTER TABLE settings ADD COLUMN smtp_security TEXT NOT NULL DEFAULT 'STARTTLS';
+ALTER TABLE settings ADD COLUMN smtp_username TEXT NOT NULL DEFAULT '';
+ALTER TABLE settings ADD COLUMN smtp_password TEXT NOT NULL DEFAULT '';
+ALTER TABLE settings ADD COLUMN smtp_from TEXT NOT NULL DEFAULT '';
+ALTER TABLE settings ADD COLUMN smtp_to TEXT NOT NULL DEFAULT '';
+
+ALTER TABLE reminder_records ADD COLUMN delivery_error TEXT;
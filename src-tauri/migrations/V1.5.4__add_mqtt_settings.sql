@@ -0,0 +1,10 @@
+-- 迁移脚本: V1.5.4__add_mqtt_settings.sql
+-- 描述: 增加 MQTT 事件发布与远程命令设置
+
+ALTER TABLE settings ADD COLUMN mqtt_enabled INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE settings ADD COLUMN mqtt_host TEXT NOT NULL DEFAULT '';
+ALTER TABLE settings ADD COLUMN mqtt_port INTEGER NOT NULL DEFAULT 1883;
+ALTER TABLE settings ADD COLUMN mqtt_username TEXT NOT NULL DEFAULT '';
+ALTER TABLE settings ADD COLUMN mqtt_password TEXT NOT NULL DEFAULT '';
+ALTER TABLE settings ADD COLUMN mqtt_client_id TEXT NOT NULL DEFAULT '';
+ALTER TABLE settings ADD COLUMN mqtt_topic_prefix TEXT NOT NULL DEFAULT 'task-reminder';
@@ -0,0 +1,11 @@
+-- 迁移脚本: V1.5.5__add_sound_settings.sql
+-- 描述: 增加后台提示音、音量递增与免打扰时段设置
+
+ALTER TABLE settings ADD COLUMN sound_default TEXT NOT NULL DEFAULT 'builtin:chime';
+ALTER TABLE settings ADD COLUMN sound_loop INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE settings ADD COLUMN sound_volume_start REAL NOT NULL DEFAULT 0.4;
+ALTER TABLE settings ADD COLUMN sound_volume_max REAL NOT NULL DEFAULT 1.0;
+ALTER TABLE settings ADD COLUMN sound_escalate_seconds INTEGER NOT NULL DEFAULT 60;
+ALTER TABLE settings ADD COLUMN quiet_hours_enabled INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE settings ADD COLUMN quiet_hours_start TEXT NOT NULL DEFAULT '22:00';
+ALTER TABLE settings ADD COLUMN quiet_hours_end TEXT NOT NULL DEFAULT '07:00';
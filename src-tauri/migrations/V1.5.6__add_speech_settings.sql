@@ -0,0 +1,6 @@
+-- 迁移脚本: V1.5.6__add_speech_settings.sql
+-- 描述: 增加提醒语音朗读的引擎、默认语音与语速设置
+
+ALTER TABLE settings ADD COLUMN tts_engine TEXT NOT NULL DEFAULT 'AUTO';
+ALTER TABLE settings ADD COLUMN tts_voice TEXT NOT NULL DEFAULT '';
+ALTER TABLE settings ADD COLUMN tts_rate REAL NOT NULL DEFAULT 1.0;
@@ -0,0 +1,6 @@
+-- 迁移脚本: V1.5.7__add_habit_state.sql
+-- 描述: 为循环提醒的每次记录增加打卡状态（完成/跳过/错过）
+
+ALTER TABLE reminder_records ADD COLUMN habit_state TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_reminder_records_reminder_id ON reminder_records(reminder_id, trigger_time);
@@ -0,0 +1,7 @@
+-- 迁移脚本: V1.5.8__add_digest_settings.sql
+-- 描述: 增加每日简报的开关、发送时间与上次发送日期
+
+ALTER TABLE settings ADD COLUMN digest_enabled INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE settings ADD COLUMN digest_time TEXT NOT NULL DEFAULT '09:00';
+ALTER TABLE settings ADD COLUMN digest_workdays_only INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE settings ADD COLUMN digest_last_date TEXT;
@@ -0,0 +1,9 @@
+-- 迁移脚本: V1.5.9__add_report_settings.sql
+-- 描述: 增加每周回顾报告的自动生成设置
+
+ALTER TABLE settings ADD COLUMN report_enabled INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE settings ADD COLUMN report_weekday INTEGER NOT NULL DEFAULT 5;
+ALTER TABLE settings ADD COLUMN report_time TEXT NOT NULL DEFAULT '17:00';
+ALTER TABLE settings ADD COLUMN report_format TEXT NOT NULL DEFAULT 'MARKDOWN';
+ALTER TABLE settings ADD COLUMN report_dir TEXT NOT NULL DEFAULT '';
+ALTER TABLE settings ADD COLUMN report_last_date TEXT;
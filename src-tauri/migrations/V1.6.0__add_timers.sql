@@ -0,0 +1,13 @@
+-- 迁移脚本: V1.6.0__add_timers.sql
+-- 描述: 增加倒计时器表，运行中的计时器保存结束时间，暂停的计时器保存剩余秒数
+
+CREATE TABLE IF NOT EXISTS timers (
+    id TEXT PRIMARY KEY,
+    label TEXT NOT NULL,
+    duration_seconds INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'RUNNING',
+    ends_at TEXT,
+    remaining_seconds INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
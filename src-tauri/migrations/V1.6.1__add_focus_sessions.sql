@@ -0,0 +1,20 @@
+-- 迁移脚本: V1.6.1__add_focus_sessions.sql
+-- 描述: 增加专注时段设置与任务用时记录表
+
+ALTER TABLE settings ADD COLUMN focus_work_minutes INTEGER NOT NULL DEFAULT 25;
+ALTER TABLE settings ADD COLUMN focus_short_break_minutes INTEGER NOT NULL DEFAULT 5;
+ALTER TABLE settings ADD COLUMN focus_long_break_minutes INTEGER NOT NULL DEFAULT 15;
+ALTER TABLE settings ADD COLUMN focus_long_break_every INTEGER NOT NULL DEFAULT 4;
+
+CREATE TABLE IF NOT EXISTS time_entries (
+    id TEXT PRIMARY KEY,
+    task_id TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    ended_at TEXT NOT NULL,
+    duration_seconds INTEGER NOT NULL DEFAULT 0,
+    source TEXT NOT NULL DEFAULT 'FOCUS',
+    updated_at TEXT,
+    deleted_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_time_entries_task_id ON time_entries(task_id);
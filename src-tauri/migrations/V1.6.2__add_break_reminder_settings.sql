@@ -0,0 +1,6 @@
+-- 迁移脚本: V1.6.2__add_break_reminder_settings.sql
+-- 描述: 增加基于键鼠活动的久坐休息提醒设置
+
+ALTER TABLE settings ADD COLUMN break_enabled INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE settings ADD COLUMN break_active_minutes INTEGER NOT NULL DEFAULT 50;
+ALTER TABLE settings ADD COLUMN break_idle_reset_minutes INTEGER NOT NULL DEFAULT 5;
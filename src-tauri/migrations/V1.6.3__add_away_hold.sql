@@ -0,0 +1,8 @@
+-- 迁移脚本: V1.6.3__add_away_hold.sql
+-- 描述: 锁屏或离开时暂缓弹出提醒，回来后补发并记录实际送达时间
+
+ALTER TABLE settings ADD COLUMN hold_when_away INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE settings ADD COLUMN away_idle_minutes INTEGER NOT NULL DEFAULT 5;
+
+ALTER TABLE reminder_records ADD COLUMN delivery_state TEXT;
+ALTER TABLE reminder_records ADD COLUMN delivered_at TEXT;
@@ -0,0 +1,6 @@
+-- 迁移脚本: V1.6.4__add_occurrence_id.sql
+-- 描述: 提醒记录关联触发点（任务 id + 计划时间），多台设备对同一次触发只保留一条记录
+
+ALTER TABLE reminder_records ADD COLUMN occurrence_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_reminder_records_occurrence_id ON reminder_records(occurrence_id);
@@ -0,0 +1,4 @@
+-- 迁移脚本: V1.6.5__add_lead_reminders.sql
+-- 描述: 新建任务默认的提前提醒分钟数（逗号分隔），任务自身的提前量保存在 notify_options 中
+
+ALTER TABLE settings ADD COLUMN default_lead_minutes TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,6 @@
+-- 迁移脚本: V1.6.6__add_task_due_at.sql
+-- 描述: 任务增加与提醒时间独立的截止时间，用于逾期判断、排序与筛选
+
+ALTER TABLE tasks ADD COLUMN due_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_due_at ON tasks(due_at);
@@ -0,0 +1,20 @@
+-- 迁移脚本: V1.6.7__add_task_reminders.sql
+-- 描述: 一次性任务可设置多个提醒时间，每个提醒时间单独记录是否已触发；tasks.reminder_time 保留为下一个提醒时间
+
+CREATE TABLE IF NOT EXISTS task_reminders (
+    id TEXT PRIMARY KEY,
+    task_id TEXT NOT NULL,
+    remind_at TEXT NOT NULL,
+    fired_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT,
+    deleted_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_reminders_task_id ON task_reminders(task_id);
+
+-- 已有的提醒时间各转成一条，id 由任务与时间决定，多台设备分别迁移后同步时不会重复。
+INSERT OR IGNORE INTO task_reminders (id, task_id, remind_at, fired_at, created_at, updated_at, deleted_at)
+SELECT id || '@' || reminder_time, id, reminder_time, NULL, created_at, updated_at, NULL
+FROM tasks
+WHERE reminder_time IS NOT NULL AND deleted_at IS NULL;
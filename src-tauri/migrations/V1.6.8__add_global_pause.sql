@@ -0,0 +1,11 @@
+-- 迁移脚本: V1.6.8__add_global_pause.sql
+-- 描述: 全局暂停（休假模式）状态表，参与同步；设置中增加恢复时错过提醒的处理方式
+
+CREATE TABLE IF NOT EXISTS pause_state (
+    id TEXT PRIMARY KEY,
+    paused_at TEXT,
+    resume_at TEXT,
+    updated_at TEXT
+);
+
+ALTER TABLE settings ADD COLUMN missed_policy TEXT NOT NULL DEFAULT 'ONCE';
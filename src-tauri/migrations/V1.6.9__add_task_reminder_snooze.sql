@@ -0,0 +1,4 @@
+-- 迁移脚本: V1.6.9__add_task_reminder_snooze.sql
+-- 描述: 稍后提醒生成的提醒时间单独标记，不算作用户设置的提醒时间
+
+ALTER TABLE task_reminders ADD COLUMN is_snooze INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,153 @@
+use std::process::Command;
+
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::leads::normalize_lead_minutes;
+use crate::models::{NotificationAction, NotifyOptions, RecurringTask, Task};
+use crate::sound::normalize_sound_id;
+use crate::speech::normalize_speech_rate;
+
+pub const ACTION_KIND_COMPLETE_TASK: &str = "COMPLETE_TASK";
+pub const ACTION_KIND_OPEN_STICKY_NOTE: &str = "OPEN_STICKY_NOTE";
+pub const ACTION_KIND_OPEN_URL: &str = "OPEN_URL";
+
+const BUILTIN_COMPLETE_ID: &str = "builtin-complete";
+const BUILTIN_OPEN_NOTE_ID: &str = "builtin-open-note";
+const MAX_CUSTOM_ACTIONS: usize = 4;
+
+pub fn task_actions(task: &Task) -> Vec<NotificationAction> {
+    let mut actions = vec![
+        NotificationAction {
+            id: BUILTIN_COMPLETE_ID.to_string(),
+            label: "标记完成".to_string(),
+            kind: ACTION_KIND_COMPLETE_TASK.to_string(),
+            url: None,
+        },
+        NotificationAction {
+            id: BUILTIN_OPEN_NOTE_ID.to_string(),
+            label: "打开便签".to_string(),
+            kind: ACTION_KIND_OPEN_STICKY_NOTE.to_string(),
+            url: None,
+        },
+    ];
+    actions.extend(task.notify_options.actions.iter().cloned());
+    actions
+}
+
+pub fn recurring_actions(task: &RecurringTask) -> Vec<NotificationAction> {
+    // 循环提醒没有“完成”与便签的概念，只保留自定义的链接按钮。
+    task.notify_options
+        .actions
+        .iter()
+        .filter(|action| action.kind == ACTION_KIND_OPEN_URL)
+        .cloned()
+        .collect()
+}
+
+pub fn find_action(actions: &[NotificationAction], action_id: &str) -> Option<NotificationAction> {
+    actions
+        .iter()
+        .find(|action| action.id == action_id)
+        .cloned()
+}
+
+pub fn sanitize_notify_options(
+    options: &mut NotifyOptions,
+    allow_task_actions: bool,
+) -> Result<(), AppError> {
+    options.lead_minutes = normalize_lead_minutes(&options.lead_minutes)?;
+    if options.actions.len() > MAX_CUSTOM_ACTIONS {
+        return Err(AppError::Invalid(format!(
+            "自定义按钮最多 {} 个",
+            MAX_CUSTOM_ACTIONS
+        )));
+    }
+    for action in options.actions.iter_mut() {
+        action.label = action.label.trim().to_string();
+        if action.label.is_empty() {
+            return Err(AppError::Invalid("按钮名称不能为空".to_string()));
+        }
+        action.kind = action.kind.trim().to_uppercase();
+        match action.kind.as_str() {
+            ACTION_KIND_OPEN_URL => {
+                let url = action
+                    .url
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .ok_or_else(|| AppError::Invalid("链接按钮需要填写地址".to_string()))?;
+                validate_url(url)?;
+                action.url = Some(url.to_string());
+            }
+            ACTION_KIND_COMPLETE_TASK | ACTION_KIND_OPEN_STICKY_NOTE if allow_task_actions => {
+                action.url = None;
+            }
+            _ => {
+                return Err(AppError::Invalid(format!(
+                    "不支持的按钮类型: {}",
+                    action.kind
+                )));
+            }
+        }
+        if action.id.trim().is_empty() || action.id.starts_with("builtin-") {
+            action.id = Uuid::new_v4().to_string();
+        }
+    }
+    if let Some(sound) = options.sound.take() {
+        if !sound.trim().is_empty() {
+            options.sound = Some(
+                normalize_sound_id(&sound)
+                    .ok_or_else(|| AppError::Invalid(format!("不支持的提示音: {}", sound)))?,
+            );
+        }
+    }
+    options.tts_voice = options
+        .tts_voice
+        .take()
+        .map(|voice| voice.trim().to_string())
+        .filter(|voice| !voice.is_empty());
+    options.tts_rate = options.tts_rate.map(normalize_speech_rate);
+    Ok(())
+}
+
+pub fn open_url(url: &str) -> Result<(), AppError> {
+    validate_url(url)?;
+
+    #[cfg(windows)]
+    let mut command = {
+        let mut command = Command::new("rundll32");
+        command.args(["url.dll,FileProtocolHandler", url]);
+        command
+    };
+
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = Command::new("open");
+        command.arg(url);
+        command
+    };
+
+    #[cfg(not(any(windows, target_os = "macos")))]
+    let mut command = {
+        let mut command = Command::new("xdg-open");
+        command.arg(url);
+        command
+    };
+
+    command
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| AppError::System(format!("打开链接失败: {}", e)))
+}
+
+fn validate_url(url: &str) -> Result<(), AppError> {
+    let lower = url.to_lowercase();
+    if lower.starts_with("http://") || lower.starts_with("https://") || lower.starts_with("mailto:")
+    {
+        return Ok(());
+    }
+    Err(AppError::Invalid(
+        "链接仅支持 http、https 或 mailto 地址".to_string(),
+    ))
+}
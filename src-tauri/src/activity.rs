@@ -0,0 +1,567 @@
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+use crate::db::DbManager;
+use crate::errors::AppError;
+use crate::models::AppSettings;
+use crate::scheduler::ReminderScheduler;
+
+pub const BREAK_REMINDER_TYPE: &str = "BREAK";
+const BREAK_REMINDER_ID: &str = "break";
+const POLL_SECS: u64 = 10;
+/// 超过这么久没有输入即视为离开，此时不弹出休息提醒。
+const AWAY_SECONDS: u64 = 60;
+
+/// 系统空闲时间来源，返回距离上次键盘鼠标输入的秒数，无法获取时返回 None。
+pub trait IdleSource: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn idle_seconds(&self) -> Option<u64>;
+}
+
+/// 锁屏状态来源，无法获取时返回 None。
+pub trait LockSource: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn is_locked(&self) -> Option<bool>;
+}
+
+/// GNOME（X11 与 Wayland）通过 Mutter 的 IdleMonitor 提供空闲时间（毫秒）。
+struct MutterIdleSource;
+
+impl IdleSource for MutterIdleSource {
+    fn name(&self) -> &'static str {
+        "MUTTER"
+    }
+
+    fn idle_seconds(&self) -> Option<u64> {
+        gdbus_call(
+            "--session",
+            "org.gnome.Mutter.IdleMonitor",
+            "/org/gnome/Mutter/IdleMonitor/Core",
+            &["org.gnome.Mutter.IdleMonitor.GetIdletime"],
+        )
+        .and_then(|output| parse_gdbus_value(&output))
+        .map(|millis: u64| millis / 1000)
+    }
+}
+
+/// KDE 等实现了 org.freedesktop.ScreenSaver 的桌面（毫秒）。
+struct ScreenSaverIdleSource;
+
+impl IdleSource for ScreenSaverIdleSource {
+    fn name(&self) -> &'static str {
+        "SCREENSAVER"
+    }
+
+    fn idle_seconds(&self) -> Option<u64> {
+        gdbus_call(
+            "--session",
+            "org.freedesktop.ScreenSaver",
+            "/org/freedesktop/ScreenSaver",
+            &["org.freedesktop.ScreenSaver.GetSessionIdleTime"],
+        )
+        .and_then(|output| parse_gdbus_value(&output))
+        .map(|millis: u64| millis / 1000)
+    }
+}
+
+/// 其他 X11 桌面使用 xprintidle（毫秒）。
+struct XprintidleSource;
+
+impl IdleSource for XprintidleSource {
+    fn name(&self) -> &'static str {
+        "XPRINTIDLE"
+    }
+
+    fn idle_seconds(&self) -> Option<u64> {
+        let output = Command::new("xprintidle").output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let millis = String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse::<u64>()
+            .ok()?;
+        Some(millis / 1000)
+    }
+}
+
+struct MacIdleSource;
+
+impl IdleSource for MacIdleSource {
+    fn name(&self) -> &'static str {
+        "IOKIT"
+    }
+
+    fn idle_seconds(&self) -> Option<u64> {
+        let output = Command::new("ioreg")
+            .args(["-c", "IOHIDSystem", "-d", "4"])
+            .output()
+            .ok()?;
+        // 形如 `"HIDIdleTime" = 1234567890`，单位为纳秒。
+        let text = String::from_utf8_lossy(&output.stdout);
+        let line = text.lines().find(|line| line.contains("\"HIDIdleTime\""))?;
+        let nanos = line.rsplit('=').next()?.trim().parse::<u64>().ok()?;
+        Some(nanos / 1_000_000_000)
+    }
+}
+
+#[cfg(windows)]
+struct WindowsIdleSource;
+
+#[cfg(windows)]
+impl IdleSource for WindowsIdleSource {
+    fn name(&self) -> &'static str {
+        "WIN32"
+    }
+
+    fn idle_seconds(&self) -> Option<u64> {
+        use windows::Win32::System::SystemInformation::GetTickCount;
+        use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+        let mut info = LASTINPUTINFO {
+            cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+            dwTime: 0,
+        };
+        if !unsafe { GetLastInputInfo(&mut info) }.as_bool() {
+            return None;
+        }
+        let now = unsafe { GetTickCount() };
+        Some(u64::from(now.wrapping_sub(info.dwTime)) / 1000)
+    }
+}
+
+/// 屏保锁屏状态：GNOME 使用 org.gnome.ScreenSaver，KDE 等使用 org.freedesktop.ScreenSaver。
+struct ScreenSaverLockSource {
+    name: &'static str,
+    dest: &'static str,
+    object_path: &'static str,
+    method: &'static str,
+}
+
+impl LockSource for ScreenSaverLockSource {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn is_locked(&self) -> Option<bool> {
+        gdbus_call("--session", self.dest, self.object_path, &[self.method])
+            .and_then(|output| parse_gdbus_value(&output))
+    }
+}
+
+/// systemd-logind 会话的 LockedHint，屏保不提供 D-Bus 接口时使用。
+struct LogindLockSource;
+
+impl LockSource for LogindLockSource {
+    fn name(&self) -> &'static str {
+        "LOGIND"
+    }
+
+    fn is_locked(&self) -> Option<bool> {
+        gdbus_call(
+            "--system",
+            "org.freedesktop.login1",
+            "/org/freedesktop/login1/session/auto",
+            &[
+                "org.freedesktop.DBus.Properties.Get",
+                "org.freedesktop.login1.Session",
+                "LockedHint",
+            ],
+        )
+        .and_then(|output| parse_gdbus_value(&output))
+    }
+}
+
+/// 固定的锁屏状态，设置环境变量 TASKREMINDER_PRESENCE=LOCKED / PRESENT 时替代系统检测，便于测试。
+pub struct FixedPresence {
+    pub locked: bool,
+}
+
+impl LockSource for FixedPresence {
+    fn name(&self) -> &'static str {
+        "FIXED"
+    }
+
+    fn is_locked(&self) -> Option<bool> {
+        Some(self.locked)
+    }
+}
+
+fn gdbus_call(bus: &str, dest: &str, object_path: &str, method: &[&str]) -> Option<String> {
+    let output = Command::new("gdbus")
+        .args([
+            "call",
+            bus,
+            "--dest",
+            dest,
+            "--object-path",
+            object_path,
+            "--method",
+        ])
+        .args(method)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// 解析 gdbus 输出的单个返回值，例如 `(uint64 12345,)`、`(true,)` 或 `(<false>,)`。
+fn parse_gdbus_value<T: std::str::FromStr>(output: &str) -> Option<T> {
+    output
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .trim_end_matches(',')
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .split_whitespace()
+        .last()?
+        .parse()
+        .ok()
+}
+
+pub fn platform_idle_sources() -> Vec<Box<dyn IdleSource>> {
+    #[cfg(windows)]
+    {
+        vec![Box::new(WindowsIdleSource)]
+    }
+    #[cfg(not(windows))]
+    {
+        if cfg!(target_os = "macos") {
+            vec![Box::new(MacIdleSource)]
+        } else {
+            vec![
+                Box::new(MutterIdleSource),
+                Box::new(ScreenSaverIdleSource),
+                Box::new(XprintidleSource),
+            ]
+        }
+    }
+}
+
+pub fn platform_lock_sources() -> Vec<Box<dyn LockSource>> {
+    match std::env::var("TASKREMINDER_PRESENCE").as_deref() {
+        Ok("LOCKED") => return vec![Box::new(FixedPresence { locked: true })],
+        Ok("PRESENT") => return vec![Box::new(FixedPresence { locked: false })],
+        _ => {}
+    }
+    if cfg!(windows) || cfg!(target_os = "macos") {
+        // 这两个平台锁屏后不再有键鼠输入，靠空闲时间即可判断离开。
+        return Vec::new();
+    }
+    vec![
+        Box::new(ScreenSaverLockSource {
+            name: "GNOME_SCREENSAVER",
+            dest: "org.gnome.ScreenSaver",
+            object_path: "/org/gnome/ScreenSaver",
+            method: "org.gnome.ScreenSaver.GetActive",
+        }),
+        Box::new(ScreenSaverLockSource {
+            name: "SCREENSAVER",
+            dest: "org.freedesktop.ScreenSaver",
+            object_path: "/org/freedesktop/ScreenSaver",
+            method: "org.freedesktop.ScreenSaver.GetActive",
+        }),
+        Box::new(LogindLockSource),
+    ]
+}
+
+/// 依次尝试各个来源，记住第一个可用的来源。
+pub struct IdleDetector {
+    sources: Vec<Box<dyn IdleSource>>,
+    active: Mutex<Option<usize>>,
+}
+
+impl IdleDetector {
+    pub fn new(sources: Vec<Box<dyn IdleSource>>) -> Self {
+        Self {
+            sources,
+            active: Mutex::new(None),
+        }
+    }
+
+    /// 返回可用来源的名称与空闲秒数。
+    pub fn idle_seconds(&self) -> Option<(&'static str, u64)> {
+        let mut active = self.active.lock().unwrap();
+        if let Some(index) = *active {
+            if let Some(seconds) = self.sources[index].idle_seconds() {
+                return Some((self.sources[index].name(), seconds));
+            }
+        }
+        for (index, source) in self.sources.iter().enumerate() {
+            if let Some(seconds) = source.idle_seconds() {
+                *active = Some(index);
+                return Some((source.name(), seconds));
+            }
+        }
+        *active = None;
+        None
+    }
+}
+
+/// 连续活动时间的计算。短暂离开不打断连续活动，离开超过 `break_idle_reset_minutes` 才算休息过。
+#[derive(Default)]
+pub struct ActivityTracker {
+    active_since: Option<NaiveDateTime>,
+    last_reminded: Option<NaiveDateTime>,
+    snoozed_until: Option<NaiveDateTime>,
+}
+
+impl ActivityTracker {
+    /// 记录一次空闲时间采样，返回是否应该提醒休息。
+    pub fn observe(
+        &mut self,
+        settings: &AppSettings,
+        now: NaiveDateTime,
+        idle_seconds: u64,
+    ) -> bool {
+        let reset_seconds = (settings.break_idle_reset_minutes.max(1) * 60) as u64;
+        if idle_seconds >= reset_seconds {
+            self.reset();
+            return false;
+        }
+        let last_input = now - chrono::Duration::seconds(idle_seconds as i64);
+        let active_since = *self.active_since.get_or_insert(last_input);
+        if idle_seconds >= AWAY_SECONDS {
+            return false;
+        }
+        // 稍后提醒到期后直接再提醒一次，不再等满一个周期。
+        if let Some(until) = self.snoozed_until {
+            if until > now {
+                return false;
+            }
+            self.snoozed_until = None;
+            self.last_reminded = Some(now);
+            return true;
+        }
+        let anchor = self.last_reminded.unwrap_or(active_since);
+        if now - anchor >= chrono::Duration::minutes(settings.break_active_minutes.max(1)) {
+            self.last_reminded = Some(now);
+            return true;
+        }
+        false
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn snooze(&mut self, until: NaiveDateTime) {
+        self.snoozed_until = Some(until);
+    }
+
+    pub fn active_seconds(&self, now: NaiveDateTime) -> i64 {
+        self.active_since
+            .map(|since| (now - since).num_seconds().max(0))
+            .unwrap_or(0)
+    }
+}
+
+/// 是否视为离开电脑：锁屏，或连续 `away_idle_minutes` 分钟没有输入。
+pub fn is_away(settings: &AppSettings, locked: Option<bool>, idle_seconds: Option<u64>) -> bool {
+    if !settings.hold_when_away {
+        return false;
+    }
+    let away_seconds = (settings.away_idle_minutes.max(1) * 60) as u64;
+    locked == Some(true) || idle_seconds.is_some_and(|seconds| seconds >= away_seconds)
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityStatus {
+    /// 当前使用的空闲时间来源，为空表示无法检测。
+    pub source: Option<String>,
+    pub idle_seconds: Option<u64>,
+    pub active_seconds: i64,
+    /// 锁屏状态来源与状态，为空表示无法检测。
+    pub lock_source: Option<String>,
+    pub locked: Option<bool>,
+    pub away: bool,
+}
+
+#[derive(Clone)]
+pub struct ActivityMonitor {
+    db: DbManager,
+    scheduler: ReminderScheduler,
+    detector: Arc<IdleDetector>,
+    lock_sources: Arc<Vec<Box<dyn LockSource>>>,
+    tracker: Arc<Mutex<ActivityTracker>>,
+}
+
+impl ActivityMonitor {
+    pub fn new(
+        db: DbManager,
+        scheduler: ReminderScheduler,
+        detector: IdleDetector,
+        lock_sources: Vec<Box<dyn LockSource>>,
+    ) -> Self {
+        Self {
+            db,
+            scheduler,
+            detector: Arc::new(detector),
+            lock_sources: Arc::new(lock_sources),
+            tracker: Arc::new(Mutex::new(ActivityTracker::default())),
+        }
+    }
+
+    pub fn start(&self) {
+        let monitor = self.clone();
+        thread::spawn(move || loop {
+            if let Err(err) = monitor.check() {
+                eprintln!("[activity] {}", err);
+            }
+            thread::sleep(Duration::from_secs(POLL_SECS));
+        });
+    }
+
+    pub fn status(&self) -> ActivityStatus {
+        let sample = self.detector.idle_seconds();
+        let lock = self.lock_state();
+        let now = self.db.clock().now();
+        ActivityStatus {
+            source: sample.map(|(name, _)| name.to_string()),
+            idle_seconds: sample.map(|(_, seconds)| seconds),
+            active_seconds: self.tracker.lock().unwrap().active_seconds(now),
+            lock_source: lock.map(|(name, _)| name.to_string()),
+            locked: lock.map(|(_, locked)| locked),
+            away: self.scheduler.is_away(),
+        }
+    }
+
+    /// 休息提醒被“稍后提醒”时，在指定分钟内不再提醒。
+    pub fn snooze(&self, minutes: i64) {
+        let until = self.db.clock().now() + chrono::Duration::minutes(minutes.max(1));
+        self.tracker.lock().unwrap().snooze(until);
+    }
+
+    fn lock_state(&self) -> Option<(&'static str, bool)> {
+        self.lock_sources
+            .iter()
+            .find_map(|source| source.is_locked().map(|locked| (source.name(), locked)))
+    }
+
+    fn check(&self) -> Result<(), AppError> {
+        let settings = self.db.load_settings()?;
+        let idle_seconds = self.detector.idle_seconds().map(|(_, seconds)| seconds);
+        let locked = self.lock_state().map(|(_, locked)| locked);
+        let away = is_away(&settings, locked, idle_seconds);
+        self.scheduler.set_away(away);
+        if !away {
+            self.scheduler.deliver_held()?;
+        }
+        self.check_break(&settings, idle_seconds, locked == Some(true))
+    }
+
+    fn check_break(
+        &self,
+        settings: &AppSettings,
+        idle_seconds: Option<u64>,
+        locked: bool,
+    ) -> Result<(), AppError> {
+        if !settings.break_enabled {
+            self.tracker.lock().unwrap().reset();
+            return Ok(());
+        }
+        let Some(idle_seconds) = idle_seconds else {
+            return Ok(());
+        };
+        if locked {
+            return Ok(());
+        }
+        let now = self.db.clock().now();
+        let remind = self
+            .tracker
+            .lock()
+            .unwrap()
+            .observe(settings, now, idle_seconds);
+        if !remind || self.scheduler.has_pending_notification() {
+            return Ok(());
+        }
+        let minutes = self.tracker.lock().unwrap().active_seconds(now) / 60;
+        self.scheduler.present_system_reminder(
+            BREAK_REMINDER_ID,
+            BREAK_REMINDER_TYPE,
+            &format!("已连续活动 {} 分钟，起来活动一下吧", minutes),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> AppSettings {
+        AppSettings {
+            break_enabled: true,
+            break_active_minutes: 50,
+            break_idle_reset_minutes: 5,
+            hold_when_away: true,
+            away_idle_minutes: 5,
+            ..AppSettings::default()
+        }
+    }
+
+    fn at(minute: i64) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str("2026-01-05T09:00:00", "%Y-%m-%dT%H:%M:%S").unwrap()
+            + chrono::Duration::minutes(minute)
+    }
+
+    #[test]
+    fn reminds_after_continuous_activity_and_again_after_next_period() {
+        let settings = settings();
+        let mut tracker = ActivityTracker::default();
+        assert!(!tracker.observe(&settings, at(0), 0));
+        assert!(!tracker.observe(&settings, at(49), 2));
+        assert!(tracker.observe(&settings, at(50), 0));
+        assert!(!tracker.observe(&settings, at(60), 0));
+        assert!(tracker.observe(&settings, at(100), 0));
+    }
+
+    #[test]
+    fn long_idle_counts_as_break() {
+        let settings = settings();
+        let mut tracker = ActivityTracker::default();
+        tracker.observe(&settings, at(0), 0);
+        assert!(!tracker.observe(&settings, at(40), 6 * 60));
+        assert_eq!(tracker.active_seconds(at(40)), 0);
+        assert!(!tracker.observe(&settings, at(80), 0));
+        assert!(tracker.observe(&settings, at(130), 0));
+    }
+
+    #[test]
+    fn snooze_reminds_as_soon_as_it_expires() {
+        let settings = settings();
+        let mut tracker = ActivityTracker::default();
+        tracker.observe(&settings, at(0), 0);
+        assert!(tracker.observe(&settings, at(50), 0));
+        tracker.snooze(at(60));
+        assert!(!tracker.observe(&settings, at(55), 0));
+        assert!(tracker.observe(&settings, at(60), 0));
+    }
+
+    #[test]
+    fn away_when_locked_or_idle_long_enough() {
+        let mut settings = settings();
+        assert!(is_away(&settings, Some(true), None));
+        assert!(is_away(&settings, Some(false), Some(5 * 60)));
+        assert!(!is_away(&settings, Some(false), Some(60)));
+        assert!(!is_away(&settings, None, None));
+        settings.hold_when_away = false;
+        assert!(!is_away(&settings, Some(true), Some(3600)));
+    }
+
+    #[test]
+    fn parses_gdbus_replies() {
+        assert_eq!(parse_gdbus_value::<u64>("(uint64 12345,)\n"), Some(12345));
+        assert_eq!(parse_gdbus_value::<bool>("(true,)"), Some(true));
+        assert_eq!(parse_gdbus_value::<bool>("(<false>,)"), Some(false));
+        assert_eq!(parse_gdbus_value::<u64>(""), None);
+    }
+}
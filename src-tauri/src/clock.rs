@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use chrono::{Local, NaiveDateTime};
+
+/// 当前时间的来源。运行时使用系统时间，测试中替换为可手动拨动的时钟。
+pub trait Clock: Send + Sync {
+    fn now(&self) -> NaiveDateTime;
+}
+
+pub type SharedClock = Arc<dyn Clock>;
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        Local::now().naive_local()
+    }
+}
+
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+#[cfg(test)]
+pub struct ManualClock {
+    now: std::sync::Mutex<NaiveDateTime>,
+}
+
+#[cfg(test)]
+impl ManualClock {
+    pub fn new(now: NaiveDateTime) -> Arc<Self> {
+        Arc::new(Self {
+            now: std::sync::Mutex::new(now),
+        })
+    }
+
+    pub fn set(&self, now: NaiveDateTime) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    pub fn advance(&self, duration: chrono::Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+#[cfg(test)]
+impl Clock for ManualClock {
+    fn now(&self) -> NaiveDateTime {
+        *self.now.lock().unwrap()
+    }
+}
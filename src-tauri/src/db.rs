@@ -1,19 +1,48 @@
 use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
 
-use chrono::Local;
+use chrono::{Duration, NaiveDateTime};
 use r2d2::{Pool, PooledConnection};
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{params, Connection, OptionalExtension};
 use uuid::Uuid;
 
+use crate::clock::{system_clock, SharedClock};
+use crate::due::{
+    is_overdue, TASK_FILTER_ALL, TASK_FILTER_DUE_TODAY, TASK_FILTER_NO_DUE, TASK_FILTER_OVERDUE,
+    TASK_SORT_CREATED, TASK_SORT_DUE, TASK_SORT_REMINDER,
+};
 use crate::errors::AppError;
-use crate::models::{AppSettings, RecurringTask, ReminderRecord, StickyNote, Task};
+use crate::habits::habit_state_for_action;
+use crate::leads::{
+    format_lead_list, lead_record_id, normalize_lead_minutes, parse_lead_list, LEAD_REMINDER_TYPE,
+};
+use crate::models::{
+    AppSettings, NotifyOptions, PauseState, RecurringTask, ReminderRecord, StickyNote, Task,
+    TaskReminder, TimeEntry, Timer, WebhookChannel, WebhookDelivery,
+};
+use crate::pause::{normalize_missed_policy, GLOBAL_PAUSE_ID};
 use crate::recurrence::REPEAT_MODE_INTERVAL_RANGE;
+use crate::report::normalize_report_format;
+use crate::sound::{normalize_sound_id, DEFAULT_SOUND};
+use crate::speech::{normalize_speech_engine, normalize_speech_rate};
+
+/// 任务写入路径上发出的事件（携带任务 id），供 MQTT 等外部集成订阅。
+#[derive(Clone)]
+pub enum TaskEvent {
+    Created(String),
+    Completed(String),
+    Deleted(String),
+}
+
+type TaskEventListener = Arc<dyn Fn(&TaskEvent) + Send + Sync>;
 
 #[derive(Clone)]
 pub struct DbManager {
     pool: Pool<SqliteConnectionManager>,
     db_path: PathBuf,
+    listeners: Arc<RwLock<Vec<TaskEventListener>>>,
+    clock: SharedClock,
 }
 
 fn normalize_sticky_note_opacity(opacity: Option<f64>) -> f64 {
@@ -26,6 +55,87 @@ fn normalize_window_opacity(opacity: Option<f64>) -> f64 {
     value.clamp(0.3, 1.0)
 }
 
+fn normalize_notification_corner(corner: &str) -> String {
+    match corner.trim().to_uppercase().as_str() {
+        "TOP_LEFT" => "TOP_LEFT".to_string(),
+        "TOP_RIGHT" => "TOP_RIGHT".to_string(),
+        "BOTTOM_LEFT" => "BOTTOM_LEFT".to_string(),
+        _ => "BOTTOM_RIGHT".to_string(),
+    }
+}
+
+fn normalize_notification_monitor(monitor: &str) -> String {
+    match monitor.trim().to_uppercase().as_str() {
+        "CURSOR" => "CURSOR".to_string(),
+        "NAMED" => "NAMED".to_string(),
+        _ => "PRIMARY".to_string(),
+    }
+}
+
+fn normalize_notification_dimension(value: Option<f64>, min: f64, max: f64) -> Option<f64> {
+    value
+        .filter(|v| v.is_finite() && *v > 0.0)
+        .map(|v| v.clamp(min, max))
+}
+
+fn normalize_notification_margin(margin: Option<f64>) -> f64 {
+    let value = margin.filter(|v| v.is_finite()).unwrap_or(16.0);
+    value.clamp(0.0, 200.0)
+}
+
+fn normalize_smtp_security(security: &str) -> String {
+    match security.trim().to_uppercase().as_str() {
+        "TLS" => "TLS".to_string(),
+        "NONE" => "NONE".to_string(),
+        _ => "STARTTLS".to_string(),
+    }
+}
+
+fn normalize_smtp_port(port: Option<i64>) -> i64 {
+    port.filter(|value| (1..=65535).contains(value))
+        .unwrap_or(587)
+}
+
+fn normalize_mqtt_port(port: Option<i64>) -> i64 {
+    port.filter(|value| (1..=65535).contains(value))
+        .unwrap_or(1883)
+}
+
+fn normalize_mqtt_topic_prefix(prefix: &str) -> String {
+    let value = prefix.trim().trim_matches('/');
+    if value.is_empty() || value.contains(['#', '+']) {
+        return "task-reminder".to_string();
+    }
+    value.to_string()
+}
+
+fn normalize_sound_volume(volume: Option<f64>, default: f64) -> f64 {
+    volume
+        .filter(|v| v.is_finite())
+        .unwrap_or(default)
+        .clamp(0.0, 1.0)
+}
+
+fn normalize_report_weekday(weekday: Option<i64>) -> i64 {
+    weekday.filter(|value| (1..=7).contains(value)).unwrap_or(5)
+}
+
+fn normalize_focus_minutes(minutes: Option<i64>, default: i64) -> i64 {
+    minutes
+        .filter(|value| (1..=240).contains(value))
+        .unwrap_or(default)
+}
+
+fn normalize_focus_rounds(rounds: Option<i64>) -> i64 {
+    rounds.filter(|value| (1..=12).contains(value)).unwrap_or(4)
+}
+
+fn normalize_quiet_time(value: &str, default: &str) -> String {
+    chrono::NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .map(|time| time.format("%H:%M").to_string())
+        .unwrap_or_else(|_| default.to_string())
+}
+
 const STICKY_NOTE_DEFAULT_POS_X: f64 = 48.0;
 const STICKY_NOTE_DEFAULT_POS_Y: f64 = 76.0;
 const STICKY_NOTE_ITEM_DEFAULT_WIDTH: f64 = 284.0;
@@ -49,17 +159,49 @@ fn normalize_sticky_item_height(height: Option<f64>) -> f64 {
 
 impl DbManager {
     pub fn new(db_path: PathBuf) -> Result<Self, AppError> {
+        Self::with_clock(db_path, system_clock())
+    }
+
+    /// 指定时间来源，调度器与同步等服务通过 `clock()` 共用同一个时钟。
+    pub fn with_clock(db_path: PathBuf, clock: SharedClock) -> Result<Self, AppError> {
         let manager = SqliteConnectionManager::file(&db_path);
         let pool = Pool::new(manager).map_err(|e| AppError::Database(e.to_string()))?;
-        let db = DbManager { pool, db_path };
+        let db = DbManager {
+            pool,
+            db_path,
+            listeners: Arc::new(RwLock::new(Vec::new())),
+            clock,
+        };
         db.init()?;
         Ok(db)
     }
 
+    pub fn clock(&self) -> SharedClock {
+        self.clock.clone()
+    }
+
+    fn now_string(&self) -> String {
+        format_db_time(self.clock.now())
+    }
+
     pub fn db_path(&self) -> PathBuf {
         self.db_path.clone()
     }
 
+    pub fn subscribe<F>(&self, listener: F)
+    where
+        F: Fn(&TaskEvent) + Send + Sync + 'static,
+    {
+        self.listeners.write().unwrap().push(Arc::new(listener));
+    }
+
+    fn emit(&self, event: TaskEvent) {
+        let listeners = self.listeners.read().unwrap().clone();
+        for listener in listeners {
+            listener(&event);
+        }
+    }
+
     fn get_conn(&self) -> Result<PooledConnection<SqliteConnectionManager>, AppError> {
         self.pool
             .get()
@@ -107,7 +249,7 @@ impl DbManager {
         if count == 0 {
             conn.execute(
                 "INSERT INTO schema_version (id, version, applied_at, description) VALUES (1, '0.0.0', ?, 'Initial')",
-                [now_string()],
+                [self.now_string()],
             )?;
         }
         Ok(())
@@ -123,7 +265,7 @@ impl DbManager {
                 execute_sql_script(&tx, script.sql)?;
                 tx.execute(
                     "UPDATE schema_version SET version = ?, applied_at = ?, description = ? WHERE id = 1",
-                    params![script.version, now_string(), script.description],
+                    params![script.version, self.now_string(), script.description],
                 )?;
                 tx.commit()?;
             }
@@ -159,21 +301,59 @@ impl DbManager {
     }
 
     pub fn list_active_tasks(&self) -> Result<Vec<Task>, AppError> {
-        let conn = self.get_conn()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, description, sticky_content, type, status, created_at, completed_at, reminder_time, updated_at, deleted_at
+        self.query_active_tasks(TASK_SORT_CREATED, TASK_FILTER_ALL)
+    }
+
+    /// 按排序方式与筛选条件列出未完成的任务，`sort` / `filter` 需先经 due 模块校验。
+    pub fn query_active_tasks(&self, sort: &str, filter: &str) -> Result<Vec<Task>, AppError> {
+        let now = self.clock.now();
+        let today = now.date().and_hms_opt(0, 0, 0).unwrap_or(now);
+        let tomorrow = today + Duration::days(1);
+        let (condition, bounds) = match filter {
+            TASK_FILTER_OVERDUE => (
+                "AND due_at IS NOT NULL AND due_at < ?",
+                vec![format_db_time(now)],
+            ),
+            TASK_FILTER_DUE_TODAY => (
+                "AND due_at >= ? AND due_at < ?",
+                vec![format_db_time(today), format_db_time(tomorrow)],
+            ),
+            TASK_FILTER_NO_DUE => ("AND due_at IS NULL", Vec::new()),
+            _ => ("", Vec::new()),
+        };
+        let order = match sort {
+            TASK_SORT_DUE => "due_at IS NULL, due_at ASC, created_at ASC",
+            TASK_SORT_REMINDER => "reminder_time IS NULL, reminder_time ASC, created_at ASC",
+            _ => "created_at ASC",
+        };
+        let sql = format!(
+            "SELECT id, description, sticky_content, type, status, created_at, completed_at, reminder_time, updated_at, deleted_at,
+                    notify_options,
+                    (SELECT COALESCE(SUM(duration_seconds), 0) FROM time_entries
+                     WHERE time_entries.task_id = tasks.id AND time_entries.deleted_at IS NULL),
+                    due_at
              FROM tasks
-             WHERE deleted_at IS NULL AND status != 'COMPLETED'
-             ORDER BY created_at ASC",
-        )?;
-        let rows = stmt.query_map([], |row| task_from_row(row))?;
-        Ok(rows.filter_map(Result::ok).collect())
+             WHERE deleted_at IS NULL AND status != 'COMPLETED' {}
+             ORDER BY {}",
+            condition, order
+        );
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(bounds), |row| task_from_row(row))?;
+        Ok(rows
+            .filter_map(Result::ok)
+            .map(|task| with_overdue(task, now))
+            .collect())
     }
 
     pub fn list_completed_tasks(&self) -> Result<Vec<Task>, AppError> {
         let conn = self.get_conn()?;
         let mut stmt = conn.prepare(
-            "SELECT id, description, sticky_content, type, status, created_at, completed_at, reminder_time, updated_at, deleted_at
+            "SELECT id, description, sticky_content, type, status, created_at, completed_at, reminder_time, updated_at, deleted_at,
+                    notify_options,
+                    (SELECT COALESCE(SUM(duration_seconds), 0) FROM time_entries
+                     WHERE time_entries.task_id = tasks.id AND time_entries.deleted_at IS NULL),
+                    due_at
              FROM tasks
              WHERE deleted_at IS NULL AND status = 'COMPLETED'
              ORDER BY completed_at DESC",
@@ -185,13 +365,17 @@ impl DbManager {
     pub fn get_task(&self, task_id: &str) -> Result<Option<Task>, AppError> {
         let conn = self.get_conn()?;
         let mut stmt = conn.prepare(
-            "SELECT id, description, sticky_content, type, status, created_at, completed_at, reminder_time, updated_at, deleted_at
+            "SELECT id, description, sticky_content, type, status, created_at, completed_at, reminder_time, updated_at, deleted_at,
+                    notify_options,
+                    (SELECT COALESCE(SUM(duration_seconds), 0) FROM time_entries
+                     WHERE time_entries.task_id = tasks.id AND time_entries.deleted_at IS NULL),
+                    due_at
              FROM tasks WHERE id = ?",
         )?;
         let task = stmt
             .query_row([task_id], |row| task_from_row(row))
             .optional()?;
-        Ok(task)
+        Ok(task.map(|task| with_overdue(task, self.clock.now())))
     }
 
     pub fn list_recurring_tasks(&self) -> Result<Vec<RecurringTask>, AppError> {
@@ -200,7 +384,7 @@ impl DbManager {
             "SELECT id, description, type, status, created_at, completed_at,
                     interval_minutes, last_triggered, next_trigger, is_paused, start_time, end_time,
                     repeat_mode, schedule_time, schedule_weekday, schedule_day, cron_expression,
-                    updated_at, deleted_at
+                    updated_at, deleted_at, notify_options
              FROM recurring_tasks
              WHERE deleted_at IS NULL
              ORDER BY created_at ASC",
@@ -215,7 +399,7 @@ impl DbManager {
             "SELECT id, description, type, status, created_at, completed_at,
                     interval_minutes, last_triggered, next_trigger, is_paused, start_time, end_time,
                     repeat_mode, schedule_time, schedule_weekday, schedule_day, cron_expression,
-                    updated_at, deleted_at
+                    updated_at, deleted_at, notify_options
              FROM recurring_tasks WHERE id = ?",
         )?;
         let task = stmt
@@ -227,7 +411,8 @@ impl DbManager {
     pub fn list_reminder_records(&self) -> Result<Vec<ReminderRecord>, AppError> {
         let conn = self.get_conn()?;
         let mut stmt = conn.prepare(
-            "SELECT id, reminder_id, description, type, trigger_time, close_time, action, updated_at, deleted_at
+            "SELECT id, reminder_id, description, type, trigger_time, close_time, action, updated_at, deleted_at, delivery_error,
+                    habit_state, delivery_state, delivered_at, occurrence_id
              FROM reminder_records
              WHERE deleted_at IS NULL
              ORDER BY trigger_time DESC",
@@ -236,10 +421,45 @@ impl DbManager {
         Ok(rows.filter_map(Result::ok).collect())
     }
 
+    /// 按触发时间升序返回区间内的提醒记录，`to` 为不含的上界。
+    pub fn list_reminder_records_between(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<ReminderRecord>, AppError> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, reminder_id, description, type, trigger_time, close_time, action, updated_at, deleted_at, delivery_error,
+                    habit_state, delivery_state, delivered_at, occurrence_id
+             FROM reminder_records
+             WHERE deleted_at IS NULL AND trigger_time >= ? AND trigger_time < ?
+             ORDER BY trigger_time ASC",
+        )?;
+        let rows = stmt.query_map(params![from, to], record_from_row)?;
+        Ok(rows.filter_map(Result::ok).collect())
+    }
+
+    pub fn list_reminder_records_for(
+        &self,
+        reminder_id: &str,
+    ) -> Result<Vec<ReminderRecord>, AppError> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, reminder_id, description, type, trigger_time, close_time, action, updated_at, deleted_at, delivery_error,
+                    habit_state, delivery_state, delivered_at, occurrence_id
+             FROM reminder_records
+             WHERE deleted_at IS NULL AND reminder_id = ?
+             ORDER BY trigger_time ASC",
+        )?;
+        let rows = stmt.query_map([reminder_id], record_from_row)?;
+        Ok(rows.filter_map(Result::ok).collect())
+    }
+
     pub fn get_reminder_record(&self, record_id: &str) -> Result<Option<ReminderRecord>, AppError> {
         let conn = self.get_conn()?;
         let mut stmt = conn.prepare(
-            "SELECT id, reminder_id, description, type, trigger_time, close_time, action, updated_at, deleted_at
+            "SELECT id, reminder_id, description, type, trigger_time, close_time, action, updated_at, deleted_at, delivery_error,
+                    habit_state, delivery_state, delivered_at, occurrence_id
              FROM reminder_records WHERE id = ?",
         )?;
         let record = stmt
@@ -254,7 +474,7 @@ impl DbManager {
         sticky_content: Option<&str>,
     ) -> Result<Task, AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         let id = Uuid::new_v4().to_string();
         let note = sticky_content.unwrap_or("").trim().to_string();
         conn.execute(
@@ -262,7 +482,7 @@ impl DbManager {
              VALUES (?, ?, 'ONE_TIME', 'PENDING', ?, NULL, NULL, ?, ?, NULL)",
             params![id, description, now, note, now],
         )?;
-        Ok(Task {
+        let task = Task {
             id,
             description: description.to_string(),
             sticky_content: if note.is_empty() { None } else { Some(note) },
@@ -273,41 +493,186 @@ impl DbManager {
             reminder_time: None,
             updated_at: Some(now),
             deleted_at: None,
-        })
+            notify_options: NotifyOptions::default(),
+            tracked_seconds: 0,
+            due_at: None,
+            overdue: false,
+        };
+        self.emit(TaskEvent::Created(task.id.clone()));
+        Ok(task)
     }
 
+    /// 更新任务内容与提醒时间列表。`reminder_times` 需先经 task_reminders 模块整理：
+    /// 保留下来的时间沿用原有记录与触发状态，去掉的软删除，新增的各建一条。
+    /// `due_at` 与 `notify_options` 为 `None` 时保持不变，全部改动在同一事务中写入。
     pub fn update_task(
         &self,
         task_id: &str,
         description: &str,
         sticky_content: Option<String>,
-        reminder_time: Option<String>,
+        reminder_times: &[String],
+        due_at: Option<Option<&str>>,
+        notify_options: Option<&NotifyOptions>,
     ) -> Result<(), AppError> {
-        let conn = self.get_conn()?;
-        let now = now_string();
+        let mut conn = self.get_conn()?;
+        let now = self.now_string();
         let note = sticky_content
             .map(|value| value.trim().to_string())
             .unwrap_or_default();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "UPDATE tasks SET description = ?, sticky_content = ?, updated_at = ? WHERE id = ?",
+            params![description, note, now, task_id],
+        )?;
+        if let Some(due_at) = due_at {
+            tx.execute(
+                "UPDATE tasks SET due_at = ? WHERE id = ?",
+                params![due_at, task_id],
+            )?;
+        }
+        if let Some(options) = notify_options {
+            tx.execute(
+                "UPDATE tasks SET notify_options = ? WHERE id = ?",
+                params![notify_options_to_json(options)?, task_id],
+            )?;
+        }
+        let existing = query_task_reminders(&tx, task_id)?;
+        for reminder in &existing {
+            if !reminder_times.contains(&reminder.remind_at) {
+                tx.execute(
+                    "UPDATE task_reminders SET deleted_at = ?, updated_at = ? WHERE id = ?",
+                    params![now, now, reminder.id],
+                )?;
+            }
+        }
+        for remind_at in reminder_times {
+            if !existing
+                .iter()
+                .any(|reminder| &reminder.remind_at == remind_at)
+            {
+                tx.execute(
+                    "INSERT INTO task_reminders (id, task_id, remind_at, fired_at, created_at, updated_at, deleted_at)
+                     VALUES (?, ?, ?, NULL, ?, ?, NULL)",
+                    params![Uuid::new_v4().to_string(), task_id, remind_at, now, now],
+                )?;
+            }
+        }
+        refresh_reminder_time(&tx, Some(task_id), &now)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// 从早到晚列出任务中用户设置的未删除提醒时间，包括已触发的，不含稍后提醒。
+    pub fn list_task_reminders(&self, task_id: &str) -> Result<Vec<TaskReminder>, AppError> {
+        let conn = self.get_conn()?;
+        query_task_reminders(&conn, task_id)
+    }
+
+    /// 同 `list_task_reminders`，另含稍后提醒生成的提醒时间，供调度使用。
+    pub fn list_task_reminders_with_snoozes(
+        &self,
+        task_id: &str,
+    ) -> Result<Vec<TaskReminder>, AppError> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, task_id, remind_at, fired_at, created_at, updated_at, deleted_at, is_snooze
+             FROM task_reminders
+             WHERE task_id = ? AND deleted_at IS NULL
+             ORDER BY remind_at ASC",
+        )?;
+        let rows = stmt.query_map([task_id], task_reminder_from_row)?;
+        Ok(rows.filter_map(Result::ok).collect())
+    }
+
+    pub fn get_task_reminder(&self, reminder_id: &str) -> Result<Option<TaskReminder>, AppError> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, task_id, remind_at, fired_at, created_at, updated_at, deleted_at, is_snooze
+             FROM task_reminders WHERE id = ?",
+        )?;
+        let reminder = stmt
+            .query_row([reminder_id], task_reminder_from_row)
+            .optional()?;
+        Ok(reminder)
+    }
+
+    /// 单独追加一个提醒时间（稍后提醒），不影响已有的提醒时间。
+    /// 稍后提醒：为任务加一个标记为稍后提醒的提醒时间，同步到其他设备，但不算用户设置的提醒时间。
+    pub fn add_task_snooze(
+        &self,
+        task_id: &str,
+        remind_at: &str,
+    ) -> Result<TaskReminder, AppError> {
+        let conn = self.get_conn()?;
+        let now = self.now_string();
+        let reminder = TaskReminder {
+            id: Uuid::new_v4().to_string(),
+            task_id: task_id.to_string(),
+            remind_at: remind_at.to_string(),
+            fired_at: None,
+            created_at: now.clone(),
+            updated_at: Some(now.clone()),
+            deleted_at: None,
+            is_snooze: true,
+        };
         conn.execute(
-            "UPDATE tasks SET description = ?, sticky_content = ?, reminder_time = ?, updated_at = ? WHERE id = ?",
-            params![description, note, reminder_time, now, task_id],
+            "INSERT INTO task_reminders (id, task_id, remind_at, fired_at, created_at, updated_at, deleted_at, is_snooze)
+             VALUES (?, ?, ?, NULL, ?, ?, NULL, 1)",
+            params![reminder.id, task_id, remind_at, now, now],
+        )?;
+        refresh_reminder_time(&conn, Some(task_id), &now)?;
+        Ok(reminder)
+    }
+
+    pub fn mark_task_reminder_fired(
+        &self,
+        task_id: &str,
+        reminder_id: &str,
+    ) -> Result<(), AppError> {
+        let conn = self.get_conn()?;
+        let now = self.now_string();
+        conn.execute(
+            "UPDATE task_reminders SET fired_at = ?, updated_at = ? WHERE id = ? AND fired_at IS NULL",
+            params![now, now, reminder_id],
+        )?;
+        refresh_reminder_time(&conn, Some(task_id), &now)?;
+        Ok(())
+    }
+
+    /// 按当前时间重新汇总所有任务的下一个提醒时间，启动与同步合并后核对调度前调用。
+    pub fn refresh_task_reminder_times(&self) -> Result<(), AppError> {
+        let conn = self.get_conn()?;
+        refresh_reminder_time(&conn, None, &self.now_string())
+    }
+
+    pub fn update_task_notify_options(
+        &self,
+        task_id: &str,
+        options: &NotifyOptions,
+    ) -> Result<(), AppError> {
+        let conn = self.get_conn()?;
+        let now = self.now_string();
+        conn.execute(
+            "UPDATE tasks SET notify_options = ?, updated_at = ? WHERE id = ?",
+            params![notify_options_to_json(options)?, now, task_id],
         )?;
         Ok(())
     }
 
     pub fn complete_task(&self, task_id: &str) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         conn.execute(
             "UPDATE tasks SET status = 'COMPLETED', completed_at = ?, updated_at = ? WHERE id = ?",
             params![now, now, task_id],
         )?;
+        self.emit(TaskEvent::Completed(task_id.to_string()));
         Ok(())
     }
 
     pub fn uncomplete_task(&self, task_id: &str) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         conn.execute(
             "UPDATE tasks SET status = 'PENDING', completed_at = NULL, updated_at = ? WHERE id = ?",
             params![now, task_id],
@@ -317,27 +682,28 @@ impl DbManager {
 
     pub fn delete_task(&self, task_id: &str) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         conn.execute(
             "UPDATE tasks SET deleted_at = ?, updated_at = ? WHERE id = ?",
             params![now, now, task_id],
         )?;
+        self.emit(TaskEvent::Deleted(task_id.to_string()));
         Ok(())
     }
 
     pub fn create_recurring_task(&self, task: &RecurringTask) -> Result<RecurringTask, AppError> {
         let conn = self.get_conn()?;
         let id = Uuid::new_v4().to_string();
-        let now = now_string();
+        let now = self.now_string();
         conn.execute(
             "INSERT INTO recurring_tasks (
                 id, description, type, status, created_at, completed_at, interval_minutes,
                 last_triggered, next_trigger, is_paused, start_time, end_time,
                 repeat_mode, schedule_time, schedule_weekday, schedule_day, cron_expression,
-                updated_at, deleted_at
+                updated_at, deleted_at, notify_options
             )
              VALUES (?, ?, 'RECURRING', 'PENDING', ?, NULL, ?, NULL, ?, 0, ?, ?,
-                     ?, ?, ?, ?, ?, ?, NULL)",
+                     ?, ?, ?, ?, ?, ?, NULL, ?)",
             params![
                 id,
                 task.description.as_str(),
@@ -351,7 +717,8 @@ impl DbManager {
                 task.schedule_weekday,
                 task.schedule_day,
                 task.cron_expression.as_deref(),
-                now
+                now,
+                notify_options_to_json(&task.notify_options)?
             ],
         )?;
         Ok(RecurringTask {
@@ -375,17 +742,18 @@ impl DbManager {
             schedule_weekday: task.schedule_weekday,
             schedule_day: task.schedule_day,
             cron_expression: task.cron_expression.clone(),
+            notify_options: task.notify_options.clone(),
         })
     }
 
     pub fn update_recurring_task(&self, task: &RecurringTask) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         conn.execute(
             "UPDATE recurring_tasks
              SET description = ?, interval_minutes = ?, start_time = ?, end_time = ?,
                  repeat_mode = ?, schedule_time = ?, schedule_weekday = ?, schedule_day = ?, cron_expression = ?,
-                 is_paused = ?, next_trigger = ?, last_triggered = ?, updated_at = ?
+                 is_paused = ?, next_trigger = ?, last_triggered = ?, notify_options = ?, updated_at = ?
              WHERE id = ?",
             params![
                 task.description.as_str(),
@@ -400,6 +768,7 @@ impl DbManager {
                 if task.is_paused { 1 } else { 0 },
                 task.next_trigger.as_str(),
                 task.last_triggered.as_deref(),
+                notify_options_to_json(&task.notify_options)?,
                 now,
                 task.id.as_str()
             ],
@@ -409,7 +778,7 @@ impl DbManager {
 
     pub fn pause_recurring_task(&self, task_id: &str) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         conn.execute(
             "UPDATE recurring_tasks SET is_paused = 1, updated_at = ? WHERE id = ?",
             params![now, task_id],
@@ -419,7 +788,7 @@ impl DbManager {
 
     pub fn resume_recurring_task(&self, task_id: &str) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         conn.execute(
             "UPDATE recurring_tasks SET is_paused = 0, updated_at = ? WHERE id = ?",
             params![now, task_id],
@@ -429,7 +798,7 @@ impl DbManager {
 
     pub fn delete_recurring_task(&self, task_id: &str) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         conn.execute(
             "UPDATE recurring_tasks SET deleted_at = ?, updated_at = ? WHERE id = ?",
             params![now, now, task_id],
@@ -443,16 +812,69 @@ impl DbManager {
         description: &str,
         reminder_type: &str,
     ) -> Result<ReminderRecord, AppError> {
-        let conn = self.get_conn()?;
         let id = Uuid::new_v4().to_string();
-        let now = now_string();
+        self.insert_reminder_record(&id, reminder_id, description, reminder_type, None)
+    }
+
+    /// 为任务的一次触发创建提醒记录。记录 id 即触发点标识，多台设备对同一次触发写入的是同一行，
+    /// 同步合并时自然去重。已存在（通常是其他设备先触发并已同步过来）时返回已有的记录。
+    pub fn create_occurrence_record(
+        &self,
+        reminder_id: &str,
+        description: &str,
+        reminder_type: &str,
+        occurrence_id: &str,
+    ) -> Result<ReminderRecord, AppError> {
+        if let Some(record) = self.get_reminder_record(occurrence_id)? {
+            return Ok(record);
+        }
+        self.insert_reminder_record(
+            occurrence_id,
+            reminder_id,
+            description,
+            reminder_type,
+            Some(occurrence_id),
+        )
+    }
+
+    /// 提前提醒的记录：id 由触发点与提前量组成，occurrence_id 指向所属的触发点。
+    pub fn create_lead_record(
+        &self,
+        reminder_id: &str,
+        description: &str,
+        occurrence_id: &str,
+        minutes: i64,
+    ) -> Result<ReminderRecord, AppError> {
+        let id = lead_record_id(occurrence_id, minutes);
+        if let Some(record) = self.get_reminder_record(&id)? {
+            return Ok(record);
+        }
+        self.insert_reminder_record(
+            &id,
+            reminder_id,
+            description,
+            LEAD_REMINDER_TYPE,
+            Some(occurrence_id),
+        )
+    }
+
+    fn insert_reminder_record(
+        &self,
+        id: &str,
+        reminder_id: &str,
+        description: &str,
+        reminder_type: &str,
+        occurrence_id: Option<&str>,
+    ) -> Result<ReminderRecord, AppError> {
+        let conn = self.get_conn()?;
+        let now = self.now_string();
         conn.execute(
-            "INSERT INTO reminder_records (id, reminder_id, description, type, trigger_time, close_time, action, updated_at, deleted_at)
-             VALUES (?, ?, ?, ?, ?, NULL, 'PENDING', ?, NULL)",
-            params![id, reminder_id, description, reminder_type, now, now],
+            "INSERT INTO reminder_records (id, reminder_id, description, type, trigger_time, close_time, action, updated_at, deleted_at, occurrence_id)
+             VALUES (?, ?, ?, ?, ?, NULL, 'PENDING', ?, NULL, ?)",
+            params![id, reminder_id, description, reminder_type, now, now, occurrence_id],
         )?;
         Ok(ReminderRecord {
-            id,
+            id: id.to_string(),
             reminder_id: reminder_id.to_string(),
             description: description.to_string(),
             reminder_type: reminder_type.to_string(),
@@ -461,6 +883,11 @@ impl DbManager {
             action: "PENDING".to_string(),
             updated_at: Some(now),
             deleted_at: None,
+            delivery_error: None,
+            habit_state: None,
+            delivery_state: None,
+            delivered_at: None,
+            occurrence_id: occurrence_id.map(str::to_string),
         })
     }
 
@@ -470,17 +897,67 @@ impl DbManager {
         action: &str,
     ) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
+        // 循环提醒同时记下默认的打卡状态，已手动标记过的不覆盖。
         conn.execute(
-            "UPDATE reminder_records SET action = ?, close_time = ?, updated_at = ? WHERE id = ?",
-            params![action, now, now, record_id],
+            "UPDATE reminder_records
+             SET action = ?, close_time = ?, updated_at = ?,
+                 habit_state = CASE WHEN type = 'RECURRING' AND habit_state IS NULL THEN ? ELSE habit_state END
+             WHERE id = ?",
+            params![action, now, now, habit_state_for_action(action), record_id],
         )?;
         Ok(())
     }
 
+    pub fn update_reminder_record_habit_state(
+        &self,
+        record_id: &str,
+        habit_state: Option<&str>,
+    ) -> Result<(), AppError> {
+        let conn = self.get_conn()?;
+        let updated = conn.execute(
+            "UPDATE reminder_records SET habit_state = ?, updated_at = ?
+             WHERE id = ? AND type = 'RECURRING' AND deleted_at IS NULL",
+            params![habit_state, self.now_string(), record_id],
+        )?;
+        if updated == 0 {
+            return Err(AppError::Invalid("只有循环提醒的记录可以打卡".to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn update_reminder_record_delivery_error(
+        &self,
+        record_id: &str,
+        error: Option<&str>,
+    ) -> Result<(), AppError> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE reminder_records SET delivery_error = ?, updated_at = ? WHERE id = ?",
+            params![error, self.now_string(), record_id],
+        )?;
+        Ok(())
+    }
+
+    /// 暂缓的提醒补发时记下实际弹出时间，返回更新后的记录。
+    pub fn mark_reminder_record_delivered_late(
+        &self,
+        record_id: &str,
+    ) -> Result<Option<ReminderRecord>, AppError> {
+        let conn = self.get_conn()?;
+        let now = self.now_string();
+        conn.execute(
+            "UPDATE reminder_records SET delivery_state = 'DELIVERED_LATE', delivered_at = ?, updated_at = ?
+             WHERE id = ?",
+            params![now, now, record_id],
+        )?;
+        drop(conn);
+        self.get_reminder_record(record_id)
+    }
+
     pub fn delete_reminder_record(&self, record_id: &str) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         conn.execute(
             "UPDATE reminder_records SET deleted_at = ?, updated_at = ? WHERE id = ?",
             params![now, now, record_id],
@@ -490,7 +967,7 @@ impl DbManager {
 
     pub fn delete_reminder_records(&self, ids: &[String]) -> Result<(), AppError> {
         let mut conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         let tx = conn.transaction()?;
         for id in ids {
             tx.execute(
@@ -502,6 +979,226 @@ impl DbManager {
         Ok(())
     }
 
+    pub fn insert_time_entry(
+        &self,
+        task_id: &str,
+        started_at: &str,
+        ended_at: &str,
+        duration_seconds: i64,
+        source: &str,
+    ) -> Result<TimeEntry, AppError> {
+        let conn = self.get_conn()?;
+        let id = Uuid::new_v4().to_string();
+        let now = self.now_string();
+        conn.execute(
+            "INSERT INTO time_entries (id, task_id, started_at, ended_at, duration_seconds, source, updated_at, deleted_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, NULL)",
+            params![id, task_id, started_at, ended_at, duration_seconds, source, now],
+        )?;
+        Ok(TimeEntry {
+            id,
+            task_id: task_id.to_string(),
+            started_at: started_at.to_string(),
+            ended_at: ended_at.to_string(),
+            duration_seconds,
+            source: source.to_string(),
+            updated_at: Some(now),
+            deleted_at: None,
+        })
+    }
+
+    pub fn list_time_entries(&self, task_id: &str) -> Result<Vec<TimeEntry>, AppError> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, task_id, started_at, ended_at, duration_seconds, source, updated_at, deleted_at
+             FROM time_entries
+             WHERE task_id = ? AND deleted_at IS NULL
+             ORDER BY started_at DESC",
+        )?;
+        let rows = stmt.query_map([task_id], time_entry_from_row)?;
+        Ok(rows.filter_map(Result::ok).collect())
+    }
+
+    pub fn delete_time_entry(&self, entry_id: &str) -> Result<(), AppError> {
+        let conn = self.get_conn()?;
+        let now = self.now_string();
+        conn.execute(
+            "UPDATE time_entries SET deleted_at = ?, updated_at = ? WHERE id = ?",
+            params![now, now, entry_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn list_timers(&self) -> Result<Vec<Timer>, AppError> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, label, duration_seconds, status, ends_at, remaining_seconds, created_at, updated_at
+             FROM timers
+             ORDER BY created_at ASC",
+        )?;
+        let rows = stmt.query_map([], timer_from_row)?;
+        Ok(rows.filter_map(Result::ok).collect())
+    }
+
+    pub fn get_timer(&self, timer_id: &str) -> Result<Option<Timer>, AppError> {
+        let conn = self.get_conn()?;
+        let timer = conn
+            .query_row(
+                "SELECT id, label, duration_seconds, status, ends_at, remaining_seconds, created_at, updated_at
+                 FROM timers WHERE id = ?",
+                [timer_id],
+                timer_from_row,
+            )
+            .optional()?;
+        Ok(timer)
+    }
+
+    pub fn create_timer(
+        &self,
+        label: &str,
+        duration_seconds: i64,
+        ends_at: &str,
+    ) -> Result<Timer, AppError> {
+        let conn = self.get_conn()?;
+        let id = Uuid::new_v4().to_string();
+        let now = self.now_string();
+        conn.execute(
+            "INSERT INTO timers (id, label, duration_seconds, status, ends_at, remaining_seconds, created_at, updated_at)
+             VALUES (?, ?, ?, 'RUNNING', ?, ?, ?, ?)",
+            params![id, label, duration_seconds, ends_at, duration_seconds, now, now],
+        )?;
+        Ok(Timer {
+            id,
+            label: label.to_string(),
+            duration_seconds,
+            status: "RUNNING".to_string(),
+            ends_at: Some(ends_at.to_string()),
+            remaining_seconds: duration_seconds,
+            created_at: now.clone(),
+            updated_at: now,
+        })
+    }
+
+    /// 运行中的计时器记结束时间，暂停的计时器记剩余秒数。
+    pub fn update_timer_state(
+        &self,
+        timer_id: &str,
+        status: &str,
+        ends_at: Option<&str>,
+        remaining_seconds: i64,
+    ) -> Result<(), AppError> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE timers SET status = ?, ends_at = ?, remaining_seconds = ?, updated_at = ? WHERE id = ?",
+            params![status, ends_at, remaining_seconds, self.now_string(), timer_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn delete_timer(&self, timer_id: &str) -> Result<(), AppError> {
+        let conn = self.get_conn()?;
+        conn.execute("DELETE FROM timers WHERE id = ?", [timer_id])?;
+        Ok(())
+    }
+
+    pub fn list_webhook_channels(&self) -> Result<Vec<WebhookChannel>, AppError> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, name, preset, url, secret, events, apply_to_all, enabled, created_at, updated_at
+             FROM webhook_channels
+             ORDER BY created_at ASC",
+        )?;
+        let rows = stmt.query_map([], webhook_channel_from_row)?;
+        Ok(rows.filter_map(Result::ok).collect())
+    }
+
+    pub fn save_webhook_channel(
+        &self,
+        channel: &WebhookChannel,
+    ) -> Result<WebhookChannel, AppError> {
+        let conn = self.get_conn()?;
+        let now = self.now_string();
+        let mut saved = channel.clone();
+        if saved.id.trim().is_empty() {
+            saved.id = Uuid::new_v4().to_string();
+            saved.created_at = now.clone();
+        } else if saved.created_at.trim().is_empty() {
+            saved.created_at = now.clone();
+        }
+        saved.updated_at = now;
+        conn.execute(
+            "INSERT INTO webhook_channels (id, name, preset, url, secret, events, apply_to_all, enabled, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET
+                 name = excluded.name, preset = excluded.preset, url = excluded.url, secret = excluded.secret,
+                 events = excluded.events, apply_to_all = excluded.apply_to_all, enabled = excluded.enabled,
+                 updated_at = excluded.updated_at",
+            params![
+                saved.id,
+                saved.name,
+                saved.preset,
+                saved.url,
+                saved.secret,
+                saved.events.join(","),
+                if saved.apply_to_all { 1 } else { 0 },
+                if saved.enabled { 1 } else { 0 },
+                saved.created_at,
+                saved.updated_at
+            ],
+        )?;
+        Ok(saved)
+    }
+
+    pub fn delete_webhook_channel(&self, channel_id: &str) -> Result<(), AppError> {
+        let conn = self.get_conn()?;
+        conn.execute("DELETE FROM webhook_channels WHERE id = ?", [channel_id])?;
+        Ok(())
+    }
+
+    pub fn insert_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<(), AppError> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "INSERT INTO webhook_deliveries (id, channel_id, record_id, event, success, attempts, status_code, error, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                delivery.id,
+                delivery.channel_id,
+                delivery.record_id,
+                delivery.event,
+                if delivery.success { 1 } else { 0 },
+                delivery.attempts,
+                delivery.status_code,
+                delivery.error,
+                delivery.created_at
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn list_webhook_deliveries(&self, limit: i64) -> Result<Vec<WebhookDelivery>, AppError> {
+        let conn = self.get_conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, channel_id, record_id, event, success, attempts, status_code, error, created_at
+             FROM webhook_deliveries
+             ORDER BY created_at DESC
+             LIMIT ?",
+        )?;
+        let rows = stmt.query_map([limit.max(1)], |row| {
+            Ok(WebhookDelivery {
+                id: row.get(0)?,
+                channel_id: row.get(1)?,
+                record_id: row.get(2)?,
+                event: row.get(3)?,
+                success: row.get::<_, i64>(4)? == 1,
+                attempts: row.get(5)?,
+                status_code: row.get(6)?,
+                error: row.get(7)?,
+                created_at: row.get(8)?,
+            })
+        })?;
+        Ok(rows.filter_map(Result::ok).collect())
+    }
+
     pub fn list_sticky_notes(&self) -> Result<Vec<StickyNote>, AppError> {
         let conn = self.get_conn()?;
         let mut stmt = conn.prepare(
@@ -510,7 +1207,8 @@ impl DbManager {
              WHERE deleted_at IS NULL AND status != 'COMPLETED'
              ORDER BY created_at ASC",
         )?;
-        let rows = stmt.query_map([], sticky_note_from_task_row)?;
+        let now = self.now_string();
+        let rows = stmt.query_map([], |row| sticky_note_from_task_row(row, &now))?;
         Ok(rows.filter_map(Result::ok).collect())
     }
 
@@ -521,7 +1219,8 @@ impl DbManager {
              FROM tasks
              WHERE id = ?",
         )?;
-        stmt.query_row([note_id], sticky_note_from_task_row)
+        let now = self.now_string();
+        stmt.query_row([note_id], |row| sticky_note_from_task_row(row, &now))
             .optional()
             .map_err(AppError::from)
     }
@@ -534,7 +1233,7 @@ impl DbManager {
         default_y: Option<f64>,
     ) -> Result<StickyNote, AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         let existing = self.get_sticky_note(note_id)?;
         let keep_existing_position = existing
             .as_ref()
@@ -613,7 +1312,7 @@ impl DbManager {
         default_height: Option<f64>,
     ) -> Result<StickyNote, AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         let id = Uuid::new_v4().to_string();
         let x = default_x
             .filter(|value| value.is_finite())
@@ -643,6 +1342,7 @@ impl DbManager {
              VALUES (?, ?, 'ONE_TIME', 'PENDING', ?, NULL, NULL, ?, NULL, ?, ?, ?, ?, ?, 1)",
             params![id, resolved_title, now, now, resolved_content, x, y, width, height],
         )?;
+        self.emit(TaskEvent::Created(id.clone()));
         Ok(StickyNote {
             task_id: id,
             title: resolved_title,
@@ -661,7 +1361,7 @@ impl DbManager {
 
     pub fn save_sticky_note_content(&self, task_id: &str, content: &str) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         conn.execute(
             "UPDATE tasks
              SET sticky_content = ?, sticky_is_open = 1, updated_at = ?
@@ -673,7 +1373,7 @@ impl DbManager {
 
     pub fn update_sticky_note_title(&self, task_id: &str, title: &str) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         let resolved_title = if title.trim().is_empty() {
             "便签".to_string()
         } else {
@@ -696,7 +1396,7 @@ impl DbManager {
 
     pub fn move_sticky_note(&self, task_id: &str, x: f64, y: f64) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         conn.execute(
             "UPDATE tasks
              SET sticky_pos_x = ?, sticky_pos_y = ?, sticky_is_open = 1, updated_at = ?
@@ -713,7 +1413,7 @@ impl DbManager {
         height: f64,
     ) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         conn.execute(
             "UPDATE tasks
              SET sticky_width = ?, sticky_height = ?, sticky_is_open = 1, updated_at = ?
@@ -730,7 +1430,7 @@ impl DbManager {
 
     pub fn set_sticky_note_pinned(&self, task_id: &str, pinned: bool) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         conn.execute(
             "UPDATE tasks
              SET sticky_is_pinned = ?, sticky_is_open = 1, updated_at = ?
@@ -757,7 +1457,7 @@ impl DbManager {
 
     pub fn close_sticky_note(&self, task_id: &str) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = now_string();
+        let now = self.now_string();
         conn.execute(
             "UPDATE tasks SET sticky_is_open = 0, updated_at = ? WHERE id = ?",
             params![now, task_id],
@@ -772,7 +1472,21 @@ impl DbManager {
                    webdav_enabled, webdav_url, webdav_username, webdav_password,
                    webdav_root_path, webdav_sync_interval_minutes, webdav_last_sync_time,
                    webdav_last_local_change_time, webdav_last_sync_status, webdav_last_sync_error,
-                   webdav_device_id, notification_theme
+                   webdav_device_id, notification_theme,
+                   notification_corner, notification_monitor, notification_monitor_name,
+                   notification_width, notification_height, notification_margin,
+                   smtp_enabled, smtp_host, smtp_port, smtp_security,
+                   smtp_username, smtp_password, smtp_from, smtp_to,
+                   mqtt_enabled, mqtt_host, mqtt_port, mqtt_username,
+                   mqtt_password, mqtt_client_id, mqtt_topic_prefix,
+                   sound_default, sound_loop, sound_volume_start, sound_volume_max,
+                   sound_escalate_seconds, quiet_hours_enabled, quiet_hours_start, quiet_hours_end,
+                   tts_engine, tts_voice, tts_rate,
+                   digest_enabled, digest_time, digest_workdays_only, digest_last_date,
+                   report_enabled, report_weekday, report_time, report_format, report_dir, report_last_date,
+                   focus_work_minutes, focus_short_break_minutes, focus_long_break_minutes, focus_long_break_every,
+                   break_enabled, break_active_minutes, break_idle_reset_minutes,
+                   hold_when_away, away_idle_minutes, default_lead_minutes, missed_policy
                    FROM settings WHERE id = 1";
         let mut stmt = conn.prepare(sql)?;
         let row = stmt.query_row([], |row| {
@@ -791,6 +1505,12 @@ impl DbManager {
             let notification_theme: String = row
                 .get::<_, Option<String>>(22)?
                 .unwrap_or_else(|| "app".to_string());
+            let notification_corner = normalize_notification_corner(
+                &row.get::<_, Option<String>>(23)?.unwrap_or_default(),
+            );
+            let notification_monitor = normalize_notification_monitor(
+                &row.get::<_, Option<String>>(24)?.unwrap_or_default(),
+            );
             Ok(AppSettings {
                 auto_start_enabled: row.get::<_, i64>(0)? == 1,
                 sound_enabled: row.get::<_, i64>(1)? == 1,
@@ -815,6 +1535,86 @@ impl DbManager {
                 webdav_last_sync_error: row.get(20)?,
                 webdav_device_id,
                 notification_theme,
+                notification_corner,
+                notification_monitor,
+                notification_monitor_name: row.get::<_, Option<String>>(25)?.unwrap_or_default(),
+                notification_width: normalize_notification_dimension(row.get(26)?, 280.0, 960.0),
+                notification_height: normalize_notification_dimension(row.get(27)?, 160.0, 720.0),
+                notification_margin: normalize_notification_margin(row.get(28)?),
+                smtp_enabled: row.get::<_, Option<i64>>(29)?.unwrap_or(0) == 1,
+                smtp_host: row.get::<_, Option<String>>(30)?.unwrap_or_default(),
+                smtp_port: normalize_smtp_port(row.get(31)?),
+                smtp_security: normalize_smtp_security(
+                    &row.get::<_, Option<String>>(32)?.unwrap_or_default(),
+                ),
+                smtp_username: row.get::<_, Option<String>>(33)?.unwrap_or_default(),
+                smtp_password: row.get::<_, Option<String>>(34)?.unwrap_or_default(),
+                smtp_from: row.get::<_, Option<String>>(35)?.unwrap_or_default(),
+                smtp_to: row.get::<_, Option<String>>(36)?.unwrap_or_default(),
+                mqtt_enabled: row.get::<_, Option<i64>>(37)?.unwrap_or(0) == 1,
+                mqtt_host: row.get::<_, Option<String>>(38)?.unwrap_or_default(),
+                mqtt_port: normalize_mqtt_port(row.get(39)?),
+                mqtt_username: row.get::<_, Option<String>>(40)?.unwrap_or_default(),
+                mqtt_password: row.get::<_, Option<String>>(41)?.unwrap_or_default(),
+                mqtt_client_id: row.get::<_, Option<String>>(42)?.unwrap_or_default(),
+                mqtt_topic_prefix: normalize_mqtt_topic_prefix(
+                    &row.get::<_, Option<String>>(43)?.unwrap_or_default(),
+                ),
+                sound_default: row
+                    .get::<_, Option<String>>(44)?
+                    .and_then(|value| normalize_sound_id(&value))
+                    .unwrap_or_else(|| DEFAULT_SOUND.to_string()),
+                sound_loop: row.get::<_, Option<i64>>(45)?.unwrap_or(1) == 1,
+                sound_volume_start: normalize_sound_volume(row.get(46)?, 0.4),
+                sound_volume_max: normalize_sound_volume(row.get(47)?, 1.0),
+                sound_escalate_seconds: row.get::<_, Option<i64>>(48)?.unwrap_or(60).max(0),
+                quiet_hours_enabled: row.get::<_, Option<i64>>(49)?.unwrap_or(0) == 1,
+                quiet_hours_start: normalize_quiet_time(
+                    &row.get::<_, Option<String>>(50)?.unwrap_or_default(),
+                    "22:00",
+                ),
+                quiet_hours_end: normalize_quiet_time(
+                    &row.get::<_, Option<String>>(51)?.unwrap_or_default(),
+                    "07:00",
+                ),
+                tts_engine: normalize_speech_engine(
+                    &row.get::<_, Option<String>>(52)?.unwrap_or_default(),
+                ),
+                tts_voice: row.get::<_, Option<String>>(53)?.unwrap_or_default(),
+                tts_rate: normalize_speech_rate(row.get::<_, Option<f64>>(54)?.unwrap_or(1.0)),
+                digest_enabled: row.get::<_, Option<i64>>(55)?.unwrap_or(0) == 1,
+                digest_time: normalize_quiet_time(
+                    &row.get::<_, Option<String>>(56)?.unwrap_or_default(),
+                    "09:00",
+                ),
+                digest_workdays_only: row.get::<_, Option<i64>>(57)?.unwrap_or(1) == 1,
+                digest_last_date: row.get(58)?,
+                report_enabled: row.get::<_, Option<i64>>(59)?.unwrap_or(0) == 1,
+                report_weekday: normalize_report_weekday(row.get(60)?),
+                report_time: normalize_quiet_time(
+                    &row.get::<_, Option<String>>(61)?.unwrap_or_default(),
+                    "17:00",
+                ),
+                report_format: normalize_report_format(
+                    &row.get::<_, Option<String>>(62)?.unwrap_or_default(),
+                ),
+                report_dir: row.get::<_, Option<String>>(63)?.unwrap_or_default(),
+                report_last_date: row.get(64)?,
+                focus_work_minutes: normalize_focus_minutes(row.get(65)?, 25),
+                focus_short_break_minutes: normalize_focus_minutes(row.get(66)?, 5),
+                focus_long_break_minutes: normalize_focus_minutes(row.get(67)?, 15),
+                focus_long_break_every: normalize_focus_rounds(row.get(68)?),
+                break_enabled: row.get::<_, Option<i64>>(69)?.unwrap_or(0) == 1,
+                break_active_minutes: normalize_focus_minutes(row.get(70)?, 50),
+                break_idle_reset_minutes: normalize_focus_minutes(row.get(71)?, 5),
+                hold_when_away: row.get::<_, Option<i64>>(72)?.unwrap_or(1) == 1,
+                away_idle_minutes: normalize_focus_minutes(row.get(73)?, 5),
+                default_lead_minutes: parse_lead_list(
+                    &row.get::<_, Option<String>>(74)?.unwrap_or_default(),
+                ),
+                missed_policy: normalize_missed_policy(
+                    &row.get::<_, Option<String>>(75)?.unwrap_or_default(),
+                ),
             })
         })?;
         Ok(row)
@@ -830,7 +1630,23 @@ impl DbManager {
                  webdav_enabled = ?, webdav_url = ?, webdav_username = ?, webdav_password = ?,
                  webdav_root_path = ?, webdav_sync_interval_minutes = ?, webdav_last_sync_time = ?,
                  webdav_last_local_change_time = ?, webdav_last_sync_status = ?, webdav_last_sync_error = ?,
-                 webdav_device_id = ?, notification_theme = ?
+                 webdav_device_id = ?, notification_theme = ?,
+                 notification_corner = ?, notification_monitor = ?, notification_monitor_name = ?,
+                 notification_width = ?, notification_height = ?, notification_margin = ?,
+                 smtp_enabled = ?, smtp_host = ?, smtp_port = ?, smtp_security = ?,
+                 smtp_username = ?, smtp_password = ?, smtp_from = ?, smtp_to = ?,
+                 mqtt_enabled = ?, mqtt_host = ?, mqtt_port = ?, mqtt_username = ?,
+                 mqtt_password = ?, mqtt_client_id = ?, mqtt_topic_prefix = ?,
+                 sound_default = ?, sound_loop = ?, sound_volume_start = ?, sound_volume_max = ?,
+                 sound_escalate_seconds = ?, quiet_hours_enabled = ?, quiet_hours_start = ?, quiet_hours_end = ?,
+                 tts_engine = ?, tts_voice = ?, tts_rate = ?,
+                 digest_enabled = ?, digest_time = ?, digest_workdays_only = ?,
+                 report_enabled = ?, report_weekday = ?, report_time = ?, report_format = ?, report_dir = ?,
+                 focus_work_minutes = ?, focus_short_break_minutes = ?, focus_long_break_minutes = ?,
+                 focus_long_break_every = ?,
+                 break_enabled = ?, break_active_minutes = ?, break_idle_reset_minutes = ?,
+                 hold_when_away = ?, away_idle_minutes = ?, default_lead_minutes = ?,
+                 missed_policy = ?
              WHERE id = 1",
             params![
                 if settings.auto_start_enabled { 1 } else { 0 },
@@ -856,18 +1672,123 @@ impl DbManager {
                 settings.webdav_last_sync_error,
                 settings.webdav_device_id,
                 settings.notification_theme,
+                normalize_notification_corner(&settings.notification_corner),
+                normalize_notification_monitor(&settings.notification_monitor),
+                settings.notification_monitor_name.trim(),
+                normalize_notification_dimension(settings.notification_width, 280.0, 960.0),
+                normalize_notification_dimension(settings.notification_height, 160.0, 720.0),
+                normalize_notification_margin(Some(settings.notification_margin)),
+                if settings.smtp_enabled { 1 } else { 0 },
+                settings.smtp_host.trim(),
+                normalize_smtp_port(Some(settings.smtp_port)),
+                normalize_smtp_security(&settings.smtp_security),
+                settings.smtp_username.trim(),
+                settings.smtp_password,
+                settings.smtp_from.trim(),
+                settings.smtp_to.trim(),
+                if settings.mqtt_enabled { 1 } else { 0 },
+                settings.mqtt_host.trim(),
+                normalize_mqtt_port(Some(settings.mqtt_port)),
+                settings.mqtt_username.trim(),
+                settings.mqtt_password,
+                settings.mqtt_client_id.trim(),
+                normalize_mqtt_topic_prefix(&settings.mqtt_topic_prefix),
+                normalize_sound_id(&settings.sound_default)
+                    .unwrap_or_else(|| DEFAULT_SOUND.to_string()),
+                if settings.sound_loop { 1 } else { 0 },
+                normalize_sound_volume(Some(settings.sound_volume_start), 0.4),
+                normalize_sound_volume(Some(settings.sound_volume_max), 1.0),
+                settings.sound_escalate_seconds.max(0),
+                if settings.quiet_hours_enabled { 1 } else { 0 },
+                normalize_quiet_time(&settings.quiet_hours_start, "22:00"),
+                normalize_quiet_time(&settings.quiet_hours_end, "07:00"),
+                normalize_speech_engine(&settings.tts_engine),
+                settings.tts_voice.trim(),
+                normalize_speech_rate(settings.tts_rate),
+                if settings.digest_enabled { 1 } else { 0 },
+                normalize_quiet_time(&settings.digest_time, "09:00"),
+                if settings.digest_workdays_only { 1 } else { 0 },
+                if settings.report_enabled { 1 } else { 0 },
+                normalize_report_weekday(Some(settings.report_weekday)),
+                normalize_quiet_time(&settings.report_time, "17:00"),
+                normalize_report_format(&settings.report_format),
+                settings.report_dir.trim(),
+                normalize_focus_minutes(Some(settings.focus_work_minutes), 25),
+                normalize_focus_minutes(Some(settings.focus_short_break_minutes), 5),
+                normalize_focus_minutes(Some(settings.focus_long_break_minutes), 15),
+                normalize_focus_rounds(Some(settings.focus_long_break_every)),
+                if settings.break_enabled { 1 } else { 0 },
+                normalize_focus_minutes(Some(settings.break_active_minutes), 50),
+                normalize_focus_minutes(Some(settings.break_idle_reset_minutes), 5),
+                if settings.hold_when_away { 1 } else { 0 },
+                normalize_focus_minutes(Some(settings.away_idle_minutes), 5),
+                format_lead_list(&normalize_lead_minutes(&settings.default_lead_minutes)?),
+                normalize_missed_policy(&settings.missed_policy),
             ],
         )?;
         Ok(())
     }
 
+    pub fn load_pause_state(&self) -> Result<PauseState, AppError> {
+        let conn = self.get_conn()?;
+        let state = conn
+            .query_row(
+                "SELECT paused_at, resume_at, updated_at FROM pause_state WHERE id = ?",
+                [GLOBAL_PAUSE_ID],
+                |row| {
+                    let paused_at: Option<String> = row.get(0)?;
+                    Ok(PauseState {
+                        paused: paused_at.is_some(),
+                        paused_at,
+                        resume_at: row.get(1)?,
+                        updated_at: row.get(2)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(state.unwrap_or_default())
+    }
+
+    /// `paused_at` 为 None 表示已恢复；保留这一行，让恢复也能同步到其他设备。
+    pub fn save_pause_state(
+        &self,
+        paused_at: Option<&str>,
+        resume_at: Option<&str>,
+    ) -> Result<(), AppError> {
+        let conn = self.get_conn()?;
+        let now = self.now_string();
+        conn.execute(
+            "REPLACE INTO pause_state (id, paused_at, resume_at, updated_at) VALUES (?, ?, ?, ?)",
+            params![GLOBAL_PAUSE_ID, paused_at, resume_at, now],
+        )?;
+        Ok(())
+    }
+
+    pub fn update_digest_last_date(&self, date: &str) -> Result<(), AppError> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE settings SET digest_last_date = ? WHERE id = 1",
+            [date],
+        )?;
+        Ok(())
+    }
+
+    pub fn update_report_last_date(&self, date: &str) -> Result<(), AppError> {
+        let conn = self.get_conn()?;
+        conn.execute(
+            "UPDATE settings SET report_last_date = ? WHERE id = 1",
+            [date],
+        )?;
+        Ok(())
+    }
+
     pub fn update_sync_status(
         &self,
         status: &str,
         error: Option<String>,
     ) -> Result<AppSettings, AppError> {
         let mut settings = self.load_settings()?;
-        let now = now_string();
+        let now = self.now_string();
         settings.webdav_last_sync_time = Some(now.clone());
         settings.webdav_last_sync_status = Some(status.to_string());
         settings.webdav_last_sync_error = error;
@@ -877,14 +1798,14 @@ impl DbManager {
 
     pub fn mark_local_change(&self) -> Result<(), AppError> {
         let mut settings = self.load_settings()?;
-        settings.webdav_last_local_change_time = Some(now_string());
+        settings.webdav_last_local_change_time = Some(self.now_string());
         self.save_settings(&settings)?;
         Ok(())
     }
 
     pub fn cleanup_data(&self) -> Result<(), AppError> {
         let conn = self.get_conn()?;
-        let now = Local::now().naive_local();
+        let now = self.clock.now();
         let completed_cutoff = (now - chrono::Duration::days(30))
             .format("%Y-%m-%dT%H:%M:%S")
             .to_string();
@@ -898,7 +1819,7 @@ impl DbManager {
         )?;
         conn.execute(
             "DELETE FROM tasks WHERE status = 'COMPLETED' AND deleted_at IS NULL AND completed_at IS NOT NULL AND completed_at < ?",
-            [completed_cutoff],
+            [completed_cutoff.clone()],
         )?;
         conn.execute(
             "DELETE FROM tasks WHERE id IN (\n                SELECT id FROM tasks\n                WHERE status = 'COMPLETED' AND deleted_at IS NULL\n                ORDER BY completed_at DESC\n                LIMIT -1 OFFSET 100\n            )",
@@ -906,8 +1827,24 @@ impl DbManager {
         )?;
         conn.execute(
             "DELETE FROM recurring_tasks WHERE deleted_at IS NOT NULL AND deleted_at < ?",
+            [deleted_cutoff.clone()],
+        )?;
+        conn.execute(
+            "DELETE FROM time_entries WHERE deleted_at IS NOT NULL AND deleted_at < ?",
+            [deleted_cutoff.clone()],
+        )?;
+        conn.execute(
+            "DELETE FROM task_reminders WHERE deleted_at IS NOT NULL AND deleted_at < ?",
             [deleted_cutoff],
         )?;
+        conn.execute(
+            "DELETE FROM task_reminders WHERE task_id NOT IN (SELECT id FROM tasks)",
+            [],
+        )?;
+        conn.execute(
+            "DELETE FROM webhook_deliveries WHERE created_at < ?",
+            [completed_cutoff],
+        )?;
         Ok(())
     }
 
@@ -932,6 +1869,75 @@ fn task_from_row(row: &rusqlite::Row<'_>) -> Result<Task, rusqlite::Error> {
         reminder_time: row.get(7)?,
         updated_at: row.get(8)?,
         deleted_at: row.get(9)?,
+        notify_options: notify_options_from_json(row.get(10)?),
+        tracked_seconds: row.get(11)?,
+        due_at: row.get(12)?,
+        overdue: false,
+    })
+}
+
+fn query_task_reminders(conn: &Connection, task_id: &str) -> Result<Vec<TaskReminder>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, task_id, remind_at, fired_at, created_at, updated_at, deleted_at, is_snooze
+         FROM task_reminders
+         WHERE task_id = ? AND deleted_at IS NULL AND is_snooze = 0
+         ORDER BY remind_at ASC",
+    )?;
+    let rows = stmt.query_map([task_id], task_reminder_from_row)?;
+    Ok(rows.filter_map(Result::ok).collect())
+}
+
+fn task_reminder_from_row(row: &rusqlite::Row<'_>) -> Result<TaskReminder, rusqlite::Error> {
+    Ok(TaskReminder {
+        id: row.get(0)?,
+        task_id: row.get(1)?,
+        remind_at: row.get(2)?,
+        fired_at: row.get(3)?,
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+        deleted_at: row.get(6)?,
+        is_snooze: row.get::<_, i64>(7)? == 1,
+    })
+}
+
+/// tasks.reminder_time 汇总为 `now` 之后最早一个未触发的提醒时间，没有时取最后一个；
+/// 只处理有提醒时间记录的任务，`task_id` 为 None 时处理全部。不改 updated_at，不算本地改动。
+fn refresh_reminder_time(
+    conn: &Connection,
+    task_id: Option<&str>,
+    now: &str,
+) -> Result<(), AppError> {
+    conn.execute(
+        "UPDATE tasks SET reminder_time = COALESCE(
+             (SELECT MIN(remind_at) FROM task_reminders
+              WHERE task_id = tasks.id AND deleted_at IS NULL AND fired_at IS NULL AND remind_at > ?1),
+             (SELECT MAX(remind_at) FROM task_reminders
+              WHERE task_id = tasks.id AND deleted_at IS NULL))
+         WHERE id IN (SELECT task_id FROM task_reminders) AND (?2 IS NULL OR id = ?2)",
+        params![now, task_id],
+    )?;
+    Ok(())
+}
+
+fn format_db_time(value: NaiveDateTime) -> String {
+    value.format("%Y-%m-%dT%H:%M:%S").to_string()
+}
+
+fn with_overdue(mut task: Task, now: NaiveDateTime) -> Task {
+    task.overdue = is_overdue(&task, now);
+    task
+}
+
+fn time_entry_from_row(row: &rusqlite::Row<'_>) -> Result<TimeEntry, rusqlite::Error> {
+    Ok(TimeEntry {
+        id: row.get(0)?,
+        task_id: row.get(1)?,
+        started_at: row.get(2)?,
+        ended_at: row.get(3)?,
+        duration_seconds: row.get(4)?,
+        source: row.get(5)?,
+        updated_at: row.get(6)?,
+        deleted_at: row.get(7)?,
     })
 }
 
@@ -960,6 +1966,7 @@ fn recurring_from_row(row: &rusqlite::Row<'_>) -> Result<RecurringTask, rusqlite
         cron_expression: row.get(16)?,
         updated_at: row.get(17)?,
         deleted_at: row.get(18)?,
+        notify_options: notify_options_from_json(row.get(19)?),
     })
 }
 
@@ -974,10 +1981,18 @@ fn record_from_row(row: &rusqlite::Row<'_>) -> Result<ReminderRecord, rusqlite::
         action: row.get(6)?,
         updated_at: row.get(7)?,
         deleted_at: row.get(8)?,
+        delivery_error: row.get(9)?,
+        habit_state: row.get(10)?,
+        delivery_state: row.get(11)?,
+        delivered_at: row.get(12)?,
+        occurrence_id: row.get(13)?,
     })
 }
 
-fn sticky_note_from_task_row(row: &rusqlite::Row<'_>) -> Result<StickyNote, rusqlite::Error> {
+fn sticky_note_from_task_row(
+    row: &rusqlite::Row<'_>,
+    now: &str,
+) -> Result<StickyNote, rusqlite::Error> {
     Ok(StickyNote {
         task_id: row.get(0)?,
         title: row
@@ -998,12 +2013,51 @@ fn sticky_note_from_task_row(row: &rusqlite::Row<'_>) -> Result<StickyNote, rusq
         created_at: row.get(9)?,
         updated_at: row
             .get::<_, Option<String>>(10)?
-            .unwrap_or_else(|| now_string()),
+            .unwrap_or_else(|| now.to_string()),
+    })
+}
+
+fn timer_from_row(row: &rusqlite::Row<'_>) -> Result<Timer, rusqlite::Error> {
+    Ok(Timer {
+        id: row.get(0)?,
+        label: row.get(1)?,
+        duration_seconds: row.get(2)?,
+        status: row.get(3)?,
+        ends_at: row.get(4)?,
+        remaining_seconds: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
     })
 }
 
-fn now_string() -> String {
-    Local::now().format("%Y-%m-%dT%H:%M:%S").to_string()
+fn webhook_channel_from_row(row: &rusqlite::Row<'_>) -> Result<WebhookChannel, rusqlite::Error> {
+    let events: String = row.get(5)?;
+    Ok(WebhookChannel {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        preset: row.get(2)?,
+        url: row.get(3)?,
+        secret: row.get(4)?,
+        events: events
+            .split(',')
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .collect(),
+        apply_to_all: row.get::<_, i64>(6)? == 1,
+        enabled: row.get::<_, i64>(7)? == 1,
+        created_at: row.get(8)?,
+        updated_at: row.get(9)?,
+    })
+}
+
+fn notify_options_from_json(value: Option<String>) -> NotifyOptions {
+    value
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn notify_options_to_json(options: &NotifyOptions) -> Result<String, AppError> {
+    serde_json::to_string(options).map_err(|e| AppError::Invalid(e.to_string()))
 }
 
 struct MigrationScript {
@@ -1011,7 +2065,6 @@ struct MigrationScript {
     description: String,
     sql: &'static str,
 }
-
 fn migration_scripts() -> Vec<MigrationScript> {
     vec![
         MigrationScript {
@@ -1079,6 +2132,106 @@ fn migration_scripts() -> Vec<MigrationScript> {
             description: "add sticky note pin state".to_string(),
             sql: include_str!("../migrations/V1.4.9__add_sticky_note_pin_state.sql"),
         },
+        MigrationScript {
+            version: "1.5.0".to_string(),
+            description: "add notify options".to_string(),
+            sql: include_str!("../migrations/V1.5.0__add_notify_options.sql"),
+        },
+        MigrationScript {
+            version: "1.5.1".to_string(),
+            description: "add notification placement".to_string(),
+            sql: include_str!("../migrations/V1.5.1__add_notification_placement.sql"),
+        },
+        MigrationScript {
+            version: "1.5.2".to_string(),
+            description: "add webhook channels".to_string(),
+            sql: include_str!("../migrations/V1.5.2__add_webhook_channels.sql"),
+        },
+        MigrationScript {
+            version: "1.5.3".to_string(),
+            description: "add smtp settings".to_string(),
+            sql: include_str!("../migrations/V1.5.3__add_smtp_settings.sql"),
+        },
+        MigrationScript {
+            version: "1.5.4".to_string(),
+            description: "add mqtt settings".to_string(),
+            sql: include_str!("../migrations/V1.5.4__add_mqtt_settings.sql"),
+        },
+        MigrationScript {
+            version: "1.5.5".to_string(),
+            description: "add sound settings".to_string(),
+            sql: include_str!("../migrations/V1.5.5__add_sound_settings.sql"),
+        },
+        MigrationScript {
+            version: "1.5.6".to_string(),
+            description: "add speech settings".to_string(),
+            sql: include_str!("../migrations/V1.5.6__add_speech_settings.sql"),
+        },
+        MigrationScript {
+            version: "1.5.7".to_string(),
+            description: "add habit state".to_string(),
+            sql: include_str!("../migrations/V1.5.7__add_habit_state.sql"),
+        },
+        MigrationScript {
+            version: "1.5.8".to_string(),
+            description: "add digest settings".to_string(),
+            sql: include_str!("../migrations/V1.5.8__add_digest_settings.sql"),
+        },
+        MigrationScript {
+            version: "1.5.9".to_string(),
+            description: "add report settings".to_string(),
+            sql: include_str!("../migrations/V1.5.9__add_report_settings.sql"),
+        },
+        MigrationScript {
+            version: "1.6.0".to_string(),
+            description: "add timers".to_string(),
+            sql: include_str!("../migrations/V1.6.0__add_timers.sql"),
+        },
+        MigrationScript {
+            version: "1.6.1".to_string(),
+            description: "add focus sessions".to_string(),
+            sql: include_str!("../migrations/V1.6.1__add_focus_sessions.sql"),
+        },
+        MigrationScript {
+            version: "1.6.2".to_string(),
+            description: "add break reminder settings".to_string(),
+            sql: include_str!("../migrations/V1.6.2__add_break_reminder_settings.sql"),
+        },
+        MigrationScript {
+            version: "1.6.3".to_string(),
+            description: "add away hold".to_string(),
+            sql: include_str!("../migrations/V1.6.3__add_away_hold.sql"),
+        },
+        MigrationScript {
+            version: "1.6.4".to_string(),
+            description: "add occurrence id".to_string(),
+            sql: include_str!("../migrations/V1.6.4__add_occurrence_id.sql"),
+        },
+        MigrationScript {
+            version: "1.6.5".to_string(),
+            description: "add lead reminders".to_string(),
+            sql: include_str!("../migrations/V1.6.5__add_lead_reminders.sql"),
+        },
+        MigrationScript {
+            version: "1.6.6".to_string(),
+            description: "add task due at".to_string(),
+            sql: include_str!("../migrations/V1.6.6__add_task_due_at.sql"),
+        },
+        MigrationScript {
+            version: "1.6.7".to_string(),
+            description: "add task reminders".to_string(),
+            sql: include_str!("../migrations/V1.6.7__add_task_reminders.sql"),
+        },
+        MigrationScript {
+            version: "1.6.8".to_string(),
+            description: "add global pause".to_string(),
+            sql: include_str!("../migrations/V1.6.8__add_global_pause.sql"),
+        },
+        MigrationScript {
+            version: "1.6.9".to_string(),
+            description: "add task reminder snooze".to_string(),
+            sql: include_str!("../migrations/V1.6.9__add_task_reminder_snooze.sql"),
+        },
     ]
 }
 
@@ -0,0 +1,288 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+use crate::models::{AppSettings, DigestItem, RecurringTask, StickyNote, Task};
+use crate::recurrence::compute_next_trigger;
+
+pub const DIGEST_REMINDER_TYPE: &str = "DIGEST";
+pub const DIGEST_ITEM_TASK: &str = "TASK";
+pub const DIGEST_ITEM_RECURRING: &str = "RECURRING";
+pub const DIGEST_ITEM_OVERDUE: &str = "OVERDUE";
+pub const DIGEST_ITEM_NOTE: &str = "NOTE";
+/// 单个循环提醒在简报中最多列出的当天次数，避免按分钟循环的提醒刷屏。
+const MAX_OCCURRENCES_PER_TASK: usize = 6;
+
+/// 今天是否应该发送简报：已到设定时间、今天尚未发送，且（若只在工作日）今天是工作日。
+/// 错过设定时间（关机、休眠）时，在之后第一次检查时补发。
+pub fn digest_due(settings: &AppSettings, now: NaiveDateTime) -> bool {
+    if !settings.digest_enabled {
+        return false;
+    }
+    let today = now.date();
+    if settings.digest_workdays_only && matches!(today.weekday(), Weekday::Sat | Weekday::Sun) {
+        return false;
+    }
+    if settings.digest_last_date.as_deref() == Some(today.format("%Y-%m-%d").to_string().as_str()) {
+        return false;
+    }
+    let Ok(time) = NaiveTime::parse_from_str(&settings.digest_time, "%H:%M") else {
+        return false;
+    };
+    now.time() >= time
+}
+
+pub fn build_digest_items(
+    now: NaiveDateTime,
+    tasks: &[Task],
+    recurring: &[RecurringTask],
+    notes: &[StickyNote],
+) -> Vec<DigestItem> {
+    let today = now.date();
+    let mut items = Vec::new();
+
+    let mut overdue = Vec::new();
+    let mut timed = Vec::new();
+    for task in tasks.iter().filter(|task| task.status != "COMPLETED") {
+        // 设置了截止时间的按截止时间判断逾期，否则沿用提醒时间已过即视为逾期。
+        let due = task.due_at.as_deref().and_then(parse_time);
+        if let Some(due) = due.filter(|due| *due < now) {
+            overdue.push((due, task));
+            continue;
+        }
+        let Some(reminder) = task.reminder_time.as_deref().and_then(parse_time) else {
+            continue;
+        };
+        if reminder < now {
+            if due.is_none() {
+                overdue.push((reminder, task));
+            }
+        } else if reminder.date() == today {
+            timed.push((reminder, task));
+        }
+    }
+    timed.sort_by_key(|(time, _)| *time);
+    overdue.sort_by_key(|(time, _)| *time);
+    for (time, task) in timed {
+        items.push(task_item(DIGEST_ITEM_TASK, task, time));
+    }
+
+    let mut occurrences = Vec::new();
+    for task in recurring.iter().filter(|task| !task.is_paused) {
+        for time in occurrences_on(task, today) {
+            occurrences.push((time, task));
+        }
+    }
+    occurrences.sort_by_key(|(time, _)| *time);
+    for (time, task) in occurrences {
+        items.push(DigestItem {
+            kind: DIGEST_ITEM_RECURRING.to_string(),
+            id: task.id.clone(),
+            title: task.description.clone(),
+            time: Some(time.format("%Y-%m-%dT%H:%M:%S").to_string()),
+        });
+    }
+
+    for (time, task) in overdue {
+        items.push(task_item(DIGEST_ITEM_OVERDUE, task, time));
+    }
+
+    for note in notes.iter().filter(|note| note.is_open) {
+        items.push(DigestItem {
+            kind: DIGEST_ITEM_NOTE.to_string(),
+            id: note.task_id.clone(),
+            title: note.title.clone(),
+            time: None,
+        });
+    }
+    items
+}
+
+pub fn digest_title(items: &[DigestItem]) -> String {
+    let count = |kind: &str| items.iter().filter(|item| item.kind == kind).count();
+    format!(
+        "今日简报：{} 个定时任务，{} 次循环提醒，{} 个逾期任务，{} 张便签",
+        count(DIGEST_ITEM_TASK),
+        count(DIGEST_ITEM_RECURRING),
+        count(DIGEST_ITEM_OVERDUE),
+        count(DIGEST_ITEM_NOTE)
+    )
+}
+
+fn task_item(kind: &str, task: &Task, time: NaiveDateTime) -> DigestItem {
+    DigestItem {
+        kind: kind.to_string(),
+        id: task.id.clone(),
+        title: task.description.clone(),
+        time: Some(time.format("%Y-%m-%dT%H:%M:%S").to_string()),
+    }
+}
+
+/// 列出循环提醒在当天剩余的触发时间，从已计划的下一次开始依次推算。
+fn occurrences_on(task: &RecurringTask, day: NaiveDate) -> Vec<NaiveDateTime> {
+    let mut result = Vec::new();
+    let mut next = parse_time(&task.next_trigger);
+    while let Some(time) = next {
+        if time.date() != day || result.len() >= MAX_OCCURRENCES_PER_TASK {
+            break;
+        }
+        result.push(time);
+        next = compute_next_trigger(task, time)
+            .ok()
+            .and_then(|value| parse_time(&value))
+            .filter(|value| *value > time);
+    }
+    result
+}
+
+fn parse_time(value: &str) -> Option<NaiveDateTime> {
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(value, fmt).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NotifyOptions;
+    use crate::recurrence::REPEAT_MODE_INTERVAL_RANGE;
+
+    fn at(value: &str) -> NaiveDateTime {
+        parse_time(value).unwrap()
+    }
+
+    fn task(id: &str, reminder: Option<&str>, due: Option<&str>) -> Task {
+        Task {
+            id: id.to_string(),
+            description: id.to_string(),
+            sticky_content: None,
+            task_type: "TASK".to_string(),
+            status: "PENDING".to_string(),
+            created_at: "2026-01-01T00:00:00".to_string(),
+            completed_at: None,
+            reminder_time: reminder.map(str::to_string),
+            updated_at: None,
+            deleted_at: None,
+            notify_options: NotifyOptions::default(),
+            tracked_seconds: 0,
+            due_at: due.map(str::to_string),
+            overdue: false,
+        }
+    }
+
+    fn every(id: &str, minutes: i64, next_trigger: &str) -> RecurringTask {
+        RecurringTask {
+            id: id.to_string(),
+            description: id.to_string(),
+            task_type: "RECURRING".to_string(),
+            status: "PENDING".to_string(),
+            created_at: "2026-01-01T00:00:00".to_string(),
+            completed_at: None,
+            reminder_time: None,
+            updated_at: None,
+            deleted_at: None,
+            interval_minutes: minutes,
+            last_triggered: None,
+            next_trigger: next_trigger.to_string(),
+            is_paused: false,
+            start_time: None,
+            end_time: None,
+            repeat_mode: REPEAT_MODE_INTERVAL_RANGE.to_string(),
+            schedule_time: None,
+            schedule_weekday: None,
+            schedule_day: None,
+            cron_expression: None,
+            notify_options: NotifyOptions::default(),
+        }
+    }
+
+    fn summary(items: &[DigestItem]) -> Vec<(&str, &str, Option<&str>)> {
+        items
+            .iter()
+            .map(|item| (item.kind.as_str(), item.id.as_str(), item.time.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn empty_day_has_no_items() {
+        let mut done = task("已完成", Some("2026-01-05T10:00:00"), None);
+        done.status = "COMPLETED".to_string();
+        let tomorrow = task("明天", Some("2026-01-06T10:00:00"), None);
+        let mut paused = every("已暂停", 60, "2026-01-05T09:00:00");
+        paused.is_paused = true;
+        let items = build_digest_items(
+            at("2026-01-05T08:00:00"),
+            &[done, tomorrow, task("无提醒", None, None)],
+            &[paused, every("明天循环", 60, "2026-01-06T09:00:00")],
+            &[],
+        );
+        assert!(items.is_empty());
+        assert_eq!(
+            digest_title(&items),
+            "今日简报：0 个定时任务，0 次循环提醒，0 个逾期任务，0 张便签"
+        );
+    }
+
+    #[test]
+    fn overdue_items_follow_today_and_are_ordered_by_time() {
+        let items = build_digest_items(
+            at("2026-01-05T08:00:00"),
+            &[
+                task("提醒已过", Some("2026-01-04T09:00:00"), None),
+                task(
+                    "截止已过",
+                    Some("2026-01-05T18:00:00"),
+                    Some("2026-01-03T12:00:00"),
+                ),
+                // 截止时间未到时，提醒时间已过不算逾期。
+                task(
+                    "截止未到",
+                    Some("2026-01-05T07:00:00"),
+                    Some("2026-01-08T12:00:00"),
+                ),
+                task("今天", Some("2026-01-05T10:00:00"), None),
+            ],
+            &[],
+            &[],
+        );
+        assert_eq!(
+            summary(&items),
+            vec![
+                ("TASK", "今天", Some("2026-01-05T10:00:00")),
+                ("OVERDUE", "截止已过", Some("2026-01-03T12:00:00")),
+                ("OVERDUE", "提醒已过", Some("2026-01-04T09:00:00")),
+            ]
+        );
+    }
+
+    #[test]
+    fn items_after_midnight_are_left_for_tomorrow() {
+        let items = build_digest_items(
+            at("2026-01-05T22:00:00"),
+            &[
+                task("午夜后", Some("2026-01-06T00:15:00"), None),
+                task("睡前", Some("2026-01-05T23:45:00"), None),
+            ],
+            &[every("喝水", 60, "2026-01-05T22:30:00")],
+            &[],
+        );
+        assert_eq!(
+            summary(&items),
+            vec![
+                ("TASK", "睡前", Some("2026-01-05T23:45:00")),
+                ("RECURRING", "喝水", Some("2026-01-05T22:30:00")),
+                ("RECURRING", "喝水", Some("2026-01-05T23:30:00")),
+            ]
+        );
+    }
+
+    #[test]
+    fn frequent_recurring_reminders_are_capped() {
+        let items = build_digest_items(
+            at("2026-01-05T08:00:00"),
+            &[],
+            &[every("站立", 5, "2026-01-05T08:05:00")],
+            &[],
+        );
+        assert_eq!(items.len(), MAX_OCCURRENCES_PER_TASK);
+        assert_eq!(items[5].time.as_deref(), Some("2026-01-05T08:30:00"));
+    }
+}
@@ -0,0 +1,148 @@
+use std::thread;
+
+use crate::db::DbManager;
+use crate::due::DUE_REMINDER_TYPE;
+use crate::email::send_reminder_email;
+use crate::leads::LEAD_REMINDER_TYPE;
+use crate::models::{NotificationPayload, NotifyOptions, ReminderRecord};
+use crate::mqtt::MqttService;
+use crate::sound::SoundService;
+use crate::speech::SpeechService;
+use crate::sync::CloudSyncService;
+use crate::webhook::{
+    WebhookMessage, WebhookService, WEBHOOK_EVENT_ACKED, WEBHOOK_EVENT_FIRED, WEBHOOK_EVENT_MISSED,
+    WEBHOOK_EVENT_SNOOZED,
+};
+
+/// 提醒生命周期事件的统一出口，弹窗之外的外部渠道都在这里挂接。
+#[derive(Clone)]
+pub struct ReminderDispatcher {
+    db: DbManager,
+    sync: CloudSyncService,
+    webhooks: WebhookService,
+    mqtt: MqttService,
+    sound: SoundService,
+    speech: SpeechService,
+}
+
+impl ReminderDispatcher {
+    pub fn new(
+        db: DbManager,
+        sync: CloudSyncService,
+        webhooks: WebhookService,
+        mqtt: MqttService,
+        sound: SoundService,
+        speech: SpeechService,
+    ) -> Self {
+        Self {
+            db,
+            sync,
+            webhooks,
+            mqtt,
+            sound,
+            speech,
+        }
+    }
+
+    pub fn reminder_fired(&self, record: &ReminderRecord, payload: &NotificationPayload) {
+        let (options, sticky_content) = self.reminder_context(record);
+        if let Err(err) = self
+            .sound
+            .play_for_reminder(&record.id, options.sound.as_deref())
+        {
+            eprintln!("[sound] {}", err);
+        }
+        self.speech.speak_for_reminder(record, &options);
+        self.publish(
+            WebhookMessage::new(
+                WEBHOOK_EVENT_FIRED,
+                record,
+                Some(payload),
+                None,
+                self.db.clock().now(),
+            ),
+            &options,
+        );
+        if options.email {
+            self.send_email(record, sticky_content);
+        }
+    }
+
+    /// 提醒被关闭（确认、执行按钮或错过）后调用，`record` 需是更新后的记录。
+    pub fn reminder_closed(&self, record: &ReminderRecord) {
+        let event = if record.action == "MISSED" {
+            WEBHOOK_EVENT_MISSED
+        } else {
+            WEBHOOK_EVENT_ACKED
+        };
+        self.sound.stop(&record.id);
+        self.speech.stop(&record.id);
+        let (options, _) = self.reminder_context(record);
+        let message = WebhookMessage::new(event, record, None, None, self.db.clock().now());
+        self.publish(message, &options);
+    }
+
+    pub fn reminder_snoozed(&self, record: &ReminderRecord, minutes: i64) {
+        self.sound.stop(&record.id);
+        self.speech.stop(&record.id);
+        let (options, _) = self.reminder_context(record);
+        self.publish(
+            WebhookMessage::new(
+                WEBHOOK_EVENT_SNOOZED,
+                record,
+                None,
+                Some(minutes),
+                self.db.clock().now(),
+            ),
+            &options,
+        );
+    }
+
+    fn publish(&self, message: WebhookMessage, options: &NotifyOptions) {
+        self.mqtt.publish_reminder(&message);
+        self.webhooks.publish(message, &options.webhook_channels);
+    }
+
+    fn send_email(&self, record: &ReminderRecord, sticky_content: Option<String>) {
+        let dispatcher = self.clone();
+        let record = record.clone();
+        thread::spawn(move || {
+            if send_reminder_email(&dispatcher.db, &record, sticky_content.as_deref()) {
+                let _ = dispatcher.sync.notify_local_change();
+            }
+        });
+    }
+
+    /// 返回提醒对应任务的提醒配置与便签内容，任务已不存在时使用默认配置。
+    fn reminder_context(&self, record: &ReminderRecord) -> (NotifyOptions, Option<String>) {
+        match record.reminder_type.as_str() {
+            "TASK" | DUE_REMINDER_TYPE => self
+                .db
+                .get_task(&record.reminder_id)
+                .ok()
+                .flatten()
+                .map(|task| (task.notify_options, task.sticky_content))
+                .unwrap_or_default(),
+            "RECURRING" => self
+                .db
+                .get_recurring_task(&record.reminder_id)
+                .ok()
+                .flatten()
+                .map(|task| (task.notify_options, None))
+                .unwrap_or_default(),
+            // 提前提醒沿用所属任务或循环任务的配置。
+            LEAD_REMINDER_TYPE => {
+                if let Some(task) = self.db.get_task(&record.reminder_id).ok().flatten() {
+                    return (task.notify_options, task.sticky_content);
+                }
+                self.db
+                    .get_recurring_task(&record.reminder_id)
+                    .ok()
+                    .flatten()
+                    .map(|task| (task.notify_options, None))
+                    .unwrap_or_default()
+            }
+            _ => Default::default(),
+        }
+    }
+}
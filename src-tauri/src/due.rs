@@ -0,0 +1,142 @@
+use chrono::{Duration, NaiveDateTime};
+
+use crate::errors::AppError;
+use crate::models::Task;
+
+/// 截止时间提醒生成的提醒记录类型，reminder_id 为任务 id。
+pub const DUE_REMINDER_TYPE: &str = "DUE";
+
+pub const TASK_SORT_CREATED: &str = "CREATED";
+pub const TASK_SORT_DUE: &str = "DUE";
+pub const TASK_SORT_REMINDER: &str = "REMINDER";
+
+pub const TASK_FILTER_ALL: &str = "ALL";
+pub const TASK_FILTER_OVERDUE: &str = "OVERDUE";
+pub const TASK_FILTER_DUE_TODAY: &str = "DUE_TODAY";
+pub const TASK_FILTER_NO_DUE: &str = "NO_DUE";
+
+pub fn normalize_task_sort(value: Option<&str>) -> Result<&'static str, AppError> {
+    match value.map(|value| value.trim().to_uppercase()).as_deref() {
+        None | Some("") | Some(TASK_SORT_CREATED) => Ok(TASK_SORT_CREATED),
+        Some(TASK_SORT_DUE) => Ok(TASK_SORT_DUE),
+        Some(TASK_SORT_REMINDER) => Ok(TASK_SORT_REMINDER),
+        Some(other) => Err(AppError::Invalid(format!("不支持的排序方式: {}", other))),
+    }
+}
+
+pub fn normalize_task_filter(value: Option<&str>) -> Result<&'static str, AppError> {
+    match value.map(|value| value.trim().to_uppercase()).as_deref() {
+        None | Some("") | Some(TASK_FILTER_ALL) => Ok(TASK_FILTER_ALL),
+        Some(TASK_FILTER_OVERDUE) => Ok(TASK_FILTER_OVERDUE),
+        Some(TASK_FILTER_DUE_TODAY) => Ok(TASK_FILTER_DUE_TODAY),
+        Some(TASK_FILTER_NO_DUE) => Ok(TASK_FILTER_NO_DUE),
+        Some(other) => Err(AppError::Invalid(format!("不支持的筛选条件: {}", other))),
+    }
+}
+
+/// 已过截止时间且尚未完成。
+pub fn is_overdue(task: &Task, now: NaiveDateTime) -> bool {
+    task.status != "COMPLETED"
+        && task
+            .due_at
+            .as_deref()
+            .and_then(parse_time)
+            .is_some_and(|due| due < now)
+}
+
+/// 逾期提醒的时间点：截止时间一次，之后每天同一时刻一次。
+/// 尚未到期时返回截止时间，否则返回不晚于 `now` 的最近一次。
+pub fn due_alert_slot(due: NaiveDateTime, now: NaiveDateTime) -> NaiveDateTime {
+    if due > now {
+        return due;
+    }
+    due + Duration::days((now - due).num_days())
+}
+
+/// 与普通提醒的触发点区分开，截止时间与提醒时间相同时也各有一条记录。
+pub fn due_record_id(occurrence_id: &str) -> String {
+    format!("{}#due", occurrence_id)
+}
+
+pub fn due_description(description: &str, due: NaiveDateTime, slot: NaiveDateTime) -> String {
+    match (slot - due).num_days() {
+        0 => format!("已到期: {}", description),
+        days => format!("已逾期 {} 天: {}", days, description),
+    }
+}
+
+/// 空白表示清除截止时间，其余统一保存为 `%Y-%m-%dT%H:%M:%S`。
+pub fn normalize_due_at(value: Option<&str>) -> Result<Option<String>, AppError> {
+    match value.map(str::trim).filter(|value| !value.is_empty()) {
+        None => Ok(None),
+        Some(value) => parse_time(value)
+            .map(|due| Some(due.format("%Y-%m-%dT%H:%M:%S").to_string()))
+            .ok_or_else(|| AppError::Invalid(format!("无法解析截止时间: {}", value))),
+    }
+}
+
+pub fn parse_time(value: &str) -> Option<NaiveDateTime> {
+    [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+    ]
+    .iter()
+    .find_map(|fmt| NaiveDateTime::parse_from_str(value, fmt).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        parse_time(value).unwrap()
+    }
+
+    #[test]
+    fn alert_slots_repeat_daily_at_the_due_time() {
+        let due = at("2026-01-05T17:00:00");
+        assert_eq!(due_alert_slot(due, at("2026-01-05T09:00:00")), due);
+        assert_eq!(due_alert_slot(due, at("2026-01-05T17:00:00")), due);
+        assert_eq!(
+            due_alert_slot(due, at("2026-01-07T16:59:00")),
+            at("2026-01-06T17:00:00")
+        );
+        assert_eq!(
+            due_description("交周报", due, at("2026-01-07T17:00:00")),
+            "已逾期 2 天: 交周报"
+        );
+        assert_eq!(due_description("交周报", due, due), "已到期: 交周报");
+    }
+
+    #[test]
+    fn sort_and_filter_names_are_validated() {
+        assert_eq!(normalize_task_sort(None).unwrap(), TASK_SORT_CREATED);
+        assert_eq!(normalize_task_sort(Some("due")).unwrap(), TASK_SORT_DUE);
+        assert!(normalize_task_sort(Some("PRIORITY")).is_err());
+        assert_eq!(
+            normalize_task_filter(Some(" overdue ")).unwrap(),
+            TASK_FILTER_OVERDUE
+        );
+        assert!(normalize_task_filter(Some("SOON")).is_err());
+    }
+
+    #[test]
+    fn due_at_is_stored_in_a_single_format() {
+        assert_eq!(
+            normalize_due_at(Some(" 2026-01-05T17:00 "))
+                .unwrap()
+                .as_deref(),
+            Some("2026-01-05T17:00:00")
+        );
+        assert_eq!(
+            normalize_due_at(Some("2026-01-05T17:00:00.250"))
+                .unwrap()
+                .as_deref(),
+            Some("2026-01-05T17:00:00")
+        );
+        assert_eq!(normalize_due_at(Some("  ")).unwrap(), None);
+        assert_eq!(normalize_due_at(None).unwrap(), None);
+        assert!(normalize_due_at(Some("明天")).is_err());
+    }
+}
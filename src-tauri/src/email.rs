@@ -0,0 +1,360 @@
+use std::time::Duration;
+
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+
+use crate::db::DbManager;
+use crate::errors::AppError;
+use crate::models::{AppSettings, ReminderRecord};
+
+const SMTP_TIMEOUT_SECS: u64 = 15;
+const SUBJECT_MAX_CHARS: usize = 60;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailContent {
+    pub subject: String,
+    pub body: String,
+}
+
+pub fn build_reminder_email(record: &ReminderRecord, sticky_content: Option<&str>) -> EmailContent {
+    let headline = record
+        .description
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("任务提醒");
+    let mut subject: String = headline.chars().take(SUBJECT_MAX_CHARS).collect();
+    if headline.chars().count() > SUBJECT_MAX_CHARS {
+        subject.push('…');
+    }
+
+    let mut body = record.description.trim().to_string();
+    if let Some(content) = sticky_content
+        .map(str::trim)
+        .filter(|content| !content.is_empty())
+    {
+        body.push_str("\n\n");
+        body.push_str(content);
+    }
+    body.push_str(&format!(
+        "\n\n提醒时间: {}",
+        record.trigger_time.replace('T', " ")
+    ));
+
+    EmailContent {
+        subject: format!("[任务提醒] {}", subject),
+        body,
+    }
+}
+
+/// 按当前设置发送提醒邮件，失败时把原因写入提醒记录。返回是否写入了失败原因。
+pub fn send_reminder_email(
+    db: &DbManager,
+    record: &ReminderRecord,
+    sticky_content: Option<&str>,
+) -> bool {
+    let result = db.load_settings().and_then(|settings| {
+        if !settings.smtp_enabled {
+            return Ok(());
+        }
+        send_email(&settings, &build_reminder_email(record, sticky_content))
+    });
+    let Err(err) = result else {
+        return false;
+    };
+    eprintln!("[email] {}", err);
+    db.update_reminder_record_delivery_error(&record.id, Some(&err.to_string()))
+        .is_ok()
+}
+
+pub fn send_email(settings: &AppSettings, content: &EmailContent) -> Result<(), AppError> {
+    let message = build_message(settings, content)?;
+    build_transport(settings)?
+        .send(&message)
+        .map(|_| ())
+        .map_err(|e| AppError::System(format!("邮件发送失败: {}", e)))
+}
+
+pub fn send_test_email(settings: &AppSettings) -> Result<(), AppError> {
+    send_email(
+        settings,
+        &EmailContent {
+            subject: "[任务提醒] 测试邮件".to_string(),
+            body: "这是一封来自任务提醒的测试邮件，收到说明 SMTP 配置可用。".to_string(),
+        },
+    )
+}
+
+fn build_message(settings: &AppSettings, content: &EmailContent) -> Result<Message, AppError> {
+    let from = if settings.smtp_from.trim().is_empty() {
+        settings.smtp_username.trim()
+    } else {
+        settings.smtp_from.trim()
+    };
+    let from: Mailbox = from
+        .parse()
+        .map_err(|_| AppError::Invalid("发件人地址格式不正确".to_string()))?;
+    let mut builder = Message::builder()
+        .from(from)
+        .subject(content.subject.clone())
+        .header(ContentType::TEXT_PLAIN);
+    let mut has_recipient = false;
+    for address in settings
+        .smtp_to
+        .split([',', ';'])
+        .map(str::trim)
+        .filter(|address| !address.is_empty())
+    {
+        let mailbox: Mailbox = address
+            .parse()
+            .map_err(|_| AppError::Invalid(format!("收件人地址格式不正确: {}", address)))?;
+        builder = builder.to(mailbox);
+        has_recipient = true;
+    }
+    if !has_recipient {
+        return Err(AppError::Invalid("请填写收件人地址".to_string()));
+    }
+    builder
+        .body(content.body.clone())
+        .map_err(|e| AppError::System(e.to_string()))
+}
+
+fn build_transport(settings: &AppSettings) -> Result<SmtpTransport, AppError> {
+    let host = settings.smtp_host.trim();
+    if host.is_empty() {
+        return Err(AppError::Invalid("请填写 SMTP 服务器地址".to_string()));
+    }
+    let port = u16::try_from(settings.smtp_port)
+        .map_err(|_| AppError::Invalid("SMTP 端口不正确".to_string()))?;
+    let builder = match settings.smtp_security.as_str() {
+        "TLS" => SmtpTransport::relay(host),
+        // 仅用于本地或内网的明文 SMTP，例如调试用的邮件服务。
+        "NONE" => Ok(SmtpTransport::builder_dangerous(host)),
+        _ => SmtpTransport::starttls_relay(host),
+    }
+    .map_err(|e| AppError::System(format!("SMTP 配置错误: {}", e)))?;
+    let mut builder = builder
+        .port(port)
+        .timeout(Some(Duration::from_secs(SMTP_TIMEOUT_SECS)));
+    if !settings.smtp_username.trim().is_empty() {
+        builder = builder.credentials(Credentials::new(
+            settings.smtp_username.trim().to_string(),
+            settings.smtp_password.clone(),
+        ));
+    }
+    Ok(builder.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::thread;
+
+    use super::*;
+
+    fn record(description: &str) -> ReminderRecord {
+        ReminderRecord {
+            id: "record".to_string(),
+            reminder_id: "task".to_string(),
+            description: description.to_string(),
+            reminder_type: "TASK".to_string(),
+            trigger_time: "2026-01-05T09:00:00".to_string(),
+            close_time: None,
+            action: "PENDING".to_string(),
+            updated_at: None,
+            deleted_at: None,
+            delivery_error: None,
+            habit_state: None,
+            delivery_state: None,
+            delivered_at: None,
+            occurrence_id: None,
+        }
+    }
+
+    struct TempDb {
+        path: PathBuf,
+        db: DbManager,
+    }
+
+    impl TempDb {
+        fn new() -> Self {
+            let path = std::env::temp_dir()
+                .join(format!("task-reminder-email-{}.db", uuid::Uuid::new_v4()));
+            let db = DbManager::new(path.clone()).unwrap();
+            Self { path, db }
+        }
+
+        fn smtp_settings(&self, port: u16, to: &str) -> AppSettings {
+            let mut settings = self.db.load_settings().unwrap();
+            settings.smtp_enabled = true;
+            settings.smtp_host = "127.0.0.1".to_string();
+            settings.smtp_port = port as i64;
+            settings.smtp_security = "NONE".to_string();
+            settings.smtp_username = String::new();
+            settings.smtp_from = "reminder@example.com".to_string();
+            settings.smtp_to = to.to_string();
+            settings
+        }
+    }
+
+    impl Drop for TempDb {
+        fn drop(&mut self) {
+            for suffix in ["", "-wal", "-shm"] {
+                let mut path = self.path.clone().into_os_string();
+                path.push(suffix);
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+
+    /// 只应答一次投递的最小 SMTP 服务，返回收到的命令与邮件内容。
+    fn serve_smtp() -> (u16, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut lines = Vec::new();
+            let mut in_data = false;
+            writer.write_all(b"220 localhost ESMTP\r\n").unwrap();
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+                let line = line.trim_end().to_string();
+                let command = line.to_uppercase();
+                lines.push(line);
+                let reply: &[u8] = if in_data {
+                    if lines.last().map(String::as_str) != Some(".") {
+                        continue;
+                    }
+                    in_data = false;
+                    b"250 OK\r\n"
+                } else if command.starts_with("EHLO") {
+                    b"250 localhost\r\n"
+                } else if command.starts_with("DATA") {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                } else if command.starts_with("QUIT") {
+                    writer.write_all(b"221 bye\r\n").unwrap();
+                    break;
+                } else {
+                    b"250 OK\r\n"
+                };
+                writer.write_all(reply).unwrap();
+            }
+            lines
+        });
+        (port, handle)
+    }
+
+    #[test]
+    fn subject_uses_the_first_line_and_is_truncated() {
+        let long = "长".repeat(SUBJECT_MAX_CHARS + 5);
+        let content = build_reminder_email(&record(&format!("\n  {}\n第二行", long)), None);
+        assert_eq!(
+            content.subject,
+            format!("[任务提醒] {}…", "长".repeat(SUBJECT_MAX_CHARS))
+        );
+
+        let exact = "短".repeat(SUBJECT_MAX_CHARS);
+        let content = build_reminder_email(&record(&exact), None);
+        assert_eq!(content.subject, format!("[任务提醒] {}", exact));
+    }
+
+    #[test]
+    fn body_appends_sticky_content_and_trigger_time() {
+        let content = build_reminder_email(&record("交周报"), Some("  附上数据  "));
+        assert_eq!(
+            content.body,
+            "交周报\n\n附上数据\n\n提醒时间: 2026-01-05 09:00:00"
+        );
+        let content = build_reminder_email(&record("交周报"), Some("   "));
+        assert_eq!(content.body, "交周报\n\n提醒时间: 2026-01-05 09:00:00");
+    }
+
+    #[test]
+    fn blank_description_falls_back_to_a_default_subject() {
+        let content = build_reminder_email(&record(" \n "), None);
+        assert_eq!(content.subject, "[任务提醒] 任务提醒");
+    }
+
+    #[test]
+    fn recipients_are_split_on_commas_and_semicolons() {
+        let temp = TempDb::new();
+        let content = build_reminder_email(&record("交周报"), None);
+        let settings = temp.smtp_settings(25, "a@example.com, b@example.com;c@example.com;");
+        let message = build_message(&settings, &content).unwrap();
+        let recipients: Vec<String> = message
+            .envelope()
+            .to()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            recipients,
+            vec!["a@example.com", "b@example.com", "c@example.com"]
+        );
+
+        let settings = temp.smtp_settings(25, "a@example.com; 不是地址");
+        assert!(
+            matches!(build_message(&settings, &content), Err(AppError::Invalid(message)) if message.contains("不是地址"))
+        );
+        let settings = temp.smtp_settings(25, " ; , ");
+        assert!(matches!(
+            build_message(&settings, &content),
+            Err(AppError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn sends_over_plain_smtp() {
+        let temp = TempDb::new();
+        let (port, server) = serve_smtp();
+        let settings = temp.smtp_settings(port, "a@example.com;b@example.com");
+
+        send_email(&settings, &build_reminder_email(&record("交周报"), None)).unwrap();
+
+        let lines = server.join().unwrap();
+        let rcpt: Vec<&String> = lines
+            .iter()
+            .filter(|line| line.to_uppercase().starts_with("RCPT TO"))
+            .collect();
+        assert_eq!(rcpt.len(), 2);
+        assert!(lines.iter().any(|line| line
+            .to_uppercase()
+            .starts_with("MAIL FROM:<REMINDER@EXAMPLE.COM>")));
+        assert!(lines.iter().any(|line| line.starts_with("Subject:")));
+    }
+
+    #[test]
+    fn send_failure_is_recorded_on_the_reminder_record() {
+        let temp = TempDb::new();
+        let record = temp
+            .db
+            .create_reminder_record("task", "交周报", "TASK")
+            .unwrap();
+        // 绑定后立即释放端口，连接会被拒绝。
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        temp.db
+            .save_settings(&temp.smtp_settings(port, "a@example.com"))
+            .unwrap();
+
+        assert!(send_reminder_email(&temp.db, &record, None));
+
+        let saved = temp.db.get_reminder_record(&record.id).unwrap().unwrap();
+        assert!(saved
+            .delivery_error
+            .is_some_and(|error| error.contains("邮件发送失败")));
+    }
+}
@@ -0,0 +1,426 @@
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, NaiveDateTime};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::db::DbManager;
+use crate::errors::AppError;
+use crate::models::{AppSettings, NotificationPayload};
+use crate::queue::{JobKey, JobKind};
+use crate::scheduler::ReminderScheduler;
+use crate::sync::CloudSyncService;
+
+pub const FOCUS_REMINDER_TYPE: &str = "FOCUS";
+pub const FOCUS_WORK: &str = "WORK";
+pub const FOCUS_SHORT_BREAK: &str = "SHORT_BREAK";
+pub const FOCUS_LONG_BREAK: &str = "LONG_BREAK";
+/// 休息已结束，等待用户在弹窗中确认后开始下一轮专注。
+pub const FOCUS_BREAK_OVER: &str = "BREAK_OVER";
+pub const TIME_ENTRY_SOURCE_FOCUS: &str = "FOCUS";
+const FOCUS_CHANGED_EVENT: &str = "focus-changed";
+/// 阶段结束的调度任务，任务 id 为阶段开始时间。
+const FOCUS_JOB: &str = "focus";
+const FOCUS_BREAK_RECORD_PREFIX: &str = "focus-break-";
+const FOCUS_NEXT_RECORD_PREFIX: &str = "focus-next-";
+/// 不足一分钟的专注不记入用时。
+const MIN_ENTRY_SECONDS: i64 = 60;
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusSession {
+    pub task_id: String,
+    pub description: String,
+    pub phase: String,
+    /// 当前（或即将开始的）专注轮次，从 1 开始。
+    pub round: i64,
+    pub completed_rounds: i64,
+    pub phase_started_at: String,
+    pub phase_ends_at: Option<String>,
+}
+
+/// 专注时段只保存在内存中，每完成一段专注就写入一条 time_entries 记录。
+#[derive(Clone)]
+pub struct FocusService {
+    app: AppHandle,
+    db: DbManager,
+    sync: CloudSyncService,
+    scheduler: ReminderScheduler,
+    session: Arc<Mutex<Option<FocusSession>>>,
+}
+
+impl FocusService {
+    pub fn new(
+        app: AppHandle,
+        db: DbManager,
+        sync: CloudSyncService,
+        scheduler: ReminderScheduler,
+    ) -> Self {
+        let service = Self {
+            app,
+            db,
+            sync,
+            scheduler,
+            session: Arc::new(Mutex::new(None)),
+        };
+        let handler = service.clone();
+        service.scheduler.register_handler(
+            FOCUS_JOB,
+            Arc::new(move |phase_started_at| handler.phase_finished(phase_started_at)),
+        );
+        service
+    }
+
+    pub fn current(&self) -> Option<FocusSession> {
+        self.session.lock().unwrap().clone()
+    }
+
+    pub fn start(&self, task_id: &str) -> Result<FocusSession, AppError> {
+        let task = self
+            .db
+            .get_task(task_id)?
+            .filter(|task| task.deleted_at.is_none())
+            .ok_or_else(|| AppError::Invalid("任务不存在".to_string()))?;
+        if task.status == "COMPLETED" {
+            return Err(AppError::Invalid("任务已完成，无法开始专注".to_string()));
+        }
+        self.stop()?;
+        let settings = self.db.load_settings()?;
+        let mut session = FocusSession {
+            task_id: task.id,
+            description: task.description,
+            phase: FOCUS_WORK.to_string(),
+            round: 1,
+            completed_rounds: 0,
+            phase_started_at: String::new(),
+            phase_ends_at: None,
+        };
+        begin_phase(
+            &mut session,
+            FOCUS_WORK,
+            Some(settings.focus_work_minutes),
+            self.now(),
+        );
+        self.replace(Some(session.clone()));
+        Ok(session)
+    }
+
+    /// 结束当前专注时段，进行中的专注按已用时长记入任务。
+    pub fn stop(&self) -> Result<(), AppError> {
+        let Some(session) = self.session.lock().unwrap().take() else {
+            return Ok(());
+        };
+        if session.phase == FOCUS_WORK {
+            self.record_entry(&session, self.now())?;
+        }
+        self.replace(None);
+        Ok(())
+    }
+
+    /// 任务完成或删除时结束与之关联的专注时段。
+    pub fn stop_for_task(&self, task_id: &str) -> Result<(), AppError> {
+        let focused = self
+            .current()
+            .is_some_and(|session| session.task_id == task_id);
+        if focused {
+            self.stop()?;
+        }
+        Ok(())
+    }
+
+    /// 提前结束当前阶段：专注直接进入休息，休息直接开始下一轮专注。
+    pub fn skip(&self) -> Result<(), AppError> {
+        let Some(session) = self.current() else {
+            return Ok(());
+        };
+        if session.phase == FOCUS_WORK {
+            self.finish_work(session)
+        } else {
+            self.start_next_round(session)
+        }
+    }
+
+    /// 休息结束弹窗中点了“稍后提醒”，延长休息。
+    pub fn extend_break(&self, minutes: i64) -> Result<(), AppError> {
+        let Some(mut session) = self.current() else {
+            return Ok(());
+        };
+        if session.phase == FOCUS_WORK {
+            return Ok(());
+        }
+        let phase = if session.phase == FOCUS_BREAK_OVER {
+            FOCUS_SHORT_BREAK.to_string()
+        } else {
+            session.phase.clone()
+        };
+        begin_phase(&mut session, &phase, Some(minutes.max(1)), self.now());
+        self.replace(Some(session));
+        Ok(())
+    }
+
+    /// 弹窗被确认时调用，休息结束的弹窗确认后开始下一轮专注。
+    pub fn notification_acknowledged(&self, record_id: &str) -> Result<(), AppError> {
+        if !record_id.starts_with(FOCUS_NEXT_RECORD_PREFIX) {
+            return Ok(());
+        }
+        match self.current() {
+            Some(session) if session.phase == FOCUS_BREAK_OVER => self.start_next_round(session),
+            _ => Ok(()),
+        }
+    }
+
+    fn phase_finished(&self, phase_started_at: &str) -> Result<(), AppError> {
+        // 阶段已被跳过、延长或结束时，旧的定时任务不再生效。
+        let Some(session) = self
+            .current()
+            .filter(|session| session.phase_started_at == phase_started_at)
+        else {
+            return Ok(());
+        };
+        if session.phase == FOCUS_WORK {
+            self.finish_work(session)
+        } else {
+            self.finish_break(session)
+        }
+    }
+
+    fn finish_work(&self, mut session: FocusSession) -> Result<(), AppError> {
+        let now = self.now();
+        self.record_entry(&session, now)?;
+        let settings = self.db.load_settings()?;
+        let minutes = complete_work(&mut session, &settings, now);
+        self.replace(Some(session.clone()));
+        self.notify(
+            &settings,
+            FOCUS_BREAK_RECORD_PREFIX,
+            &session,
+            format!(
+                "第 {} 轮专注完成：{}，休息 {} 分钟",
+                session.completed_rounds, session.description, minutes
+            ),
+        )
+    }
+
+    fn finish_break(&self, mut session: FocusSession) -> Result<(), AppError> {
+        complete_break(&mut session, self.now());
+        self.replace(Some(session.clone()));
+        let settings = self.db.load_settings()?;
+        self.notify(
+            &settings,
+            FOCUS_NEXT_RECORD_PREFIX,
+            &session,
+            format!(
+                "休息结束，确认后开始第 {} 轮专注：{}",
+                session.round, session.description
+            ),
+        )
+    }
+
+    fn start_next_round(&self, mut session: FocusSession) -> Result<(), AppError> {
+        let settings = self.db.load_settings()?;
+        begin_next_round(&mut session, &settings, self.now());
+        self.replace(Some(session));
+        Ok(())
+    }
+
+    fn record_entry(
+        &self,
+        session: &FocusSession,
+        ended_at: NaiveDateTime,
+    ) -> Result<(), AppError> {
+        let Some(started_at) = parse_time(&session.phase_started_at) else {
+            return Ok(());
+        };
+        let seconds = (ended_at - started_at).num_seconds();
+        if seconds < MIN_ENTRY_SECONDS {
+            return Ok(());
+        }
+        self.db.insert_time_entry(
+            &session.task_id,
+            &session.phase_started_at,
+            &format_time(ended_at),
+            seconds,
+            TIME_ENTRY_SOURCE_FOCUS,
+        )?;
+        self.sync.notify_local_change()
+    }
+
+    fn notify(
+        &self,
+        settings: &AppSettings,
+        prefix: &str,
+        session: &FocusSession,
+        description: String,
+    ) -> Result<(), AppError> {
+        let payload = NotificationPayload {
+            record_id: format!("{}{}", prefix, session.phase_started_at),
+            reminder_id: session.task_id.clone(),
+            reminder_type: FOCUS_REMINDER_TYPE.to_string(),
+            description,
+            snooze_minutes: settings.snooze_minutes,
+            actions: Vec::new(),
+            digest_items: Vec::new(),
+        };
+        self.scheduler.present_transient(settings, &payload)
+    }
+
+    fn now(&self) -> NaiveDateTime {
+        self.db.clock().now()
+    }
+
+    /// 替换当前会话、重新安排阶段结束的定时任务并通知前端。
+    fn replace(&self, session: Option<FocusSession>) {
+        let previous = std::mem::replace(&mut *self.session.lock().unwrap(), session.clone());
+        if let Some(previous) = previous {
+            self.scheduler
+                .cancel_job(&phase_job(&previous.phase_started_at));
+        }
+        if let Some(session) = &session {
+            if let Some(ends_at) = session.phase_ends_at.as_deref().and_then(parse_time) {
+                self.scheduler
+                    .schedule_job(phase_job(&session.phase_started_at), ends_at);
+            }
+        }
+        let _ = self.app.emit(FOCUS_CHANGED_EVENT, session);
+    }
+}
+
+fn phase_job(phase_started_at: &str) -> JobKey {
+    JobKey::new(JobKind::Handler(FOCUS_JOB), phase_started_at)
+}
+
+fn begin_phase(session: &mut FocusSession, phase: &str, minutes: Option<i64>, now: NaiveDateTime) {
+    session.phase = phase.to_string();
+    session.phase_started_at = format_time(now);
+    session.phase_ends_at = minutes.map(|minutes| format_time(now + Duration::minutes(minutes)));
+}
+
+/// 一轮专注结束后进入休息，每完成 `focus_long_break_every` 轮休息一次长的。返回休息分钟数。
+fn complete_work(session: &mut FocusSession, settings: &AppSettings, now: NaiveDateTime) -> i64 {
+    session.completed_rounds += 1;
+    let (phase, minutes) = if session.completed_rounds % settings.focus_long_break_every == 0 {
+        (FOCUS_LONG_BREAK, settings.focus_long_break_minutes)
+    } else {
+        (FOCUS_SHORT_BREAK, settings.focus_short_break_minutes)
+    };
+    begin_phase(session, phase, Some(minutes), now);
+    minutes
+}
+
+fn complete_break(session: &mut FocusSession, now: NaiveDateTime) {
+    session.round = session.completed_rounds + 1;
+    begin_phase(session, FOCUS_BREAK_OVER, None, now);
+}
+
+fn begin_next_round(session: &mut FocusSession, settings: &AppSettings, now: NaiveDateTime) {
+    session.round = session.completed_rounds + 1;
+    begin_phase(session, FOCUS_WORK, Some(settings.focus_work_minutes), now);
+}
+
+fn format_time(value: NaiveDateTime) -> String {
+    value.format("%Y-%m-%dT%H:%M:%S").to_string()
+}
+
+fn parse_time(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::{at, Simulation};
+
+    fn settings() -> AppSettings {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let mut settings = sim.db.load_settings().unwrap();
+        settings.focus_work_minutes = 25;
+        settings.focus_short_break_minutes = 5;
+        settings.focus_long_break_minutes = 15;
+        settings.focus_long_break_every = 3;
+        settings
+    }
+
+    fn session(settings: &AppSettings, now: NaiveDateTime) -> FocusSession {
+        let mut session = FocusSession {
+            task_id: "task".to_string(),
+            description: "写报告".to_string(),
+            phase: String::new(),
+            round: 1,
+            completed_rounds: 0,
+            phase_started_at: String::new(),
+            phase_ends_at: None,
+        };
+        begin_phase(
+            &mut session,
+            FOCUS_WORK,
+            Some(settings.focus_work_minutes),
+            now,
+        );
+        session
+    }
+
+    fn ends_at(session: &FocusSession) -> NaiveDateTime {
+        parse_time(session.phase_ends_at.as_deref().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn long_break_follows_every_nth_round() {
+        let settings = settings();
+        let mut session = session(&settings, at("2026-01-05T09:00:00"));
+        assert_eq!(
+            session.phase_ends_at.as_deref(),
+            Some("2026-01-05T09:25:00")
+        );
+
+        let mut breaks = Vec::new();
+        for _ in 0..4 {
+            let now = ends_at(&session);
+            let minutes = complete_work(&mut session, &settings, now);
+            breaks.push((session.phase.clone(), minutes));
+            let now = ends_at(&session);
+            complete_break(&mut session, now);
+            assert_eq!(session.phase, FOCUS_BREAK_OVER);
+            assert_eq!(session.phase_ends_at, None);
+            assert_eq!(session.round, session.completed_rounds + 1);
+            begin_next_round(&mut session, &settings, at("2026-01-05T12:00:00"));
+            assert_eq!(session.phase, FOCUS_WORK);
+        }
+        assert_eq!(
+            breaks,
+            vec![
+                (FOCUS_SHORT_BREAK.to_string(), 5),
+                (FOCUS_SHORT_BREAK.to_string(), 5),
+                (FOCUS_LONG_BREAK.to_string(), 15),
+                (FOCUS_SHORT_BREAK.to_string(), 5),
+            ]
+        );
+        assert_eq!(session.round, 5);
+        assert_eq!(session.completed_rounds, 4);
+    }
+
+    #[test]
+    fn skipping_work_counts_the_round_and_skipping_a_break_starts_the_next() {
+        let settings = settings();
+        let mut session = session(&settings, at("2026-01-05T09:00:00"));
+
+        // 专注中跳过：与到点结束相同，计入完成轮次并从当前时间开始休息。
+        complete_work(&mut session, &settings, at("2026-01-05T09:10:00"));
+        assert_eq!(session.phase, FOCUS_SHORT_BREAK);
+        assert_eq!(session.completed_rounds, 1);
+        assert_eq!(
+            session.phase_ends_at.as_deref(),
+            Some("2026-01-05T09:15:00")
+        );
+
+        // 休息中跳过：不等休息结束的确认，直接开始下一轮。
+        begin_next_round(&mut session, &settings, at("2026-01-05T09:12:00"));
+        assert_eq!(session.phase, FOCUS_WORK);
+        assert_eq!(session.round, 2);
+        assert_eq!(session.completed_rounds, 1);
+        assert_eq!(session.phase_started_at, "2026-01-05T09:12:00");
+        assert_eq!(
+            session.phase_ends_at.as_deref(),
+            Some("2026-01-05T09:37:00")
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, NaiveDate};
+use serde::Serialize;
+
+use crate::errors::AppError;
+use crate::models::{RecurringTask, ReminderRecord};
+
+pub const HABIT_DONE: &str = "DONE";
+pub const HABIT_SKIPPED: &str = "SKIPPED";
+pub const HABIT_MISSED: &str = "MISSED";
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HabitDay {
+    pub date: String,
+    pub state: String,
+    pub done_count: i64,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HabitSummary {
+    pub reminder_id: String,
+    pub description: String,
+    pub current_streak: i64,
+    pub longest_streak: i64,
+    pub done_days: i64,
+    pub skipped_days: i64,
+    pub missed_days: i64,
+    /// 区间内有记录的日期，用于绘制热力图；没有提醒的日期不返回。
+    pub days: Vec<HabitDay>,
+}
+
+pub fn normalize_habit_state(state: &str) -> Result<String, AppError> {
+    match state.trim().to_uppercase().as_str() {
+        HABIT_DONE => Ok(HABIT_DONE.to_string()),
+        HABIT_SKIPPED => Ok(HABIT_SKIPPED.to_string()),
+        HABIT_MISSED => Ok(HABIT_MISSED.to_string()),
+        other => Err(AppError::Invalid(format!("不支持的打卡状态: {}", other))),
+    }
+}
+
+/// 循环提醒被关闭时默认记下的打卡状态，稍后提醒不算一次结果。
+pub fn habit_state_for_action(action: &str) -> Option<&'static str> {
+    match action {
+        "PENDING" | "SNOOZED" => None,
+        "MISSED" => Some(HABIT_MISSED),
+        "SKIPPED" => Some(HABIT_SKIPPED),
+        "DISMISSED" | "COMPLETED" | "OPENED_NOTE" | "OPENED_URL" => Some(HABIT_DONE),
+        _ => None,
+    }
+}
+
+/// 用户是否已处理该提醒。未处理和被覆盖错过的都不算。
+pub fn is_acknowledged_action(action: &str) -> bool {
+    !matches!(action, "PENDING" | "MISSED")
+}
+
+fn state_rank(state: &str) -> u8 {
+    match state {
+        HABIT_DONE => 3,
+        HABIT_SKIPPED => 2,
+        HABIT_MISSED => 1,
+        _ => 0,
+    }
+}
+
+/// 按天合并打卡状态。每日多次提醒时同一天有多条记录，取最好的结果：完成 > 跳过 > 错过。
+/// 已过去的日期里仍未处理的提醒算作错过。
+fn merge_days(records: &[ReminderRecord], today: NaiveDate) -> BTreeMap<NaiveDate, HabitDay> {
+    let mut days: BTreeMap<NaiveDate, HabitDay> = BTreeMap::new();
+    for record in records {
+        let Some(date) = record
+            .trigger_time
+            .get(..10)
+            .and_then(|value| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok())
+        else {
+            continue;
+        };
+        let state = match record.habit_state.as_deref() {
+            Some(state) => state,
+            None if record.action == "PENDING" && date < today => HABIT_MISSED,
+            None => continue,
+        };
+        let day = days.entry(date).or_insert_with(|| HabitDay {
+            date: date.format("%Y-%m-%d").to_string(),
+            state: state.to_string(),
+            done_count: 0,
+        });
+        if state_rank(state) > state_rank(&day.state) {
+            day.state = state.to_string();
+        }
+        if state == HABIT_DONE {
+            day.done_count += 1;
+        }
+    }
+    days
+}
+
+/// `records` 为该循环提醒的全部历史记录，连续天数按全部历史计算，`days` 只保留区间内的日期。
+/// 跳过的日期和没有提醒的日期不打断也不累加连续天数；今天尚未完成时不影响当前连续天数。
+pub fn summarize_habit(
+    task: &RecurringTask,
+    records: &[ReminderRecord],
+    from: NaiveDate,
+    to: NaiveDate,
+    today: NaiveDate,
+) -> HabitSummary {
+    let days = merge_days(records, today);
+
+    let mut longest_streak = 0;
+    let mut running = 0;
+    for day in days.values() {
+        match day.state.as_str() {
+            HABIT_DONE => {
+                running += 1;
+                longest_streak = longest_streak.max(running);
+            }
+            HABIT_MISSED => running = 0,
+            _ => {}
+        }
+    }
+
+    let mut current_streak = 0;
+    for (date, day) in days.iter().rev() {
+        if *date > today {
+            continue;
+        }
+        match day.state.as_str() {
+            HABIT_DONE => current_streak += 1,
+            HABIT_MISSED if *date == today => {}
+            HABIT_MISSED => break,
+            _ => {}
+        }
+    }
+
+    let in_range: Vec<HabitDay> = days
+        .range(from..to + Duration::days(1))
+        .map(|(_, day)| day.clone())
+        .collect();
+    let count = |state: &str| in_range.iter().filter(|day| day.state == state).count() as i64;
+
+    HabitSummary {
+        reminder_id: task.id.clone(),
+        description: task.description.clone(),
+        current_streak,
+        longest_streak,
+        done_days: count(HABIT_DONE),
+        skipped_days: count(HABIT_SKIPPED),
+        missed_days: count(HABIT_MISSED),
+        days: in_range,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NotifyOptions;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn task() -> RecurringTask {
+        RecurringTask {
+            id: "habit".to_string(),
+            description: "喝水".to_string(),
+            task_type: "RECURRING".to_string(),
+            status: "PENDING".to_string(),
+            created_at: "2026-01-01T00:00:00".to_string(),
+            completed_at: None,
+            reminder_time: None,
+            updated_at: None,
+            deleted_at: None,
+            interval_minutes: 0,
+            last_triggered: None,
+            next_trigger: "2026-01-01T09:00:00".to_string(),
+            is_paused: false,
+            start_time: None,
+            end_time: None,
+            repeat_mode: "DAILY".to_string(),
+            schedule_time: Some("09:00".to_string()),
+            schedule_weekday: None,
+            schedule_day: None,
+            cron_expression: None,
+            notify_options: NotifyOptions::default(),
+        }
+    }
+
+    fn record(trigger: &str, action: &str) -> ReminderRecord {
+        ReminderRecord {
+            id: trigger.to_string(),
+            reminder_id: "habit".to_string(),
+            description: "喝水".to_string(),
+            reminder_type: "RECURRING".to_string(),
+            trigger_time: trigger.to_string(),
+            close_time: None,
+            action: action.to_string(),
+            updated_at: None,
+            deleted_at: None,
+            delivery_error: None,
+            habit_state: habit_state_for_action(action).map(str::to_string),
+            delivery_state: None,
+            delivered_at: None,
+            occurrence_id: None,
+        }
+    }
+
+    fn summary(records: &[ReminderRecord], today: &str) -> HabitSummary {
+        summarize_habit(
+            &task(),
+            records,
+            date("2026-01-01"),
+            date(today),
+            date(today),
+        )
+    }
+
+    #[test]
+    fn only_known_actions_map_to_a_habit_state() {
+        assert_eq!(habit_state_for_action("DISMISSED"), Some(HABIT_DONE));
+        assert_eq!(habit_state_for_action("OPENED_URL"), Some(HABIT_DONE));
+        assert_eq!(habit_state_for_action("SKIPPED"), Some(HABIT_SKIPPED));
+        assert_eq!(habit_state_for_action("MISSED"), Some(HABIT_MISSED));
+        assert_eq!(habit_state_for_action("SNOOZED"), None);
+        assert_eq!(habit_state_for_action("UNKNOWN"), None);
+    }
+
+    #[test]
+    fn streaks_skip_over_skipped_days_and_reset_on_missed() {
+        let records = vec![
+            record("2026-01-01T09:00:00", "DISMISSED"),
+            record("2026-01-02T09:00:00", "COMPLETED"),
+            record("2026-01-03T09:00:00", "DISMISSED"),
+            record("2026-01-04T09:00:00", "MISSED"),
+            record("2026-01-05T09:00:00", "DISMISSED"),
+            record("2026-01-06T09:00:00", "SKIPPED"),
+            record("2026-01-07T09:00:00", "DISMISSED"),
+        ];
+        let summary = summary(&records, "2026-01-07");
+        assert_eq!(summary.longest_streak, 3);
+        assert_eq!(summary.current_streak, 2);
+        assert_eq!(summary.done_days, 5);
+        assert_eq!(summary.skipped_days, 1);
+        assert_eq!(summary.missed_days, 1);
+    }
+
+    #[test]
+    fn pending_record_counts_as_missed_once_its_day_has_passed() {
+        let records = vec![
+            record("2026-01-03T09:00:00", "DISMISSED"),
+            record("2026-01-04T09:00:00", "PENDING"),
+            record("2026-01-05T09:00:00", "PENDING"),
+        ];
+        let summary = summary(&records, "2026-01-05");
+        assert_eq!(summary.missed_days, 1);
+        assert_eq!(summary.current_streak, 0);
+        let states: Vec<&str> = summary.days.iter().map(|day| day.state.as_str()).collect();
+        assert_eq!(states, vec![HABIT_DONE, HABIT_MISSED]);
+    }
+
+    #[test]
+    fn records_on_the_same_day_merge_to_the_best_state() {
+        let records = vec![
+            record("2026-01-05T09:00:00", "MISSED"),
+            record("2026-01-05T13:00:00", "DISMISSED"),
+            record("2026-01-05T17:00:00", "COMPLETED"),
+            record("2026-01-06T09:00:00", "MISSED"),
+            record("2026-01-06T13:00:00", "SKIPPED"),
+        ];
+        let summary = summary(&records, "2026-01-06");
+        assert_eq!(summary.days.len(), 2);
+        assert_eq!(summary.days[0].state, HABIT_DONE);
+        assert_eq!(summary.days[0].done_count, 2);
+        assert_eq!(summary.days[1].state, HABIT_SKIPPED);
+        assert_eq!(summary.missed_days, 0);
+        assert_eq!(summary.current_streak, 1);
+    }
+}
@@ -0,0 +1,114 @@
+use chrono::{Duration, NaiveDateTime};
+
+use crate::errors::AppError;
+
+/// 提前提醒生成的提醒记录类型，reminder_id 为所属任务或循环任务的 id。
+pub const LEAD_REMINDER_TYPE: &str = "LEAD";
+const MAX_LEAD_OFFSETS: usize = 5;
+/// 最多提前 30 天。
+const MAX_LEAD_MINUTES: i64 = 30 * 24 * 60;
+
+/// 校验并整理提前量：去重后从早到晚（提前量从大到小）排列。
+pub fn normalize_lead_minutes(minutes: &[i64]) -> Result<Vec<i64>, AppError> {
+    let mut normalized = Vec::new();
+    for &value in minutes {
+        if value <= 0 {
+            return Err(AppError::Invalid("提前提醒的时间必须大于 0".to_string()));
+        }
+        if value > MAX_LEAD_MINUTES {
+            return Err(AppError::Invalid("提前提醒最多提前 30 天".to_string()));
+        }
+        if !normalized.contains(&value) {
+            normalized.push(value);
+        }
+    }
+    if normalized.len() > MAX_LEAD_OFFSETS {
+        return Err(AppError::Invalid(format!(
+            "提前提醒最多 {} 个",
+            MAX_LEAD_OFFSETS
+        )));
+    }
+    normalized.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(normalized)
+}
+
+/// 设置中以逗号分隔保存，无法识别的项直接忽略。
+pub fn parse_lead_list(value: &str) -> Vec<i64> {
+    let mut minutes: Vec<i64> = value
+        .split(',')
+        .filter_map(|item| item.trim().parse().ok())
+        .filter(|value| (1..=MAX_LEAD_MINUTES).contains(value))
+        .collect();
+    minutes.sort_unstable_by(|a, b| b.cmp(a));
+    minutes.dedup();
+    minutes.truncate(MAX_LEAD_OFFSETS);
+    minutes
+}
+
+pub fn format_lead_list(minutes: &[i64]) -> String {
+    minutes
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+pub fn lead_deadline(scheduled: NaiveDateTime, minutes: i64) -> NaiveDateTime {
+    scheduled - Duration::minutes(minutes)
+}
+
+/// 同一次触发的每个提前量各有一条记录，记录的 occurrence_id 仍指向所属的触发点。
+pub fn lead_record_id(occurrence_id: &str, minutes: i64) -> String {
+    format!("{}#-{}m", occurrence_id, minutes)
+}
+
+pub fn lead_description(description: &str, minutes: i64) -> String {
+    format!("{}后: {}", format_lead_offset(minutes), description)
+}
+
+pub fn format_lead_offset(minutes: i64) -> String {
+    let days = minutes / (24 * 60);
+    let hours = minutes % (24 * 60) / 60;
+    let rest = minutes % 60;
+    let mut parts = Vec::new();
+    if days > 0 {
+        parts.push(format!("{} 天", days));
+    }
+    if hours > 0 {
+        parts.push(format!("{} 小时", hours));
+    }
+    if rest > 0 {
+        parts.push(format!("{} 分钟", rest));
+    }
+    parts.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_dedupes_and_orders_earliest_first() {
+        assert_eq!(
+            normalize_lead_minutes(&[10, 1440, 60, 10]).unwrap(),
+            vec![1440, 60, 10]
+        );
+        assert!(normalize_lead_minutes(&[0]).is_err());
+        assert!(normalize_lead_minutes(&[MAX_LEAD_MINUTES + 1]).is_err());
+        assert!(normalize_lead_minutes(&[1, 2, 3, 4, 5, 6]).is_err());
+    }
+
+    #[test]
+    fn settings_list_ignores_invalid_items() {
+        assert_eq!(parse_lead_list("60, abc,10,-5,"), vec![60, 10]);
+        assert_eq!(parse_lead_list(""), Vec::<i64>::new());
+        assert_eq!(format_lead_list(&[1440, 10]), "1440,10");
+    }
+
+    #[test]
+    fn offsets_are_described_in_largest_units() {
+        assert_eq!(format_lead_offset(1440), "1 天");
+        assert_eq!(format_lead_offset(90), "1 小时 30 分钟");
+        assert_eq!(lead_description("交周报", 10), "10 分钟后: 交周报");
+    }
+}
@@ -1,37 +1,72 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod actions;
+mod activity;
 mod autostart;
+mod clock;
 mod db;
+mod digest;
+mod dispatch;
+mod due;
+mod email;
 mod errors;
+mod focus;
+mod habits;
+mod leads;
 mod maintenance;
 mod models;
+mod mqtt;
+mod notification;
 mod paths;
+mod pause;
+mod queue;
 mod recurrence;
+mod report;
 mod scheduler;
+#[cfg(test)]
+mod sim;
 mod single_instance;
+mod sound;
+mod speech;
 mod state;
+mod stats;
 mod sync;
+mod task_reminders;
+mod timers;
 mod tray;
+mod webhook;
 
 use std::sync::{Arc, Mutex};
 
 use chrono::Local;
 use serde::{Deserialize, Serialize};
 use tauri::{
-    Emitter, LogicalPosition, LogicalSize, Manager, State, WebviewUrl, WebviewWindowBuilder,
-    WindowEvent,
+    Emitter, LogicalPosition, LogicalSize, Manager, PhysicalPosition, PhysicalSize, State,
+    WebviewUrl, WebviewWindowBuilder, WindowEvent,
 };
 
+use crate::activity::{
+    platform_idle_sources, platform_lock_sources, ActivityMonitor, ActivityStatus, IdleDetector,
+};
 use crate::db::DbManager;
+use crate::dispatch::ReminderDispatcher;
 use crate::errors::AppError;
+use crate::focus::{FocusService, FocusSession};
+use crate::habits::{normalize_habit_state, summarize_habit, HabitSummary};
 use crate::models::{
-    AppSettings, NotificationPayload, RecurringTask, ReminderRecord, StickyNote, SyncStatus, Task,
-    UiStatePayload,
+    AppSettings, MonitorInfo, NotificationPayload, NotifyOptions, PauseState, RecurringTask,
+    ReminderRecord, StickyNote, SyncStatus, Task, TaskReminder, TimeEntry, Timer, UiStatePayload,
+    UpcomingReminder, WebhookChannel, WebhookDelivery,
 };
-use crate::scheduler::ReminderScheduler;
+use crate::mqtt::MqttService;
+use crate::scheduler::{ReminderScheduler, SCHEDULE_RECONCILED_EVENT};
 use crate::single_instance::InstanceLock;
+use crate::sound::{SoundInfo, SoundService};
+use crate::speech::SpeechService;
 use crate::state::AppState;
+use crate::stats::{compute_reminder_stats, resolve_stats_range, ReminderStats};
 use crate::sync::CloudSyncService;
+use crate::webhook::WebhookService;
 
 type ApiResult<T> = Result<T, String>;
 const STICKY_NOTE_ITEM_PREFIX: &str = "sticky-note-item-";
@@ -45,6 +80,8 @@ const STICKY_NOTE_DEFAULT_OPACITY: f64 = 0.95;
 const WINDOW_MIN_OPACITY: f64 = 0.3;
 const WINDOW_MAX_OPACITY: f64 = 1.0;
 const WINDOW_DEFAULT_OPACITY: f64 = 1.0;
+const UPCOMING_DEFAULT_LIMIT: usize = 10;
+const UPCOMING_MAX_LIMIT: usize = 50;
 
 fn into_api<T>(result: Result<T, AppError>) -> ApiResult<T> {
     result.map_err(|e| e.to_string())
@@ -90,6 +127,22 @@ struct TaskUpdatePayload {
     description: String,
     sticky_content: Option<String>,
     reminder_time: Option<String>,
+    /// 全部提醒时间；未提供时按 `reminder_time` 作为唯一的提醒时间。
+    #[serde(default)]
+    reminder_times: Option<Vec<String>>,
+    /// 未提供时保留原截止时间，为 null 或空白时清除。
+    #[serde(default, deserialize_with = "deserialize_present")]
+    due_at: Option<Option<String>>,
+    notify_options: Option<NotifyOptions>,
+}
+
+/// 区分字段缺失与显式的 null：缺失时为 `None`，其余包一层 `Some`。
+fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
 }
 
 #[derive(Deserialize)]
@@ -111,6 +164,7 @@ struct CreateRecurringPayload {
     schedule_weekday: Option<i64>,
     schedule_day: Option<i64>,
     cron_expression: Option<String>,
+    notify_options: Option<NotifyOptions>,
 }
 
 #[derive(Deserialize)]
@@ -120,6 +174,15 @@ struct AckPayload {
     action: String,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NotificationActionPayload {
+    record_id: String,
+    reminder_id: String,
+    reminder_type: String,
+    action_id: String,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct SnoozePayload {
@@ -129,6 +192,13 @@ struct SnoozePayload {
     minutes: i64,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StartTimerPayload {
+    label: Option<String>,
+    seconds: i64,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct OpenStickyNotePayload {
@@ -184,8 +254,14 @@ struct DebugInfo {
 }
 
 #[tauri::command]
-fn list_active_tasks(state: State<AppState>) -> ApiResult<Vec<Task>> {
-    into_api(state.db.list_active_tasks())
+fn list_active_tasks(
+    state: State<AppState>,
+    sort: Option<String>,
+    filter: Option<String>,
+) -> ApiResult<Vec<Task>> {
+    let sort = into_api(due::normalize_task_sort(sort.as_deref()))?;
+    let filter = into_api(due::normalize_task_filter(filter.as_deref()))?;
+    into_api(state.db.query_active_tasks(sort, filter))
 }
 
 #[tauri::command]
@@ -203,30 +279,110 @@ fn list_reminder_records(state: State<AppState>) -> ApiResult<Vec<ReminderRecord
     into_api(state.db.list_reminder_records())
 }
 
+#[tauri::command]
+fn get_reminder_stats(
+    state: State<AppState>,
+    from: Option<String>,
+    to: Option<String>,
+) -> ApiResult<ReminderStats> {
+    let today = state.db.clock().now().date();
+    let (from, to) = into_api(resolve_stats_range(from.as_deref(), to.as_deref(), today))?;
+    let records = into_api(
+        state.db.list_reminder_records_between(
+            &from.format("%Y-%m-%dT00:00:00").to_string(),
+            &(to + chrono::Duration::days(1))
+                .format("%Y-%m-%dT00:00:00")
+                .to_string(),
+        ),
+    )?;
+    Ok(compute_reminder_stats(from, to, &records))
+}
+
+#[tauri::command]
+fn get_habit_summaries(
+    state: State<AppState>,
+    reminder_id: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+) -> ApiResult<Vec<HabitSummary>> {
+    let today = state.db.clock().now().date();
+    let (from, to) = into_api(resolve_stats_range(from.as_deref(), to.as_deref(), today))?;
+    let tasks = into_api(state.db.list_recurring_tasks())?;
+    let mut summaries = Vec::new();
+    for task in tasks
+        .iter()
+        .filter(|task| reminder_id.as_deref().is_none_or(|id| id == task.id))
+    {
+        let records = into_api(state.db.list_reminder_records_for(&task.id))?;
+        summaries.push(summarize_habit(task, &records, from, to, today));
+    }
+    Ok(summaries)
+}
+
+#[tauri::command]
+fn set_habit_state(
+    state: State<AppState>,
+    record_id: String,
+    habit_state: Option<String>,
+) -> ApiResult<()> {
+    let habit_state = match habit_state.as_deref() {
+        Some(value) => Some(into_api(normalize_habit_state(value))?),
+        None => None,
+    };
+    into_api(
+        state
+            .db
+            .update_reminder_record_habit_state(&record_id, habit_state.as_deref()),
+    )?;
+    into_api(state.sync.notify_local_change())
+}
+
 #[tauri::command]
 fn create_task(state: State<AppState>, payload: CreateTaskPayload) -> ApiResult<Task> {
-    let task = into_api(state.db.create_task(
+    let mut task = into_api(state.db.create_task(
         payload.description.trim(),
         payload.sticky_content.as_deref(),
     ))?;
+    let settings = into_api(state.db.load_settings())?;
+    if !settings.default_lead_minutes.is_empty() {
+        task.notify_options.lead_minutes = settings.default_lead_minutes;
+        into_api(
+            state
+                .db
+                .update_task_notify_options(&task.id, &task.notify_options),
+        )?;
+    }
     into_api(state.sync.notify_local_change())?;
     Ok(task)
 }
 
 #[tauri::command]
 fn update_task(state: State<AppState>, task: TaskUpdatePayload) -> ApiResult<()> {
+    let reminder_times = match &task.reminder_times {
+        Some(times) => times.clone(),
+        None => task.reminder_time.iter().cloned().collect(),
+    };
+    let reminder_times = into_api(task_reminders::normalize_reminder_times(&reminder_times))?;
+    let due_at = match &task.due_at {
+        Some(value) => Some(into_api(due::normalize_due_at(value.as_deref()))?),
+        None => None,
+    };
+    let mut notify_options = task.notify_options;
+    if let Some(options) = notify_options.as_mut() {
+        into_api(actions::sanitize_notify_options(options, true))?;
+    }
     into_api(state.db.update_task(
         &task.id,
         task.description.trim(),
         task.sticky_content.clone(),
-        task.reminder_time.clone(),
+        &reminder_times,
+        due_at.as_ref().map(Option::as_deref),
+        notify_options.as_ref(),
     ))?;
     state.scheduler.cancel_task(&task.id);
-    if let Some(reminder_time) = task.reminder_time {
-        if scheduler::is_future(&reminder_time).unwrap_or(false) {
-            if let Some(updated) = into_api(state.db.get_task(&task.id))? {
-                into_api(state.scheduler.schedule_task(updated))?;
-            }
+    if let Some(updated) = into_api(state.db.get_task(&task.id))? {
+        if updated.status != "COMPLETED" {
+            into_api(state.scheduler.schedule_task(updated))?;
         }
     }
     into_api(state.sync.notify_local_change())?;
@@ -235,9 +391,14 @@ fn update_task(state: State<AppState>, task: TaskUpdatePayload) -> ApiResult<()>
 
 #[tauri::command]
 fn complete_task(state: State<AppState>, id: String) -> ApiResult<()> {
-    into_api(state.db.complete_task(&id))?;
-    state.scheduler.cancel_task(&id);
-    into_api(state.sync.notify_local_change())?;
+    into_api(complete_task_via_app(state.inner(), &id))
+}
+
+pub(crate) fn complete_task_via_app(state: &AppState, task_id: &str) -> Result<(), AppError> {
+    state.focus.stop_for_task(task_id)?;
+    state.db.complete_task(task_id)?;
+    state.scheduler.cancel_task(task_id);
+    state.sync.notify_local_change()?;
     Ok(())
 }
 
@@ -245,11 +406,7 @@ fn complete_task(state: State<AppState>, id: String) -> ApiResult<()> {
 fn uncomplete_task(state: State<AppState>, id: String) -> ApiResult<()> {
     into_api(state.db.uncomplete_task(&id))?;
     if let Some(task) = into_api(state.db.get_task(&id))? {
-        if let Some(reminder_time) = &task.reminder_time {
-            if scheduler::is_future(reminder_time).unwrap_or(false) {
-                into_api(state.scheduler.schedule_task(task))?;
-            }
-        }
+        into_api(state.scheduler.schedule_task(task))?;
     }
     into_api(state.sync.notify_local_change())?;
     Ok(())
@@ -257,6 +414,7 @@ fn uncomplete_task(state: State<AppState>, id: String) -> ApiResult<()> {
 
 #[tauri::command]
 fn delete_task(state: State<AppState>, id: String) -> ApiResult<()> {
+    into_api(state.focus.stop_for_task(&id))?;
     into_api(state.db.delete_task(&id))?;
     state.scheduler.cancel_task(&id);
     into_api(state.sync.notify_local_change())?;
@@ -268,6 +426,13 @@ fn create_recurring_task(
     state: State<AppState>,
     payload: CreateRecurringPayload,
 ) -> ApiResult<RecurringTask> {
+    let notify_options = match payload.notify_options {
+        Some(options) => options,
+        None => NotifyOptions {
+            lead_minutes: into_api(state.db.load_settings())?.default_lead_minutes,
+            ..NotifyOptions::default()
+        },
+    };
     let mut draft = RecurringTask {
         id: String::new(),
         description: payload.description.trim().to_string(),
@@ -291,9 +456,17 @@ fn create_recurring_task(
         schedule_weekday: payload.schedule_weekday,
         schedule_day: payload.schedule_day,
         cron_expression: payload.cron_expression,
+        notify_options,
     };
     into_api(recurrence::sanitize_recurring_task(&mut draft))?;
-    draft.next_trigger = into_api(recurrence::compute_next_trigger(&draft, None))?;
+    into_api(actions::sanitize_notify_options(
+        &mut draft.notify_options,
+        false,
+    ))?;
+    draft.next_trigger = into_api(recurrence::compute_next_trigger(
+        &draft,
+        state.db.clock().now(),
+    ))?;
     let task = into_api(state.db.create_recurring_task(&draft))?;
     if !task.is_paused {
         into_api(state.scheduler.schedule_recurring(task.clone()))?;
@@ -306,7 +479,14 @@ fn create_recurring_task(
 fn update_recurring_task(state: State<AppState>, task: RecurringTask) -> ApiResult<()> {
     let mut task = task;
     into_api(recurrence::sanitize_recurring_task(&mut task))?;
-    task.next_trigger = into_api(recurrence::compute_next_trigger(&task, None))?;
+    into_api(actions::sanitize_notify_options(
+        &mut task.notify_options,
+        false,
+    ))?;
+    task.next_trigger = into_api(recurrence::compute_next_trigger(
+        &task,
+        state.db.clock().now(),
+    ))?;
     into_api(state.db.update_recurring_task(&task))?;
     if task.is_paused {
         state.scheduler.cancel_recurring(&task.id);
@@ -332,7 +512,10 @@ fn resume_recurring_task(state: State<AppState>, id: String) -> ApiResult<()> {
     };
     task.is_paused = false;
     into_api(recurrence::sanitize_recurring_task(&mut task))?;
-    task.next_trigger = into_api(recurrence::compute_next_trigger(&task, None))?;
+    task.next_trigger = into_api(recurrence::compute_next_trigger(
+        &task,
+        state.db.clock().now(),
+    ))?;
     into_api(state.db.update_recurring_task(&task))?;
     into_api(state.scheduler.schedule_recurring(task))?;
     into_api(state.sync.notify_local_change())?;
@@ -387,6 +570,7 @@ fn save_settings(
     let _ = app.emit("sticky-note-settings-updated", sanitized_settings.clone());
     let _ = app.emit("settings-updated", sanitized_settings);
     into_api(state.sync.update_settings())?;
+    into_api(state.mqtt.restart())?;
     into_api(state.sync.notify_local_change())?;
     Ok(())
 }
@@ -408,12 +592,27 @@ fn open_sticky_note(
     state: State<AppState>,
     payload: OpenStickyNotePayload,
 ) -> ApiResult<StickyNote> {
-    let note = into_api(state.db.open_sticky_note(
+    into_api(open_sticky_note_via_app(
+        &app,
+        state.inner(),
         &payload.task_id,
         payload.title,
         payload.default_x,
         payload.default_y,
-    ))?;
+    ))
+}
+
+pub(crate) fn open_sticky_note_via_app(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    task_id: &str,
+    title: Option<String>,
+    default_x: Option<f64>,
+    default_y: Option<f64>,
+) -> Result<StickyNote, AppError> {
+    let note = state
+        .db
+        .open_sticky_note(task_id, title, default_x, default_y)?;
     {
         let app_for_show = app.clone();
         let note_for_show = note.clone();
@@ -465,9 +664,14 @@ pub(crate) fn create_custom_sticky_note_via_app(
     default_width: Option<f64>,
     default_height: Option<f64>,
 ) -> Result<StickyNote, AppError> {
-    let note = state
-        .db
-        .create_custom_sticky_note(title, content, default_x, default_y, default_width, default_height)?;
+    let note = state.db.create_custom_sticky_note(
+        title,
+        content,
+        default_x,
+        default_y,
+        default_width,
+        default_height,
+    )?;
     {
         let app_for_show = app.clone();
         let note_for_show = note.clone();
@@ -597,10 +801,24 @@ fn get_sticky_note_pinned_by_window_label(
 }
 
 #[tauri::command]
-fn test_webdav(settings: AppSettings) -> ApiResult<WebDavTestResult> {
+fn test_webdav(settings: AppSettings) -> ApiResult<ConnectionTestResult> {
     match sync::test_webdav(&settings) {
-        Ok((ok, message)) => Ok(WebDavTestResult { ok, message }),
-        Err(err) => Ok(WebDavTestResult {
+        Ok((ok, message)) => Ok(ConnectionTestResult { ok, message }),
+        Err(err) => Ok(ConnectionTestResult {
+            ok: false,
+            message: err.to_string(),
+        }),
+    }
+}
+
+#[tauri::command]
+fn test_smtp(settings: AppSettings) -> ApiResult<ConnectionTestResult> {
+    match email::send_test_email(&settings) {
+        Ok(()) => Ok(ConnectionTestResult {
+            ok: true,
+            message: "测试邮件已发送".to_string(),
+        }),
+        Err(err) => Ok(ConnectionTestResult {
             ok: false,
             message: err.to_string(),
         }),
@@ -608,7 +826,7 @@ fn test_webdav(settings: AppSettings) -> ApiResult<WebDavTestResult> {
 }
 
 #[derive(serde::Serialize)]
-struct WebDavTestResult {
+struct ConnectionTestResult {
     ok: bool,
     message: String,
 }
@@ -633,56 +851,410 @@ fn set_autostart(state: State<AppState>, enabled: bool) -> ApiResult<()> {
 
 #[tauri::command]
 fn ack_notification(state: State<AppState>, payload: AckPayload) -> ApiResult<()> {
-    if into_api(state.db.get_reminder_record(&payload.record_id))?.is_some() {
-        into_api(
-            state
-                .db
-                .update_reminder_record_action(&payload.record_id, &payload.action),
-        )?;
-        into_api(state.sync.notify_local_change())?;
+    into_api(acknowledge_record(
+        state.inner(),
+        &payload.record_id,
+        &payload.action,
+    ))
+}
+
+pub(crate) fn acknowledge_record(
+    state: &AppState,
+    record_id: &str,
+    action: &str,
+) -> Result<(), AppError> {
+    state.focus.notification_acknowledged(record_id)?;
+    if state.db.get_reminder_record(record_id)?.is_some() {
+        state.db.update_reminder_record_action(record_id, action)?;
+        state.sync.notify_local_change()?;
+        if let Some(record) = state.db.get_reminder_record(record_id)? {
+            state.dispatcher.reminder_closed(&record);
+        }
     }
     *state.notification_snapshot.lock().unwrap() = None;
     Ok(())
 }
 
+#[tauri::command]
+fn run_notification_action(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    payload: NotificationActionPayload,
+) -> ApiResult<()> {
+    let available = match payload.reminder_type.as_str() {
+        "TASK" => into_api(state.db.get_task(&payload.reminder_id))?
+            .map(|task| actions::task_actions(&task))
+            .unwrap_or_default(),
+        "RECURRING" => into_api(state.db.get_recurring_task(&payload.reminder_id))?
+            .map(|task| actions::recurring_actions(&task))
+            .unwrap_or_default(),
+        due::DUE_REMINDER_TYPE => into_api(state.db.get_task(&payload.reminder_id))?
+            .map(|task| actions::task_actions(&task))
+            .unwrap_or_default(),
+        leads::LEAD_REMINDER_TYPE => match into_api(state.db.get_task(&payload.reminder_id))? {
+            Some(task) => actions::task_actions(&task),
+            None => into_api(state.db.get_recurring_task(&payload.reminder_id))?
+                .map(|task| actions::recurring_actions(&task))
+                .unwrap_or_default(),
+        },
+        _ => Vec::new(),
+    };
+    let Some(action) = actions::find_action(&available, &payload.action_id) else {
+        return Err("找不到对应的通知按钮".to_string());
+    };
+    let record_action = match action.kind.as_str() {
+        actions::ACTION_KIND_COMPLETE_TASK => {
+            into_api(complete_task_via_app(state.inner(), &payload.reminder_id))?;
+            "COMPLETED"
+        }
+        actions::ACTION_KIND_OPEN_STICKY_NOTE => {
+            into_api(open_sticky_note_via_app(
+                &app,
+                state.inner(),
+                &payload.reminder_id,
+                None,
+                None,
+                None,
+            ))?;
+            "OPENED_NOTE"
+        }
+        actions::ACTION_KIND_OPEN_URL => {
+            into_api(actions::open_url(action.url.as_deref().unwrap_or_default()))?;
+            "OPENED_URL"
+        }
+        _ => return Err(format!("不支持的按钮类型: {}", action.kind)),
+    };
+    into_api(acknowledge_record(
+        state.inner(),
+        &payload.record_id,
+        record_action,
+    ))
+}
+
+/// 生成周报；`target` 为 "CLIPBOARD" 时复制到剪贴板，否则写入 `path`（为空时使用默认目录），返回写入位置。
+#[tauri::command]
+fn generate_weekly_report(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    date: Option<String>,
+    format: Option<String>,
+    target: String,
+    path: Option<String>,
+) -> ApiResult<String> {
+    let today = state.db.clock().now().date();
+    let date = match date.as_deref().filter(|value| !value.trim().is_empty()) {
+        Some(value) => into_api(
+            chrono::NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+                .map_err(|_| AppError::Invalid(format!("日期格式不正确: {}", value))),
+        )?,
+        None => today,
+    };
+    let settings = into_api(state.db.load_settings())?;
+    let format = format.unwrap_or(settings.report_format);
+    let report = into_api(report::build_weekly_report(&state.db, date, today))?;
+    let content = report::render_report(&report, &format);
+    if target == "CLIPBOARD" {
+        into_api(report::copy_to_clipboard(&content))?;
+        return Ok("剪贴板".to_string());
+    }
+    let target = match path
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        Some(value) => std::path::PathBuf::from(value),
+        None if !settings.report_dir.trim().is_empty() => {
+            std::path::PathBuf::from(settings.report_dir.trim())
+        }
+        None => paths::default_reports_dir(&into_api(paths::resolve_data_dir(&app))?),
+    };
+    let written = into_api(report::write_report(
+        &target,
+        &report::report_file_name(&report, &format),
+        &content,
+    ))?;
+    Ok(written.display().to_string())
+}
+
+#[tauri::command]
+fn send_digest_now(state: State<AppState>) -> ApiResult<()> {
+    let settings = into_api(state.db.load_settings())?;
+    into_api(state.scheduler.send_digest(&settings))
+}
+
+#[tauri::command]
+fn open_digest_item(
+    app: tauri::AppHandle,
+    state: State<AppState>,
+    kind: String,
+    id: String,
+) -> ApiResult<()> {
+    match kind.as_str() {
+        digest::DIGEST_ITEM_TASK | digest::DIGEST_ITEM_OVERDUE | digest::DIGEST_ITEM_NOTE => {
+            into_api(open_sticky_note_via_app(
+                &app,
+                state.inner(),
+                &id,
+                None,
+                None,
+                None,
+            ))?;
+        }
+        _ => tray::show_main(&app),
+    }
+    Ok(())
+}
+
 #[tauri::command]
 fn snooze_notification(state: State<AppState>, payload: SnoozePayload) -> ApiResult<()> {
-    let minutes = payload.minutes.max(1);
-    into_api(
-        state
-            .db
-            .update_reminder_record_action(&payload.record_id, "SNOOZED"),
-    )?;
-    match payload.reminder_type.as_str() {
+    into_api(snooze_record_via_app(
+        state.inner(),
+        &payload.record_id,
+        &payload.reminder_id,
+        &payload.reminder_type,
+        payload.minutes,
+    ))
+}
+
+pub(crate) fn snooze_record_via_app(
+    state: &AppState,
+    record_id: &str,
+    reminder_id: &str,
+    reminder_type: &str,
+    minutes: i64,
+) -> Result<(), AppError> {
+    // 提前提醒与逾期提醒到点后不会再次安排，稍后提醒没有意义。
+    if matches!(
+        reminder_type,
+        leads::LEAD_REMINDER_TYPE | due::DUE_REMINDER_TYPE
+    ) {
+        return Err(AppError::Invalid("该提醒不支持稍后提醒".to_string()));
+    }
+    let minutes = minutes.max(1);
+    state
+        .db
+        .update_reminder_record_action(record_id, "SNOOZED")?;
+    if let Some(record) = state.db.get_reminder_record(record_id)? {
+        state.dispatcher.reminder_snoozed(&record, minutes);
+    }
+    match reminder_type {
         "TASK" => {
-            if let Some(mut task) = into_api(state.db.get_task(&payload.reminder_id))? {
-                let reminder_time = add_minutes(minutes);
-                into_api(state.db.update_task(
-                    &task.id,
-                    &task.description,
-                    task.sticky_content.clone(),
-                    Some(reminder_time.clone()),
-                ))?;
-                task.reminder_time = Some(reminder_time);
-                state.scheduler.cancel_task(&task.id);
-                into_api(state.scheduler.schedule_task(task))?;
+            if let Some(task) = state.db.get_task(reminder_id)? {
+                let remind_at = add_minutes(state.db.clock().now(), minutes);
+                let reminder = state.db.add_task_snooze(&task.id, &remind_at)?;
+                state.scheduler.snooze_task(task, &reminder.id)?;
             }
         }
         "RECURRING" => {
-            if let Some(mut task) = into_api(state.db.get_recurring_task(&payload.reminder_id))? {
-                task.next_trigger = add_minutes(minutes);
+            if let Some(mut task) = state.db.get_recurring_task(reminder_id)? {
+                task.next_trigger = add_minutes(state.db.clock().now(), minutes);
                 task.is_paused = false;
-                into_api(state.db.update_recurring_task(&task))?;
-                into_api(state.scheduler.schedule_recurring(task))?;
+                state.db.update_recurring_task(&task)?;
+                state.scheduler.snooze_recurring(task)?;
+            }
+        }
+        focus::FOCUS_REMINDER_TYPE => {
+            state.focus.extend_break(minutes)?;
+        }
+        activity::BREAK_REMINDER_TYPE => {
+            state.activity.snooze(minutes);
+        }
+        timers::TIMER_REMINDER_TYPE => {
+            // 计时器到点后已删除，稍后提醒即按原名称重新计时。
+            if let Some(record) = state.db.get_reminder_record(record_id)? {
+                state
+                    .scheduler
+                    .start_timer(Some(&record.description), minutes * 60)?;
             }
         }
         _ => {}
     }
-    into_api(state.sync.notify_local_change())?;
+    state.sync.notify_local_change()?;
     *state.notification_snapshot.lock().unwrap() = None;
     Ok(())
 }
 
+#[tauri::command]
+fn get_activity_status(state: State<AppState>) -> ApiResult<ActivityStatus> {
+    Ok(state.activity.status())
+}
+
+#[tauri::command]
+fn get_focus_session(state: State<AppState>) -> ApiResult<Option<FocusSession>> {
+    Ok(state.focus.current())
+}
+
+#[tauri::command]
+fn start_focus(state: State<AppState>, task_id: String) -> ApiResult<FocusSession> {
+    into_api(state.focus.start(&task_id))
+}
+
+#[tauri::command]
+fn stop_focus(state: State<AppState>) -> ApiResult<()> {
+    into_api(state.focus.stop())
+}
+
+#[tauri::command]
+fn skip_focus_phase(state: State<AppState>) -> ApiResult<()> {
+    into_api(state.focus.skip())
+}
+
+#[tauri::command]
+fn list_task_reminders(state: State<AppState>, task_id: String) -> ApiResult<Vec<TaskReminder>> {
+    into_api(state.db.list_task_reminders(&task_id))
+}
+
+#[tauri::command]
+fn list_time_entries(state: State<AppState>, task_id: String) -> ApiResult<Vec<TimeEntry>> {
+    into_api(state.db.list_time_entries(&task_id))
+}
+
+#[tauri::command]
+fn delete_time_entry(state: State<AppState>, id: String) -> ApiResult<()> {
+    into_api(state.db.delete_time_entry(&id))?;
+    into_api(state.sync.notify_local_change())
+}
+
+#[tauri::command]
+fn list_upcoming_reminders(
+    state: State<AppState>,
+    limit: Option<usize>,
+) -> ApiResult<Vec<UpcomingReminder>> {
+    let limit = limit
+        .unwrap_or(UPCOMING_DEFAULT_LIMIT)
+        .clamp(1, UPCOMING_MAX_LIMIT);
+    into_api(state.scheduler.upcoming(limit))
+}
+
+#[tauri::command]
+fn get_pause_state(state: State<AppState>) -> ApiResult<PauseState> {
+    into_api(state.db.load_pause_state())
+}
+
+/// 全局暂停全部提醒，`resume_at` 为空时需手动恢复。
+#[tauri::command]
+fn pause_all_reminders(state: State<AppState>, resume_at: Option<String>) -> ApiResult<PauseState> {
+    let resume_at = match resume_at.as_deref().map(str::trim) {
+        Some(value) if !value.is_empty() => {
+            Some(due::parse_time(value).ok_or_else(|| format!("无法解析恢复时间: {}", value))?)
+        }
+        _ => None,
+    };
+    into_api(state.scheduler.pause_all(resume_at))
+}
+
+#[tauri::command]
+fn resume_all_reminders(state: State<AppState>) -> ApiResult<()> {
+    into_api(state.scheduler.resume_all())
+}
+
+#[tauri::command]
+fn list_timers(state: State<AppState>) -> ApiResult<Vec<Timer>> {
+    let timers = into_api(state.db.list_timers())?;
+    Ok(timers::with_remaining(timers, state.db.clock().now()))
+}
+
+#[tauri::command]
+fn start_timer(state: State<AppState>, payload: StartTimerPayload) -> ApiResult<Timer> {
+    into_api(
+        state
+            .scheduler
+            .start_timer(payload.label.as_deref(), payload.seconds),
+    )
+}
+
+#[tauri::command]
+fn pause_timer(state: State<AppState>, id: String) -> ApiResult<()> {
+    into_api(state.scheduler.pause_timer(&id))
+}
+
+#[tauri::command]
+fn resume_timer(state: State<AppState>, id: String) -> ApiResult<()> {
+    into_api(state.scheduler.resume_timer(&id))
+}
+
+#[tauri::command]
+fn cancel_timer(state: State<AppState>, id: String) -> ApiResult<()> {
+    into_api(state.scheduler.stop_timer(&id))
+}
+
+#[tauri::command]
+fn list_webhook_channels(state: State<AppState>) -> ApiResult<Vec<WebhookChannel>> {
+    into_api(state.db.list_webhook_channels())
+}
+
+#[tauri::command]
+fn save_webhook_channel(
+    state: State<AppState>,
+    mut channel: WebhookChannel,
+) -> ApiResult<WebhookChannel> {
+    into_api(webhook::sanitize_webhook_channel(&mut channel))?;
+    into_api(state.db.save_webhook_channel(&channel))
+}
+
+#[tauri::command]
+fn delete_webhook_channel(state: State<AppState>, channel_id: String) -> ApiResult<()> {
+    into_api(state.db.delete_webhook_channel(&channel_id))
+}
+
+#[tauri::command]
+fn test_webhook_channel(
+    state: State<AppState>,
+    mut channel: WebhookChannel,
+) -> ApiResult<WebhookDelivery> {
+    into_api(webhook::sanitize_webhook_channel(&mut channel))?;
+    Ok(state.webhooks.send_test(&channel))
+}
+
+#[tauri::command]
+fn list_webhook_deliveries(
+    state: State<AppState>,
+    limit: Option<i64>,
+) -> ApiResult<Vec<WebhookDelivery>> {
+    into_api(state.db.list_webhook_deliveries(limit.unwrap_or(50)))
+}
+
+#[tauri::command]
+fn list_sounds(state: State<AppState>) -> ApiResult<Vec<SoundInfo>> {
+    Ok(state.sound.list_sounds())
+}
+
+#[tauri::command]
+fn get_sounds_dir(state: State<AppState>) -> ApiResult<String> {
+    into_api(state.sound.sounds_dir()).map(|path| path.display().to_string())
+}
+
+#[tauri::command]
+fn preview_sound(state: State<AppState>, sound_id: String) -> ApiResult<()> {
+    into_api(state.sound.preview(&sound_id))
+}
+
+#[tauri::command]
+fn stop_sound(state: State<AppState>) -> ApiResult<()> {
+    state.sound.stop_all();
+    Ok(())
+}
+
+#[tauri::command]
+fn preview_speech(
+    state: State<AppState>,
+    engine: Option<String>,
+    voice: Option<String>,
+    rate: Option<f64>,
+) -> ApiResult<()> {
+    into_api(
+        state
+            .speech
+            .preview(engine.as_deref(), voice.as_deref(), rate),
+    )
+}
+
+#[tauri::command]
+fn stop_speech(state: State<AppState>) -> ApiResult<()> {
+    state.speech.stop_preview();
+    Ok(())
+}
+
 #[tauri::command]
 fn get_sync_status(state: State<AppState>) -> ApiResult<SyncStatus> {
     into_api(state.sync.get_status())
@@ -714,6 +1286,11 @@ fn read_syscolor_theme() -> Option<String> {
     Some(if luminance < 0.5 { "dark" } else { "light" }.to_string())
 }
 
+#[tauri::command]
+fn list_monitors(app: tauri::AppHandle) -> ApiResult<Vec<MonitorInfo>> {
+    into_api(notification::list_monitors(&app))
+}
+
 #[tauri::command]
 fn is_dev_mode() -> bool {
     paths::is_dev_mode()
@@ -804,8 +1381,8 @@ fn get_debug_info(state: State<AppState>) -> ApiResult<DebugInfo> {
     })
 }
 
-fn add_minutes(minutes: i64) -> String {
-    let dt = Local::now().naive_local() + chrono::Duration::minutes(minutes);
+fn add_minutes(now: chrono::NaiveDateTime, minutes: i64) -> String {
+    let dt = now + chrono::Duration::minutes(minutes);
     dt.format("%Y-%m-%dT%H:%M:%S").to_string()
 }
 
@@ -962,8 +1539,18 @@ fn show_sticky_note_item_window(app: &tauri::AppHandle, note: &StickyNote) -> Re
 
     let width = note.width.max(STICKY_NOTE_ITEM_MIN_WIDTH);
     let height = note.height.max(STICKY_NOTE_ITEM_MIN_HEIGHT);
-    let _ = window.set_size(LogicalSize::new(width, height));
-    let _ = window.set_position(LogicalPosition::new(note.pos_x, note.pos_y));
+    let monitors = notification::monitor_areas(app);
+    match notification::place_saved_window(note.pos_x, note.pos_y, width, height, &monitors) {
+        Some(rect) => {
+            // 先移动到目标显示器再设置物理尺寸，与提醒弹窗的处理相同。
+            let _ = window.set_position(PhysicalPosition::new(rect.x, rect.y));
+            let _ = window.set_size(PhysicalSize::new(rect.width, rect.height));
+        }
+        None => {
+            let _ = window.set_size(LogicalSize::new(width, height));
+            let _ = window.set_position(LogicalPosition::new(note.pos_x, note.pos_y));
+        }
+    }
     let _ = window.set_shadow(false);
     enforce_sticky_item_layer(&window, is_pinned);
     let _ = window.emit(&refresh_event, note.clone());
@@ -1044,6 +1631,12 @@ fn main() {
             list_completed_tasks,
             list_recurring_tasks,
             list_reminder_records,
+            get_reminder_stats,
+            get_habit_summaries,
+            send_digest_now,
+            generate_weekly_report,
+            open_digest_item,
+            set_habit_state,
             create_task,
             update_task,
             complete_task,
@@ -1056,6 +1649,23 @@ fn main() {
             delete_recurring_task,
             delete_reminder_record,
             delete_reminder_records,
+            get_activity_status,
+            get_focus_session,
+            start_focus,
+            stop_focus,
+            skip_focus_phase,
+            list_task_reminders,
+            list_time_entries,
+            delete_time_entry,
+            list_upcoming_reminders,
+            get_pause_state,
+            pause_all_reminders,
+            resume_all_reminders,
+            list_timers,
+            start_timer,
+            pause_timer,
+            resume_timer,
+            cancel_timer,
             get_settings,
             save_settings,
             get_sticky_note_by_window_label,
@@ -1069,14 +1679,28 @@ fn main() {
             set_sticky_note_pinned_by_window_label,
             get_sticky_note_pinned_by_window_label,
             test_webdav,
+            test_smtp,
             sync_now,
             set_autostart,
             ack_notification,
+            run_notification_action,
             snooze_notification,
             get_sync_status,
             get_notification_snapshot,
             get_current_theme,
             get_debug_info,
+            list_monitors,
+            list_webhook_channels,
+            save_webhook_channel,
+            delete_webhook_channel,
+            test_webhook_channel,
+            list_webhook_deliveries,
+            list_sounds,
+            get_sounds_dir,
+            preview_sound,
+            stop_sound,
+            preview_speech,
+            stop_speech,
             is_dev_mode,
             emit_ui_state_changed,
             get_ui_state
@@ -1117,14 +1741,70 @@ fn main() {
                 let db = DbManager::new(db_path)?;
                 let snapshot = Arc::new(Mutex::new(None));
                 let sync = CloudSyncService::new(app_handle.clone(), db.clone());
+                let webhooks = WebhookService::new(db.clone())?;
+                let mqtt = MqttService::new(app_handle.clone(), db.clone());
+                let sound = SoundService::new(db.clone(), &data_dir);
+                let speech = SpeechService::new(db.clone());
+                let mqtt_for_events = mqtt.clone();
+                db.subscribe(move |event| mqtt_for_events.publish_task_event(event));
+                let dispatcher = ReminderDispatcher::new(
+                    db.clone(),
+                    sync.clone(),
+                    webhooks.clone(),
+                    mqtt.clone(),
+                    sound.clone(),
+                    speech.clone(),
+                );
                 let scheduler = ReminderScheduler::new(
                     app_handle.clone(),
                     db.clone(),
                     sync.clone(),
+                    dispatcher.clone(),
                     snapshot.clone(),
                 );
+                let focus = FocusService::new(
+                    app_handle.clone(),
+                    db.clone(),
+                    sync.clone(),
+                    scheduler.clone(),
+                );
+                let activity = ActivityMonitor::new(
+                    db.clone(),
+                    scheduler.clone(),
+                    IdleDetector::new(platform_idle_sources()),
+                    platform_lock_sources(),
+                );
+                let scheduler_for_sync = scheduler.clone();
+                let app_for_sync = app_handle.clone();
+                sync.on_merged(move || match scheduler_for_sync.reconcile() {
+                    Ok(summary) => {
+                        if !summary.is_empty() {
+                            eprintln!(
+                                "[sync] 合并后调整提醒: 新增 {}，改期 {}，取消 {}",
+                                summary.armed.len(),
+                                summary.rescheduled.len(),
+                                summary.cancelled.len()
+                            );
+                        }
+                        let _ = app_for_sync.emit(SCHEDULE_RECONCILED_EVENT, summary);
+                    }
+                    Err(err) => eprintln!("[sync] 合并后调整提醒失败: {}", err),
+                });
+                let scheduler_for_close = scheduler.clone();
+                sync.on_merged(move || {
+                    if let Err(err) = scheduler_for_close.close_handled_elsewhere() {
+                        eprintln!("[sync] 关闭已在其他设备处理的提醒失败: {}", err);
+                    }
+                });
+                scheduler.start();
                 scheduler.schedule_existing()?;
+                scheduler.start_periodic_jobs();
+                timers::start_timer_ticker(app_handle.clone(), db.clone());
+                activity.start();
                 sync.start()?;
+                if let Err(err) = mqtt.restart() {
+                    eprintln!("[mqtt] 启动失败: {}", err);
+                }
                 maintenance::start_maintenance(db.clone());
 
                 let mut settings = db.load_settings()?;
@@ -1148,10 +1828,18 @@ fn main() {
                     db,
                     scheduler,
                     sync,
+                    dispatcher,
+                    webhooks,
+                    mqtt,
+                    sound,
+                    speech,
+                    focus,
+                    activity,
                     notification_snapshot: snapshot,
                     ui_state: Arc::new(Mutex::new(None)),
                 };
                 app.manage(state);
+                tray::refresh_tray_menu(app_handle);
                 restore_open_sticky_note_items(&app_handle, &db_for_restore)?;
                 Ok(())
             })();
@@ -11,9 +11,21 @@ pub struct Task {
     pub status: String,
     pub created_at: String,
     pub completed_at: Option<String>,
+    /// 下一个尚未触发的提醒时间，都已触发时为最后一个；由 task_reminders 汇总，只读。
     pub reminder_time: Option<String>,
     pub updated_at: Option<String>,
     pub deleted_at: Option<String>,
+    #[serde(default)]
+    pub notify_options: NotifyOptions,
+    /// 专注时段累计的用时（秒），由 time_entries 汇总，只读。
+    #[serde(default)]
+    pub tracked_seconds: i64,
+    /// 截止时间，与提醒时间相互独立。
+    #[serde(default)]
+    pub due_at: Option<String>,
+    /// 已过截止时间且未完成，查询时按当前时间计算，只读。
+    #[serde(default)]
+    pub overdue: bool,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -40,6 +52,38 @@ pub struct RecurringTask {
     pub schedule_weekday: Option<i64>,
     pub schedule_day: Option<i64>,
     pub cron_expression: Option<String>,
+    #[serde(default)]
+    pub notify_options: NotifyOptions,
+}
+
+/// 单个任务的提醒附加配置，以 JSON 形式存放在 tasks / recurring_tasks 的 notify_options 列中。
+#[derive(Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotifyOptions {
+    pub actions: Vec<NotificationAction>,
+    pub webhook_channels: Vec<String>,
+    pub email: bool,
+    /// 为空时使用全局默认提示音，"none" 表示静音。
+    pub sound: Option<String>,
+    /// 弹出提醒时朗读提醒内容，语音与语速为空时使用全局设置。
+    pub tts: bool,
+    pub tts_voice: Option<String>,
+    pub tts_rate: Option<f64>,
+    /// 提前提醒的分钟数，每项在触发点之前单独弹出一次。
+    pub lead_minutes: Vec<i64>,
+    /// 仅任务使用：到截止时间时提醒一次，之后每天同一时刻提醒，直到完成。
+    pub overdue_alert: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationAction {
+    #[serde(default)]
+    pub id: String,
+    pub label: String,
+    pub kind: String,
+    #[serde(default)]
+    pub url: Option<String>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -55,6 +99,108 @@ pub struct ReminderRecord {
     pub action: String,
     pub updated_at: Option<String>,
     pub deleted_at: Option<String>,
+    #[serde(default)]
+    pub delivery_error: Option<String>,
+    /// 仅循环提醒使用的打卡状态：DONE / SKIPPED / MISSED。
+    #[serde(default)]
+    pub habit_state: Option<String>,
+    /// 锁屏或离开期间被暂缓、回来后才弹出的提醒为 DELIVERED_LATE。
+    #[serde(default)]
+    pub delivery_state: Option<String>,
+    /// 实际弹出的时间，仅暂缓补发的提醒记录。
+    #[serde(default)]
+    pub delivered_at: Option<String>,
+    /// 对应的触发点（任务 id + 计划时间），计时器等本机提醒为空。
+    #[serde(default)]
+    pub occurrence_id: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeEntry {
+    pub id: String,
+    pub task_id: String,
+    pub started_at: String,
+    pub ended_at: String,
+    pub duration_seconds: i64,
+    pub source: String,
+    pub updated_at: Option<String>,
+    pub deleted_at: Option<String>,
+}
+
+/// 全局暂停（休假模式）：暂停期间不触发任何提醒，各循环任务自身的暂停状态不受影响。参与同步。
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PauseState {
+    pub paused: bool,
+    pub paused_at: Option<String>,
+    /// 到这个时间自动恢复，为空时需手动恢复。
+    pub resume_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+/// 一次性任务的一个提醒时间，每个提醒时间各自记录是否已触发。
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskReminder {
+    pub id: String,
+    pub task_id: String,
+    pub remind_at: String,
+    pub fired_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: Option<String>,
+    pub deleted_at: Option<String>,
+    /// 稍后提醒生成的一次性提醒时间，不在编辑任务时列出。
+    #[serde(default)]
+    pub is_snooze: bool,
+}
+
+/// 倒计时器只保存在本机，不参与同步；到点后生成 TIMER 类型的提醒记录。
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Timer {
+    pub id: String,
+    pub label: String,
+    pub duration_seconds: i64,
+    /// RUNNING / PAUSED
+    pub status: String,
+    /// 运行中的计时器的结束时间，暂停时为空。
+    pub ends_at: Option<String>,
+    pub remaining_seconds: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// 调度队列中接下来的一次触发。循环任务除队列中的下一次外，还会按规则向后推算若干次。
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingReminder {
+    pub reminder_id: String,
+    /// TASK / RECURRING / TIMER / LEAD，与提醒记录的 type 一致。
+    pub reminder_type: String,
+    /// 安排来源：TASK / RECURRING / TIMER / LEAD，稍后提醒为 SNOOZE。
+    pub source: String,
+    pub description: String,
+    pub fire_at: String,
+}
+
+/// 同步合并后按数据库重新核对调度队列的结果，列出受影响的任务 id。
+#[derive(Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcileSummary {
+    /// 队列中原本没有、新安排的。
+    pub armed: Vec<String>,
+    /// 触发时间被改动、已改期的。
+    pub rescheduled: Vec<String>,
+    /// 已删除、完成、暂停或不再需要提醒的。
+    pub cancelled: Vec<String>,
+    pub unchanged: usize,
+}
+
+impl ReconcileSummary {
+    pub fn is_empty(&self) -> bool {
+        self.armed.is_empty() && self.rescheduled.is_empty() && self.cancelled.is_empty()
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -100,6 +246,70 @@ pub struct AppSettings {
     pub webdav_last_sync_error: Option<String>,
     pub webdav_device_id: String,
     pub notification_theme: String,
+    pub notification_corner: String,
+    pub notification_monitor: String,
+    pub notification_monitor_name: String,
+    pub notification_width: Option<f64>,
+    pub notification_height: Option<f64>,
+    pub notification_margin: f64,
+    pub smtp_enabled: bool,
+    pub smtp_host: String,
+    pub smtp_port: i64,
+    pub smtp_security: String,
+    pub smtp_username: String,
+    pub smtp_password: String,
+    pub smtp_from: String,
+    pub smtp_to: String,
+    pub mqtt_enabled: bool,
+    pub mqtt_host: String,
+    pub mqtt_port: i64,
+    pub mqtt_username: String,
+    pub mqtt_password: String,
+    pub mqtt_client_id: String,
+    pub mqtt_topic_prefix: String,
+    pub sound_default: String,
+    pub sound_loop: bool,
+    pub sound_volume_start: f64,
+    pub sound_volume_max: f64,
+    pub sound_escalate_seconds: i64,
+    pub quiet_hours_enabled: bool,
+    pub quiet_hours_start: String,
+    pub quiet_hours_end: String,
+    pub tts_engine: String,
+    pub tts_voice: String,
+    pub tts_rate: f64,
+    pub digest_enabled: bool,
+    pub digest_time: String,
+    pub digest_workdays_only: bool,
+    /// 上次发送简报的日期，由后台维护，保存设置时不会覆盖。
+    #[serde(default)]
+    pub digest_last_date: Option<String>,
+    pub report_enabled: bool,
+    /// 1 为周一，7 为周日。
+    pub report_weekday: i64,
+    pub report_time: String,
+    pub report_format: String,
+    /// 自动生成的报告保存目录，为空时使用默认目录。
+    pub report_dir: String,
+    #[serde(default)]
+    pub report_last_date: Option<String>,
+    pub focus_work_minutes: i64,
+    pub focus_short_break_minutes: i64,
+    pub focus_long_break_minutes: i64,
+    /// 每完成多少轮专注后安排一次长休息。
+    pub focus_long_break_every: i64,
+    /// 连续活动达到 break_active_minutes 分钟后提醒休息。
+    pub break_enabled: bool,
+    pub break_active_minutes: i64,
+    /// 无键鼠输入超过该分钟数视为已经休息，连续活动时间重新计算。
+    pub break_idle_reset_minutes: i64,
+    /// 锁屏或无键鼠输入超过 away_idle_minutes 分钟时暂缓弹出提醒，回来后补发。
+    pub hold_when_away: bool,
+    pub away_idle_minutes: i64,
+    /// 新建任务与循环任务默认的提前提醒分钟数。
+    pub default_lead_minutes: Vec<i64>,
+    /// 结束全局暂停时如何处理暂停期间错过的提醒：ONCE / SKIP。
+    pub missed_policy: String,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -110,6 +320,64 @@ pub struct NotificationPayload {
     pub reminder_type: String,
     pub description: String,
     pub snooze_minutes: i64,
+    pub actions: Vec<NotificationAction>,
+    /// 仅每日简报使用，列出今天需要关注的条目。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub digest_items: Vec<DigestItem>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestItem {
+    pub kind: String,
+    pub id: String,
+    pub title: String,
+    pub time: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorInfo {
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    pub is_primary: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookChannel {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub preset: String,
+    pub url: String,
+    #[serde(default)]
+    pub secret: String,
+    pub events: Vec<String>,
+    pub apply_to_all: bool,
+    pub enabled: bool,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub updated_at: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub channel_id: String,
+    pub record_id: String,
+    pub event: String,
+    pub success: bool,
+    pub attempts: i64,
+    pub status_code: Option<i64>,
+    pub error: Option<String>,
+    pub created_at: String,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -0,0 +1,421 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use rumqttc::{Client, Event, LastWill, MqttOptions, Packet, QoS};
+use serde::Deserialize;
+use serde_json::json;
+use tauri::{AppHandle, Manager};
+
+use crate::db::{DbManager, TaskEvent};
+use crate::due::DUE_REMINDER_TYPE;
+use crate::errors::AppError;
+use crate::leads::LEAD_REMINDER_TYPE;
+use crate::notification::close_notification;
+use crate::state::AppState;
+use crate::webhook::WebhookMessage;
+use crate::{acknowledge_record, complete_task_via_app, snooze_record_via_app};
+
+const KEEP_ALIVE_SECS: u64 = 30;
+const RECONNECT_DELAY_SECS: u64 = 5;
+const REQUEST_CAPACITY: usize = 32;
+
+#[derive(Clone)]
+pub struct MqttService {
+    app: AppHandle,
+    db: DbManager,
+    session: Arc<Mutex<Option<MqttSession>>>,
+    generation: Arc<AtomicU64>,
+}
+
+#[derive(Clone)]
+struct MqttSession {
+    client: Client,
+    prefix: String,
+}
+
+/// 外部通过 `<前缀>/command` 下发的命令，未指定 recordId 时作用于当前弹窗中的提醒。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MqttCommand {
+    command: String,
+    record_id: Option<String>,
+    task_id: Option<String>,
+    minutes: Option<i64>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum MqttAction {
+    Dismiss {
+        record_id: String,
+    },
+    Snooze {
+        record_id: String,
+        reminder_id: String,
+        reminder_type: String,
+        minutes: i64,
+    },
+    Complete {
+        task_id: String,
+        record_id: Option<String>,
+    },
+}
+
+impl MqttService {
+    pub fn new(app: AppHandle, db: DbManager) -> Self {
+        Self {
+            app,
+            db,
+            session: Arc::new(Mutex::new(None)),
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// 按当前设置（重新）建立连接；设置关闭时只断开旧连接。
+    pub fn restart(&self) -> Result<(), AppError> {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        if let Some(previous) = self.session.lock().unwrap().take() {
+            let _ = previous.client.try_disconnect();
+        }
+
+        let settings = self.db.load_settings()?;
+        if !settings.mqtt_enabled || settings.mqtt_host.trim().is_empty() {
+            return Ok(());
+        }
+        let port = u16::try_from(settings.mqtt_port)
+            .map_err(|_| AppError::Invalid("MQTT 端口不正确".to_string()))?;
+        let client_id = if settings.mqtt_client_id.trim().is_empty() {
+            let device: String = settings.webdav_device_id.chars().take(8).collect();
+            format!("task-reminder-{}", device)
+        } else {
+            settings.mqtt_client_id.trim().to_string()
+        };
+        let prefix = settings.mqtt_topic_prefix.clone();
+        let status_topic = format!("{}/status", prefix);
+        let command_topic = format!("{}/command", prefix);
+
+        let mut options = MqttOptions::new(client_id, settings.mqtt_host.trim(), port);
+        options.set_keep_alive(Duration::from_secs(KEEP_ALIVE_SECS));
+        options.set_last_will(LastWill::new(
+            status_topic.clone(),
+            "offline",
+            QoS::AtLeastOnce,
+            true,
+        ));
+        if !settings.mqtt_username.trim().is_empty() {
+            options.set_credentials(
+                settings.mqtt_username.trim(),
+                settings.mqtt_password.clone(),
+            );
+        }
+
+        let (client, mut connection) = Client::new(options, REQUEST_CAPACITY);
+        *self.session.lock().unwrap() = Some(MqttSession {
+            client: client.clone(),
+            prefix,
+        });
+
+        let service = self.clone();
+        thread::spawn(move || {
+            for notification in connection.iter() {
+                if service.generation.load(Ordering::SeqCst) != generation {
+                    break;
+                }
+                match notification {
+                    Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                        // 每次（重新）连接后都要重新订阅并刷新在线状态。
+                        let _ = client.try_subscribe(command_topic.clone(), QoS::AtLeastOnce);
+                        let _ = client.try_publish(
+                            status_topic.clone(),
+                            QoS::AtLeastOnce,
+                            true,
+                            "online",
+                        );
+                    }
+                    Ok(Event::Incoming(Packet::Publish(publish))) => {
+                        if publish.topic == command_topic {
+                            if let Err(err) = service.handle_command(&publish.payload) {
+                                eprintln!("[mqtt] 执行命令失败: {}", err);
+                            }
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        eprintln!("[mqtt] 连接异常: {}", err);
+                        thread::sleep(Duration::from_secs(RECONNECT_DELAY_SECS));
+                    }
+                }
+            }
+        });
+        Ok(())
+    }
+
+    pub fn publish_reminder(&self, message: &WebhookMessage) {
+        let Ok(payload) = serde_json::to_vec(message) else {
+            return;
+        };
+        self.publish(
+            &format!("reminder/{}", message.event.to_lowercase()),
+            payload,
+        );
+    }
+
+    pub fn publish_task_event(&self, event: &TaskEvent) {
+        let (name, task_id) = match event {
+            TaskEvent::Created(task_id) => ("created", task_id),
+            TaskEvent::Completed(task_id) => ("completed", task_id),
+            TaskEvent::Deleted(task_id) => ("deleted", task_id),
+        };
+        let Some(task) = self.db.get_task(task_id).ok().flatten() else {
+            return;
+        };
+        let payload = json!({ "event": name, "task": task });
+        self.publish(&format!("task/{}", name), payload.to_string().into_bytes());
+    }
+
+    fn publish(&self, suffix: &str, payload: Vec<u8>) {
+        let Some(session) = self.session.lock().unwrap().clone() else {
+            return;
+        };
+        let topic = format!("{}/{}", session.prefix, suffix);
+        if let Err(err) = session
+            .client
+            .try_publish(topic, QoS::AtLeastOnce, false, payload)
+        {
+            eprintln!("[mqtt] 发布消息失败: {}", err);
+        }
+    }
+
+    fn handle_command(&self, payload: &[u8]) -> Result<(), AppError> {
+        let Some(state) = self.app.try_state::<AppState>() else {
+            return Ok(());
+        };
+        let state = state.inner();
+        let current = state
+            .notification_snapshot
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|payload| payload.record_id.clone());
+        match resolve_command(&state.db, payload, current)? {
+            MqttAction::Dismiss { record_id } => {
+                acknowledge_record(state, &record_id, "DISMISSED")?;
+                close_notification(&self.app, &record_id);
+            }
+            MqttAction::Snooze {
+                record_id,
+                reminder_id,
+                reminder_type,
+                minutes,
+            } => {
+                snooze_record_via_app(state, &record_id, &reminder_id, &reminder_type, minutes)?;
+                close_notification(&self.app, &record_id);
+            }
+            MqttAction::Complete { task_id, record_id } => {
+                complete_task_via_app(state, &task_id)?;
+                if let Some(record_id) = record_id {
+                    acknowledge_record(state, &record_id, "COMPLETED")?;
+                    close_notification(&self.app, &record_id);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 解析 MQTT 命令并确定作用的提醒记录或任务，不执行任何改动。
+/// `current_record_id` 为当前弹窗中的提醒，命令未指定 recordId 时使用。
+fn resolve_command(
+    db: &DbManager,
+    payload: &[u8],
+    current_record_id: Option<String>,
+) -> Result<MqttAction, AppError> {
+    let command: MqttCommand = serde_json::from_slice(payload)
+        .map_err(|e| AppError::Invalid(format!("无法解析 MQTT 命令: {}", e)))?;
+    let record = match command.record_id.or(current_record_id).as_deref() {
+        Some(record_id) => db.get_reminder_record(record_id)?,
+        None => None,
+    };
+    let require_record = || {
+        record
+            .clone()
+            .ok_or_else(|| AppError::Invalid("找不到提醒记录".to_string()))
+    };
+
+    match command.command.trim().to_lowercase().as_str() {
+        "ack" | "dismiss" => Ok(MqttAction::Dismiss {
+            record_id: require_record()?.id,
+        }),
+        "snooze" => {
+            let record = require_record()?;
+            // 提前提醒与逾期提醒到点后不会再次安排，稍后提醒没有意义。
+            if matches!(
+                record.reminder_type.as_str(),
+                LEAD_REMINDER_TYPE | DUE_REMINDER_TYPE
+            ) {
+                return Err(AppError::Invalid("该提醒不支持稍后提醒".to_string()));
+            }
+            let minutes = match command.minutes {
+                Some(minutes) => minutes,
+                None => db.load_settings()?.snooze_minutes,
+            };
+            Ok(MqttAction::Snooze {
+                record_id: record.id,
+                reminder_id: record.reminder_id,
+                reminder_type: record.reminder_type,
+                minutes,
+            })
+        }
+        "complete" => {
+            let task_id = command
+                .task_id
+                .or_else(|| {
+                    record
+                        .as_ref()
+                        .filter(|record| record.reminder_type == "TASK")
+                        .map(|record| record.reminder_id.clone())
+                })
+                .ok_or_else(|| AppError::Invalid("找不到要完成的任务".to_string()))?;
+            let record_id = record
+                .filter(|record| record.reminder_id == task_id)
+                .map(|record| record.id);
+            Ok(MqttAction::Complete { task_id, record_id })
+        }
+        other => Err(AppError::Invalid(format!("不支持的 MQTT 命令: {}", other))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    struct TempDb {
+        path: PathBuf,
+        db: DbManager,
+    }
+
+    impl TempDb {
+        fn new() -> Self {
+            let path = std::env::temp_dir()
+                .join(format!("task-reminder-mqtt-{}.db", uuid::Uuid::new_v4()));
+            let db = DbManager::new(path.clone()).unwrap();
+            Self { path, db }
+        }
+
+        fn record(&self, reminder_id: &str, reminder_type: &str) -> String {
+            self.db
+                .create_reminder_record(reminder_id, "交周报", reminder_type)
+                .unwrap()
+                .id
+        }
+
+        fn resolve(&self, payload: &str, current: Option<&str>) -> Result<MqttAction, AppError> {
+            resolve_command(&self.db, payload.as_bytes(), current.map(str::to_string))
+        }
+    }
+
+    impl Drop for TempDb {
+        fn drop(&mut self) {
+            for suffix in ["", "-wal", "-shm"] {
+                let mut path = self.path.clone().into_os_string();
+                path.push(suffix);
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+
+    #[test]
+    fn ack_and_dismiss_default_to_the_current_popup() {
+        let temp = TempDb::new();
+        let current = temp.record("task", "TASK");
+        let other = temp.record("other", "TASK");
+
+        for payload in [r#"{"command":"ack"}"#, r#"{"command":" Dismiss "}"#] {
+            assert_eq!(
+                temp.resolve(payload, Some(&current)).unwrap(),
+                MqttAction::Dismiss {
+                    record_id: current.clone()
+                }
+            );
+        }
+        let payload = format!(r#"{{"command":"ack","recordId":"{}"}}"#, other);
+        assert_eq!(
+            temp.resolve(&payload, Some(&current)).unwrap(),
+            MqttAction::Dismiss { record_id: other }
+        );
+        assert!(temp.resolve(r#"{"command":"ack"}"#, None).is_err());
+        assert!(temp
+            .resolve(r#"{"command":"ack"}"#, Some("missing"))
+            .is_err());
+    }
+
+    #[test]
+    fn snooze_uses_the_default_minutes_and_rejects_lead_and_due() {
+        let temp = TempDb::new();
+        let record = temp.record("task", "TASK");
+        let default_minutes = temp.db.load_settings().unwrap().snooze_minutes;
+
+        assert_eq!(
+            temp.resolve(r#"{"command":"snooze"}"#, Some(&record))
+                .unwrap(),
+            MqttAction::Snooze {
+                record_id: record.clone(),
+                reminder_id: "task".to_string(),
+                reminder_type: "TASK".to_string(),
+                minutes: default_minutes,
+            }
+        );
+        assert!(matches!(
+            temp.resolve(r#"{"command":"snooze","minutes":15}"#, Some(&record)),
+            Ok(MqttAction::Snooze { minutes: 15, .. })
+        ));
+        for reminder_type in [LEAD_REMINDER_TYPE, DUE_REMINDER_TYPE] {
+            let record = temp.record("task", reminder_type);
+            assert!(matches!(
+                temp.resolve(r#"{"command":"snooze"}"#, Some(&record)),
+                Err(AppError::Invalid(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn complete_targets_the_task_of_the_record_or_the_given_task() {
+        let temp = TempDb::new();
+        let record = temp.record("task", "TASK");
+        let recurring = temp.record("daily", "RECURRING");
+
+        assert_eq!(
+            temp.resolve(r#"{"command":"complete"}"#, Some(&record))
+                .unwrap(),
+            MqttAction::Complete {
+                task_id: "task".to_string(),
+                record_id: Some(record.clone()),
+            }
+        );
+        assert_eq!(
+            temp.resolve(r#"{"command":"complete","taskId":"other"}"#, Some(&record))
+                .unwrap(),
+            MqttAction::Complete {
+                task_id: "other".to_string(),
+                record_id: None,
+            }
+        );
+        assert!(temp
+            .resolve(r#"{"command":"complete"}"#, Some(&recurring))
+            .is_err());
+    }
+
+    #[test]
+    fn unknown_or_malformed_commands_are_rejected() {
+        let temp = TempDb::new();
+        let record = temp.record("task", "TASK");
+        assert!(matches!(
+            temp.resolve(r#"{"command":"reboot"}"#, Some(&record)),
+            Err(AppError::Invalid(message)) if message.contains("reboot")
+        ));
+        assert!(temp.resolve("ack", Some(&record)).is_err());
+    }
+}
@@ -0,0 +1,386 @@
+use tauri::{AppHandle, Emitter, Manager, Monitor, WebviewUrl, WebviewWindowBuilder};
+
+use crate::errors::AppError;
+use crate::models::{AppSettings, MonitorInfo, NotificationPayload};
+
+const DEFAULT_NOTIFICATION_WIDTH: f64 = 392.0;
+const DEFAULT_NOTIFICATION_HEIGHT_LINUX: f64 = 228.0;
+const DEFAULT_NOTIFICATION_HEIGHT: f64 = 248.0;
+
+/// 工作区（物理像素），即显示器去掉任务栏/面板后的可用区域。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkArea {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 显示器的整体范围、工作区（均为物理像素）与缩放比例。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitorArea {
+    pub bounds: WorkArea,
+    pub work_area: WorkArea,
+    pub scale_factor: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+pub fn emit_notification(
+    app: &AppHandle,
+    settings: &AppSettings,
+    payload: &NotificationPayload,
+) -> Result<(), AppError> {
+    let (logical_width, logical_height) = notification_size(settings);
+    let window = if let Some(existing) = app.get_webview_window("notification") {
+        existing
+    } else {
+        WebviewWindowBuilder::new(
+            app,
+            "notification",
+            WebviewUrl::App("notification.html".into()),
+        )
+        .title("提醒通知")
+        .decorations(false)
+        .transparent(true)
+        .shadow(false)
+        .always_on_top(true)
+        .resizable(false)
+        .visible(false)
+        .inner_size(logical_width, logical_height)
+        .build()
+        .map_err(|e| AppError::System(e.to_string()))?
+    };
+    let _ = window.set_shadow(false);
+
+    if let Some(monitor) = resolve_monitor(app, settings) {
+        let work_area = monitor_work_area(&monitor);
+        let rect = compute_notification_rect(
+            work_area,
+            monitor.scale_factor(),
+            logical_width,
+            logical_height,
+            settings.notification_margin,
+            &settings.notification_corner,
+        );
+        // 先移动到目标显示器再设置物理尺寸，避免跨 DPI 显示器时按旧缩放比例换算。
+        let _ = window.set_position(tauri::PhysicalPosition::new(rect.x, rect.y));
+        let _ = window.set_size(tauri::PhysicalSize::new(rect.width, rect.height));
+    } else {
+        let _ = window.set_size(tauri::LogicalSize::new(logical_width, logical_height));
+    }
+
+    window.emit("notification", payload).ok();
+    window.show().ok();
+    window.set_focus().ok();
+    Ok(())
+}
+
+/// 让弹窗关闭正在显示的这条提醒（弹窗已换成其他提醒时忽略）。
+pub fn close_notification(app: &AppHandle, record_id: &str) {
+    if let Some(window) = app.get_webview_window("notification") {
+        let _ = window.emit("notification-closed", record_id);
+    }
+}
+
+pub fn list_monitors(app: &AppHandle) -> Result<Vec<MonitorInfo>, AppError> {
+    let primary_name = app
+        .primary_monitor()
+        .map_err(|e| AppError::System(e.to_string()))?
+        .and_then(|monitor| monitor.name().cloned());
+    let monitors = app
+        .available_monitors()
+        .map_err(|e| AppError::System(e.to_string()))?;
+    Ok(monitors
+        .into_iter()
+        .map(|monitor| {
+            let name = monitor.name().cloned().unwrap_or_default();
+            let position = monitor.position();
+            let size = monitor.size();
+            MonitorInfo {
+                is_primary: primary_name.as_deref() == Some(name.as_str()),
+                name,
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+                scale_factor: monitor.scale_factor(),
+            }
+        })
+        .collect())
+}
+
+pub fn compute_notification_rect(
+    work_area: WorkArea,
+    scale_factor: f64,
+    logical_width: f64,
+    logical_height: f64,
+    logical_margin: f64,
+    corner: &str,
+) -> WindowRect {
+    let scale = normalize_scale(scale_factor);
+    let width = ((logical_width * scale).round() as u32).min(work_area.width);
+    let height = ((logical_height * scale).round() as u32).min(work_area.height);
+    let margin = (logical_margin.max(0.0) * scale).round() as i64;
+
+    let left = work_area.x as i64;
+    let top = work_area.y as i64;
+    let right = left + work_area.width as i64 - width as i64;
+    let bottom = top + work_area.height as i64 - height as i64;
+
+    let (x, y) = match corner {
+        "TOP_LEFT" => (left + margin, top + margin),
+        "TOP_RIGHT" => (right - margin, top + margin),
+        "BOTTOM_LEFT" => (left + margin, bottom - margin),
+        _ => (right - margin, bottom - margin),
+    };
+    // 边距过大时仍然保证弹窗完整落在工作区内。
+    clamp_to_work_area(work_area, x, y, width, height)
+}
+
+/// 便签等窗口以逻辑像素保存位置，保存时按窗口所在显示器的缩放比例换算。恢复时按各显示器
+/// 的缩放比例换回物理像素，中心落在该显示器上的即为原来的显示器（主显示器优先）；
+/// 都不符合时（显示器已断开或分辨率改变）放到第一个显示器。结果完整落在工作区内。
+pub fn place_saved_window(
+    logical_x: f64,
+    logical_y: f64,
+    logical_width: f64,
+    logical_height: f64,
+    monitors: &[MonitorArea],
+) -> Option<WindowRect> {
+    let to_physical = |monitor: &MonitorArea| {
+        let scale = normalize_scale(monitor.scale_factor);
+        (
+            (logical_x * scale).round() as i64,
+            (logical_y * scale).round() as i64,
+            (logical_width.max(1.0) * scale).round() as u32,
+            (logical_height.max(1.0) * scale).round() as u32,
+        )
+    };
+    let monitor = monitors
+        .iter()
+        .find(|monitor| {
+            let (x, y, width, height) = to_physical(monitor);
+            contains(monitor.bounds, x + width as i64 / 2, y + height as i64 / 2)
+        })
+        .or_else(|| monitors.first())?;
+    let (x, y, width, height) = to_physical(monitor);
+    Some(clamp_to_work_area(
+        monitor.work_area,
+        x,
+        y,
+        width.min(monitor.work_area.width),
+        height.min(monitor.work_area.height),
+    ))
+}
+
+/// 可用显示器的范围，主显示器排在最前。
+pub fn monitor_areas(app: &AppHandle) -> Vec<MonitorArea> {
+    let primary_name = app
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .and_then(|monitor| monitor.name().cloned());
+    let mut monitors = app.available_monitors().unwrap_or_default();
+    monitors.sort_by_key(|monitor| monitor.name() != primary_name.as_ref());
+    monitors
+        .iter()
+        .map(|monitor| {
+            let position = monitor.position();
+            let size = monitor.size();
+            MonitorArea {
+                bounds: WorkArea {
+                    x: position.x,
+                    y: position.y,
+                    width: size.width,
+                    height: size.height,
+                },
+                work_area: monitor_work_area(monitor),
+                scale_factor: monitor.scale_factor(),
+            }
+        })
+        .collect()
+}
+
+/// `width`、`height` 需不大于工作区。
+fn clamp_to_work_area(area: WorkArea, x: i64, y: i64, width: u32, height: u32) -> WindowRect {
+    let left = area.x as i64;
+    let top = area.y as i64;
+    let right = left + area.width as i64 - width as i64;
+    let bottom = top + area.height as i64 - height as i64;
+    WindowRect {
+        x: x.clamp(left, right.max(left)) as i32,
+        y: y.clamp(top, bottom.max(top)) as i32,
+        width,
+        height,
+    }
+}
+
+fn contains(area: WorkArea, x: i64, y: i64) -> bool {
+    let (left, top) = (area.x as i64, area.y as i64);
+    x >= left && x < left + area.width as i64 && y >= top && y < top + area.height as i64
+}
+
+fn normalize_scale(scale_factor: f64) -> f64 {
+    if scale_factor.is_finite() && scale_factor > 0.0 {
+        scale_factor
+    } else {
+        1.0
+    }
+}
+
+fn notification_size(settings: &AppSettings) -> (f64, f64) {
+    let default_height = if cfg!(target_os = "linux") {
+        DEFAULT_NOTIFICATION_HEIGHT_LINUX
+    } else {
+        DEFAULT_NOTIFICATION_HEIGHT
+    };
+    (
+        settings
+            .notification_width
+            .unwrap_or(DEFAULT_NOTIFICATION_WIDTH),
+        settings.notification_height.unwrap_or(default_height),
+    )
+}
+
+fn resolve_monitor(app: &AppHandle, settings: &AppSettings) -> Option<Monitor> {
+    let preferred = match settings.notification_monitor.as_str() {
+        "CURSOR" => app
+            .cursor_position()
+            .ok()
+            .and_then(|position| app.monitor_from_point(position.x, position.y).ok())
+            .flatten(),
+        "NAMED" => app.available_monitors().ok().and_then(|monitors| {
+            monitors.into_iter().find(|monitor| {
+                monitor.name().map(String::as_str)
+                    == Some(settings.notification_monitor_name.as_str())
+            })
+        }),
+        _ => None,
+    };
+    preferred
+        .or_else(|| app.primary_monitor().ok().flatten())
+        .or_else(|| {
+            app.available_monitors()
+                .ok()
+                .and_then(|monitors| monitors.into_iter().next())
+        })
+}
+
+fn monitor_work_area(monitor: &Monitor) -> WorkArea {
+    let area = monitor.work_area();
+    if area.size.width == 0 || area.size.height == 0 {
+        let position = monitor.position();
+        let size = monitor.size();
+        return WorkArea {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+        };
+    }
+    WorkArea {
+        x: area.position.x,
+        y: area.position.y,
+        width: area.size.width,
+        height: area.size.height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(x: i32, y: i32, width: u32, height: u32) -> WorkArea {
+        WorkArea {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// 主显示器 1920x1080（底部 40px 任务栏），左侧副显示器 2560x1440、150% 缩放。
+    fn monitors() -> Vec<MonitorArea> {
+        vec![
+            MonitorArea {
+                bounds: area(0, 0, 1920, 1080),
+                work_area: area(0, 0, 1920, 1040),
+                scale_factor: 1.0,
+            },
+            MonitorArea {
+                bounds: area(-2560, 0, 2560, 1440),
+                work_area: area(-2560, 0, 2560, 1440),
+                scale_factor: 1.5,
+            },
+        ]
+    }
+
+    #[test]
+    fn notification_sits_in_the_corner_of_a_scaled_monitor() {
+        let rect = compute_notification_rect(
+            area(-2560, 0, 2560, 1400),
+            1.5,
+            392.0,
+            248.0,
+            16.0,
+            "BOTTOM_RIGHT",
+        );
+        assert_eq!(
+            rect,
+            WindowRect {
+                x: -588 - 24,
+                y: 1400 - 372 - 24,
+                width: 588,
+                height: 372,
+            }
+        );
+        let rect =
+            compute_notification_rect(area(0, 0, 1920, 1040), 1.0, 392.0, 248.0, 16.0, "TOP_LEFT");
+        assert_eq!((rect.x, rect.y), (16, 16));
+    }
+
+    #[test]
+    fn notification_is_clamped_when_margin_or_size_is_too_large() {
+        let work_area = area(0, 0, 1920, 1040);
+        let rect = compute_notification_rect(work_area, 1.0, 392.0, 248.0, 5000.0, "BOTTOM_RIGHT");
+        assert_eq!((rect.x, rect.y), (0, 0));
+        let rect = compute_notification_rect(work_area, 2.0, 1200.0, 800.0, 0.0, "TOP_LEFT");
+        assert_eq!((rect.width, rect.height), (1920, 1040));
+        assert_eq!((rect.x, rect.y), (0, 0));
+    }
+
+    #[test]
+    fn saved_window_stays_on_the_secondary_monitor() {
+        // 在副显示器上保存的逻辑位置 (-1000, 100) 按 150% 还原为物理像素。
+        let rect = place_saved_window(-1000.0, 100.0, 284.0, 280.0, &monitors()).unwrap();
+        assert_eq!(
+            rect,
+            WindowRect {
+                x: -1500,
+                y: 150,
+                width: 426,
+                height: 420,
+            }
+        );
+        let rect = place_saved_window(600.0, 300.0, 284.0, 280.0, &monitors()).unwrap();
+        assert_eq!((rect.x, rect.y, rect.width), (600, 300, 284));
+    }
+
+    #[test]
+    fn off_screen_window_is_pulled_back_into_a_work_area() {
+        // 显示器已断开：放到主显示器并完整落在工作区内，不压住任务栏。
+        let rect = place_saved_window(5000.0, 3000.0, 284.0, 280.0, &monitors()).unwrap();
+        assert_eq!((rect.x, rect.y), (1920 - 284, 1040 - 280));
+        // 中心仍在主显示器上、只是超出边缘时推回来。
+        let rect = place_saved_window(-100.0, 900.0, 284.0, 280.0, &monitors()).unwrap();
+        assert_eq!((rect.x, rect.y), (0, 1040 - 280));
+        assert_eq!(place_saved_window(0.0, 0.0, 284.0, 280.0, &[]), None);
+    }
+}
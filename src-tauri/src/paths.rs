@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use tauri::{AppHandle, Manager};
 
@@ -63,3 +63,10 @@ pub fn db_path(data_dir: &PathBuf) -> PathBuf {
 pub fn lock_path(data_dir: &PathBuf) -> PathBuf {
     data_dir.join(".taskreminder.lock")
 }
+
+/// 周报默认保存在“文档”目录下，取不到时放在数据目录中。
+pub fn default_reports_dir(data_dir: &Path) -> PathBuf {
+    dirs_next::document_dir()
+        .map(|dir| dir.join("TaskReminder Reports"))
+        .unwrap_or_else(|| data_dir.join("reports"))
+}
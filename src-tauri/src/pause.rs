@@ -0,0 +1,26 @@
+/// 全局暂停在 pause_state 表中只有一行，调度队列中恢复任务的 id 也用它。
+pub const GLOBAL_PAUSE_ID: &str = "global";
+
+/// 恢复时，暂停期间错过的提醒各补发一次（循环任务只补最近一次，一次性任务每个任务只补最后一个提醒时间）。
+pub const MISSED_POLICY_ONCE: &str = "ONCE";
+/// 恢复时不补发暂停期间错过的提醒，循环任务直接顺延到下一次。
+pub const MISSED_POLICY_SKIP: &str = "SKIP";
+
+pub fn normalize_missed_policy(policy: &str) -> String {
+    match policy.trim().to_uppercase().as_str() {
+        MISSED_POLICY_SKIP => MISSED_POLICY_SKIP.to_string(),
+        _ => MISSED_POLICY_ONCE.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_policies_fall_back_to_once() {
+        assert_eq!(normalize_missed_policy(" skip "), MISSED_POLICY_SKIP);
+        assert_eq!(normalize_missed_policy("ONCE"), MISSED_POLICY_ONCE);
+        assert_eq!(normalize_missed_policy(""), MISSED_POLICY_ONCE);
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::NaiveDateTime;
+
+/// 调度队列中的任务类别。`Handler` 交给其他服务注册的回调处理（如专注时段）。
+/// `Task(reminder_id)` 为任务的某个提醒时间（task_reminders 中的 id），id 为所属任务；
+/// `Lead(minutes)` 为任务或循环任务提前 minutes 分钟的提醒，id 与所属任务相同；
+/// `Due` 为任务的截止时间与之后每天的逾期提醒。
+/// `Resume` 为全局暂停到点自动恢复，id 固定为 pause 模块的 GLOBAL_PAUSE_ID。
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum JobKind {
+    Task(String),
+    Recurring,
+    Lead(i64),
+    Due,
+    Resume,
+    Timer,
+    Handler(&'static str),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct JobKey {
+    pub kind: JobKind,
+    pub id: String,
+}
+
+impl JobKey {
+    pub fn new(kind: JobKind, id: &str) -> Self {
+        Self {
+            kind,
+            id: id.to_string(),
+        }
+    }
+}
+
+/// 按墙上时间排序的到期队列，同一个 key 只保留最近一次安排的时间。
+#[derive(Default)]
+pub struct DeadlineQueue {
+    entries: BTreeSet<(NaiveDateTime, JobKey)>,
+    deadlines: HashMap<JobKey, NaiveDateTime>,
+}
+
+impl DeadlineQueue {
+    /// 安排或改期，返回新的时间是否成为队首（调度循环需要提前唤醒）。
+    pub fn schedule(&mut self, key: JobKey, deadline: NaiveDateTime) -> bool {
+        self.cancel(&key);
+        self.deadlines.insert(key.clone(), deadline);
+        self.entries.insert((deadline, key));
+        self.next_deadline() == Some(deadline)
+    }
+
+    pub fn cancel(&mut self, key: &JobKey) -> bool {
+        match self.deadlines.remove(key) {
+            Some(deadline) => self.entries.remove(&(deadline, key.clone())),
+            None => false,
+        }
+    }
+
+    pub fn next_deadline(&self) -> Option<NaiveDateTime> {
+        self.entries.first().map(|(deadline, _)| *deadline)
+    }
+
+    /// 按到期时间先后遍历队列中的任务。
+    pub fn iter(&self) -> impl Iterator<Item = &(NaiveDateTime, JobKey)> {
+        self.entries.iter()
+    }
+
+    /// 取出所有在 `now` 之前（含）到期的任务，按到期时间先后排列。
+    pub fn pop_due(&mut self, now: NaiveDateTime) -> Vec<(NaiveDateTime, JobKey)> {
+        let mut due = Vec::new();
+        while let Some((deadline, _)) = self.entries.first() {
+            if *deadline > now {
+                break;
+            }
+            let (deadline, key) = self.entries.pop_first().unwrap();
+            self.deadlines.remove(&key);
+            due.push((deadline, key));
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn pops_due_jobs_in_deadline_order() {
+        let mut queue = DeadlineQueue::default();
+        queue.schedule(JobKey::new(JobKind::Timer, "b"), at("2026-01-05T09:00:00"));
+        queue.schedule(
+            JobKey::new(JobKind::Recurring, "a"),
+            at("2026-01-05T08:00:00"),
+        );
+        queue.schedule(JobKey::new(JobKind::Timer, "c"), at("2026-01-05T10:00:00"));
+
+        let due = queue.pop_due(at("2026-01-05T09:00:00"));
+        let ids: Vec<&str> = due.iter().map(|(_, key)| key.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b"]);
+        assert_eq!(queue.next_deadline(), Some(at("2026-01-05T10:00:00")));
+    }
+
+    #[test]
+    fn rescheduling_replaces_previous_deadline() {
+        let mut queue = DeadlineQueue::default();
+        let key = JobKey::new(JobKind::Timer, "a");
+        assert!(queue.schedule(key.clone(), at("2026-01-05T08:00:00")));
+        assert!(!queue.schedule(JobKey::new(JobKind::Timer, "b"), at("2026-01-05T09:00:00")));
+        assert!(!queue.schedule(key.clone(), at("2026-01-05T12:00:00")));
+
+        assert!(queue.pop_due(at("2026-01-05T08:30:00")).is_empty());
+        let due = queue.pop_due(at("2026-01-05T12:00:00"));
+        assert_eq!(due.len(), 2);
+        assert_eq!(due[1], (at("2026-01-05T12:00:00"), key));
+    }
+
+    #[test]
+    fn cancelled_job_is_not_popped() {
+        let mut queue = DeadlineQueue::default();
+        let key = JobKey::new(JobKind::Handler("focus"), "s1");
+        queue.schedule(key.clone(), at("2026-01-05T08:00:00"));
+        assert!(queue.cancel(&key));
+        assert!(!queue.cancel(&key));
+        assert_eq!(queue.next_deadline(), None);
+        assert!(queue.pop_due(at("2026-01-06T00:00:00")).is_empty());
+    }
+}
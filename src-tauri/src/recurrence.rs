@@ -110,14 +110,11 @@ pub fn sanitize_recurring_task(task: &mut RecurringTask) -> Result<(), AppError>
     Ok(())
 }
 
-pub fn compute_next_trigger(
-    task: &RecurringTask,
-    base: Option<NaiveDateTime>,
-) -> Result<String, AppError> {
+/// 计算 `base` 之后的下一次触发时间，`base` 一般为时钟的当前时间。
+pub fn compute_next_trigger(task: &RecurringTask, base: NaiveDateTime) -> Result<String, AppError> {
     let mut normalized = task.clone();
     sanitize_recurring_task(&mut normalized)?;
 
-    let base = base.unwrap_or_else(|| Local::now().naive_local());
     let next = match normalized.repeat_mode.as_str() {
         REPEAT_MODE_INTERVAL_RANGE => compute_interval_next(&normalized, base)?,
         REPEAT_MODE_DAILY => compute_daily_next(&normalized, base)?,
@@ -383,3 +380,100 @@ fn midnight_time() -> NaiveTime {
 fn minute_of_day(time: NaiveTime) -> i32 {
     (time.hour() as i32) * 60 + (time.minute() as i32)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NotifyOptions;
+
+    fn task(repeat_mode: &str) -> RecurringTask {
+        RecurringTask {
+            id: "r1".to_string(),
+            description: "测试".to_string(),
+            task_type: "RECURRING".to_string(),
+            status: "PENDING".to_string(),
+            created_at: String::new(),
+            completed_at: None,
+            reminder_time: None,
+            updated_at: None,
+            deleted_at: None,
+            interval_minutes: 30,
+            last_triggered: None,
+            next_trigger: String::new(),
+            is_paused: false,
+            start_time: None,
+            end_time: None,
+            repeat_mode: repeat_mode.to_string(),
+            schedule_time: Some("09:00".to_string()),
+            schedule_weekday: None,
+            schedule_day: None,
+            cron_expression: None,
+            notify_options: NotifyOptions::default(),
+        }
+    }
+
+    fn next(task: &RecurringTask, base: &str) -> String {
+        let base = NaiveDateTime::parse_from_str(base, "%Y-%m-%dT%H:%M:%S").unwrap();
+        compute_next_trigger(task, base).unwrap()
+    }
+
+    #[test]
+    fn daily_moves_to_tomorrow_once_time_has_passed() {
+        let daily = task(REPEAT_MODE_DAILY);
+        assert_eq!(next(&daily, "2026-01-05T08:00:00"), "2026-01-05T09:00:00");
+        assert_eq!(next(&daily, "2026-01-05T09:00:00"), "2026-01-06T09:00:00");
+    }
+
+    #[test]
+    fn weekly_targets_configured_weekday() {
+        let mut weekly = task(REPEAT_MODE_WEEKLY);
+        weekly.schedule_weekday = Some(3);
+        // 2026-01-05 为周一。
+        assert_eq!(next(&weekly, "2026-01-05T10:00:00"), "2026-01-07T09:00:00");
+        assert_eq!(next(&weekly, "2026-01-07T09:30:00"), "2026-01-14T09:00:00");
+    }
+
+    #[test]
+    fn monthly_clamps_to_last_day_of_short_months() {
+        let mut monthly = task(REPEAT_MODE_MONTHLY);
+        monthly.schedule_day = Some(31);
+        assert_eq!(next(&monthly, "2026-01-31T10:00:00"), "2026-02-28T09:00:00");
+        assert_eq!(next(&monthly, "2026-03-01T00:00:00"), "2026-03-31T09:00:00");
+    }
+
+    #[test]
+    fn interval_waits_for_window_start_and_rolls_over_after_end() {
+        let mut interval = task(REPEAT_MODE_INTERVAL_RANGE);
+        interval.start_time = Some("09:00".to_string());
+        interval.end_time = Some("18:00".to_string());
+        assert_eq!(
+            next(&interval, "2026-01-05T07:00:00"),
+            "2026-01-05T09:00:00"
+        );
+        assert_eq!(
+            next(&interval, "2026-01-05T10:00:00"),
+            "2026-01-05T10:30:00"
+        );
+        assert_eq!(
+            next(&interval, "2026-01-05T17:50:00"),
+            "2026-01-06T09:00:00"
+        );
+    }
+
+    #[test]
+    fn cron_accepts_five_field_expressions() {
+        let mut cron = task(REPEAT_MODE_CRON);
+        cron.cron_expression = Some("0 9 * * MON-FRI".to_string());
+        assert_eq!(next(&cron, "2026-01-09T10:00:00"), "2026-01-12T09:00:00");
+    }
+
+    #[test]
+    fn should_trigger_now_respects_interval_window() {
+        let mut interval = task(REPEAT_MODE_INTERVAL_RANGE);
+        interval.start_time = Some("09:00".to_string());
+        interval.end_time = Some("18:00".to_string());
+        let at = |value| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").unwrap();
+        assert!(should_trigger_now(&interval, at("2026-01-05T12:00:00")).unwrap());
+        assert!(!should_trigger_now(&interval, at("2026-01-05T19:00:00")).unwrap());
+    }
+}
@@ -0,0 +1,372 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::db::DbManager;
+use crate::errors::AppError;
+use crate::habits::{summarize_habit, HabitSummary};
+use crate::models::{AppSettings, Task};
+use crate::stats::{compute_reminder_stats, TaskReminderStats};
+
+pub const REPORT_FORMAT_MARKDOWN: &str = "MARKDOWN";
+pub const REPORT_FORMAT_HTML: &str = "HTML";
+
+/// 一周的回顾数据，周一到周日。
+pub struct WeeklyReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub completed: Vec<Task>,
+    pub created: Vec<Task>,
+    /// 本周被推迟或错过过的提醒，最常被忽略的排在前面。
+    pub ignored: Vec<TaskReminderStats>,
+    pub habits: Vec<HabitSummary>,
+    pub open: Vec<Task>,
+}
+
+pub fn normalize_report_format(format: &str) -> String {
+    match format.trim().to_uppercase().as_str() {
+        REPORT_FORMAT_HTML => REPORT_FORMAT_HTML.to_string(),
+        _ => REPORT_FORMAT_MARKDOWN.to_string(),
+    }
+}
+
+/// 返回 `date` 所在周的周一与周日。
+pub fn week_range(date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+    (monday, monday + Duration::days(6))
+}
+
+pub fn build_weekly_report(
+    db: &DbManager,
+    date: NaiveDate,
+    today: NaiveDate,
+) -> Result<WeeklyReport, AppError> {
+    let (from, to) = week_range(date);
+    let start = from.format("%Y-%m-%dT00:00:00").to_string();
+    let end = (to + Duration::days(1))
+        .format("%Y-%m-%dT00:00:00")
+        .to_string();
+    let in_week = |value: Option<&str>| {
+        value.is_some_and(|value| value >= start.as_str() && value < end.as_str())
+    };
+
+    let active = db.list_active_tasks()?;
+    let done = db.list_completed_tasks()?;
+
+    let mut completed: Vec<Task> = done
+        .iter()
+        .filter(|task| in_week(task.completed_at.as_deref()))
+        .cloned()
+        .collect();
+    completed.sort_by(|a, b| a.completed_at.cmp(&b.completed_at));
+
+    let mut created: Vec<Task> = active
+        .iter()
+        .chain(done.iter())
+        .filter(|task| in_week(Some(&task.created_at)))
+        .cloned()
+        .collect();
+    created.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+    let records = db.list_reminder_records_between(&start, &end)?;
+    let ignored = compute_reminder_stats(from, to, &records)
+        .tasks
+        .into_iter()
+        .filter(|task| task.summary.snoozed > 0 || task.summary.missed > 0)
+        .collect();
+
+    let mut habits = Vec::new();
+    for task in db.list_recurring_tasks()? {
+        let records = db.list_reminder_records_for(&task.id)?;
+        let summary = summarize_habit(&task, &records, from, to, today);
+        if !summary.days.is_empty() || summary.current_streak > 0 {
+            habits.push(summary);
+        }
+    }
+
+    Ok(WeeklyReport {
+        from,
+        to,
+        completed,
+        created,
+        ignored,
+        habits,
+        open: active,
+    })
+}
+
+pub fn render_markdown(report: &WeeklyReport) -> String {
+    let mut out = format!(
+        "# 周报 {} ~ {}\n",
+        report.from.format("%Y-%m-%d"),
+        report.to.format("%Y-%m-%d")
+    );
+
+    out.push_str(&format!("\n## 本周完成（{}）\n\n", report.completed.len()));
+    push_task_list(&mut out, &report.completed, |task| {
+        task.completed_at
+            .as_deref()
+            .map(|time| format!("完成于 {}", short_time(time)))
+    });
+
+    out.push_str(&format!("\n## 本周新建（{}）\n\n", report.created.len()));
+    push_task_list(&mut out, &report.created, |task| {
+        Some(format!("创建于 {}", short_time(&task.created_at)))
+    });
+
+    out.push_str("\n## 推迟或忽略的提醒\n\n");
+    if report.ignored.is_empty() {
+        out.push_str("无\n");
+    } else {
+        out.push_str("| 提醒 | 次数 | 稍后 | 错过 | 确认率 |\n| --- | --- | --- | --- | --- |\n");
+        for item in &report.ignored {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                escape_table_cell(&item.description),
+                item.summary.total,
+                item.summary.snoozed,
+                item.summary.missed,
+                format_rate(item.summary.ack_rate)
+            ));
+        }
+    }
+
+    out.push_str("\n## 习惯打卡\n\n");
+    if report.habits.is_empty() {
+        out.push_str("无\n");
+    } else {
+        out.push_str(
+            "| 习惯 | 当前连续 | 最长连续 | 完成 | 跳过 | 错过 |\n| --- | --- | --- | --- | --- | --- |\n",
+        );
+        for habit in &report.habits {
+            out.push_str(&format!(
+                "| {} | {} 天 | {} 天 | {} | {} | {} |\n",
+                escape_table_cell(&habit.description),
+                habit.current_streak,
+                habit.longest_streak,
+                habit.done_days,
+                habit.skipped_days,
+                habit.missed_days
+            ));
+        }
+    }
+
+    out.push_str(&format!("\n## 仍未完成（{}）\n\n", report.open.len()));
+    push_task_list(&mut out, &report.open, open_task_note);
+    out
+}
+
+pub fn render_html(report: &WeeklyReport) -> String {
+    let title = format!(
+        "周报 {} ~ {}",
+        report.from.format("%Y-%m-%d"),
+        report.to.format("%Y-%m-%d")
+    );
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n\
+         <style>body{{font-family:sans-serif;max-width:760px;margin:24px auto;line-height:1.6}}\
+         table{{border-collapse:collapse}}td,th{{border:1px solid #ccc;padding:4px 8px}}\
+         .meta{{color:#888}}</style>\n</head>\n<body>\n<h1>{0}</h1>\n",
+        title
+    );
+
+    out.push_str(&format!(
+        "<h2>本周完成（{}）</h2>\n",
+        report.completed.len()
+    ));
+    push_task_html(&mut out, &report.completed, |task| {
+        task.completed_at
+            .as_deref()
+            .map(|time| format!("完成于 {}", short_time(time)))
+    });
+
+    out.push_str(&format!("<h2>本周新建（{}）</h2>\n", report.created.len()));
+    push_task_html(&mut out, &report.created, |task| {
+        Some(format!("创建于 {}", short_time(&task.created_at)))
+    });
+
+    out.push_str("<h2>推迟或忽略的提醒</h2>\n");
+    if report.ignored.is_empty() {
+        out.push_str("<p>无</p>\n");
+    } else {
+        out.push_str(
+            "<table>\n<tr><th>提醒</th><th>次数</th><th>稍后</th><th>错过</th><th>确认率</th></tr>\n",
+        );
+        for item in &report.ignored {
+            out.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&item.description),
+                item.summary.total,
+                item.summary.snoozed,
+                item.summary.missed,
+                format_rate(item.summary.ack_rate)
+            ));
+        }
+        out.push_str("</table>\n");
+    }
+
+    out.push_str("<h2>习惯打卡</h2>\n");
+    if report.habits.is_empty() {
+        out.push_str("<p>无</p>\n");
+    } else {
+        out.push_str(
+            "<table>\n<tr><th>习惯</th><th>当前连续</th><th>最长连续</th><th>完成</th><th>跳过</th><th>错过</th></tr>\n",
+        );
+        for habit in &report.habits {
+            out.push_str(&format!(
+                "<tr><td>{}</td><td>{} 天</td><td>{} 天</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&habit.description),
+                habit.current_streak,
+                habit.longest_streak,
+                habit.done_days,
+                habit.skipped_days,
+                habit.missed_days
+            ));
+        }
+        out.push_str("</table>\n");
+    }
+
+    out.push_str(&format!("<h2>仍未完成（{}）</h2>\n", report.open.len()));
+    push_task_html(&mut out, &report.open, open_task_note);
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+pub fn render_report(report: &WeeklyReport, format: &str) -> String {
+    if normalize_report_format(format) == REPORT_FORMAT_HTML {
+        render_html(report)
+    } else {
+        render_markdown(report)
+    }
+}
+
+pub fn report_file_name(report: &WeeklyReport, format: &str) -> String {
+    let extension = if normalize_report_format(format) == REPORT_FORMAT_HTML {
+        "html"
+    } else {
+        "md"
+    };
+    format!(
+        "weekly-review-{}.{}",
+        report.from.format("%Y-%m-%d"),
+        extension
+    )
+}
+
+/// 写入报告文件。`target` 为目录时按周生成文件名，否则直接作为文件路径。
+pub fn write_report(target: &Path, file_name: &str, content: &str) -> Result<PathBuf, AppError> {
+    let path = if target.is_dir() || target.extension().is_none() {
+        std::fs::create_dir_all(target)?;
+        target.join(file_name)
+    } else {
+        if let Some(parent) = target
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+        target.to_path_buf()
+    };
+    std::fs::write(&path, content)?;
+    Ok(path)
+}
+
+pub fn copy_to_clipboard(content: &str) -> Result<(), AppError> {
+    let mut clipboard = arboard::Clipboard::new()
+        .map_err(|e| AppError::System(format!("无法访问剪贴板: {}", e)))?;
+    clipboard
+        .set_text(content.to_string())
+        .map_err(|e| AppError::System(format!("写入剪贴板失败: {}", e)))
+}
+
+/// 是否到了自动生成周报的时间：设定的星期几、已过设定时间，且本周尚未生成。
+pub fn report_due(settings: &AppSettings, now: NaiveDateTime) -> bool {
+    if !settings.report_enabled {
+        return false;
+    }
+    let today = now.date();
+    if today.weekday().number_from_monday() as i64 != settings.report_weekday {
+        return false;
+    }
+    if settings.report_last_date.as_deref() == Some(today.format("%Y-%m-%d").to_string().as_str()) {
+        return false;
+    }
+    NaiveTime::parse_from_str(&settings.report_time, "%H:%M")
+        .map(|time| now.time() >= time)
+        .unwrap_or(false)
+}
+
+fn push_task_list(out: &mut String, tasks: &[Task], meta: impl Fn(&Task) -> Option<String>) {
+    if tasks.is_empty() {
+        out.push_str("无\n");
+        return;
+    }
+    for task in tasks {
+        match meta(task) {
+            Some(meta) => out.push_str(&format!(
+                "- {}（{}）\n",
+                single_line(&task.description),
+                meta
+            )),
+            None => out.push_str(&format!("- {}\n", single_line(&task.description))),
+        }
+    }
+}
+
+fn push_task_html(out: &mut String, tasks: &[Task], meta: impl Fn(&Task) -> Option<String>) {
+    if tasks.is_empty() {
+        out.push_str("<p>无</p>\n");
+        return;
+    }
+    out.push_str("<ul>\n");
+    for task in tasks {
+        out.push_str(&format!(
+            "<li>{}",
+            escape_html(&single_line(&task.description))
+        ));
+        if let Some(meta) = meta(task) {
+            out.push_str(&format!(
+                " <span class=\"meta\">{}</span>",
+                escape_html(&meta)
+            ));
+        }
+        out.push_str("</li>\n");
+    }
+    out.push_str("</ul>\n");
+}
+
+/// 未完成任务的附注：有截止时间时列截止时间（逾期时标出），否则列提醒时间。
+fn open_task_note(task: &Task) -> Option<String> {
+    if let Some(due_at) = task.due_at.as_deref() {
+        let suffix = if task.overdue { "，已逾期" } else { "" };
+        return Some(format!("截止于 {}{}", short_time(due_at), suffix));
+    }
+    task.reminder_time
+        .as_deref()
+        .map(|time| format!("提醒于 {}", short_time(time)))
+}
+
+fn short_time(value: &str) -> String {
+    value.get(5..16).unwrap_or(value).replace('T', " ")
+}
+
+fn single_line(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn escape_table_cell(value: &str) -> String {
+    single_line(value).replace('|', "\\|")
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn format_rate(rate: Option<f64>) -> String {
+    rate.map(|rate| format!("{:.0}%", rate * 100.0))
+        .unwrap_or_else(|| "-".to_string())
+}
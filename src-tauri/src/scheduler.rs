@@ -1,24 +1,131 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
-use chrono::{Local, NaiveDateTime};
-use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
-use tokio::time::sleep;
+use chrono::{Duration, NaiveDateTime};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Notify;
+use tokio::time::{sleep, timeout};
 
+use crate::actions::{recurring_actions, task_actions};
+use crate::clock::SharedClock;
 use crate::db::DbManager;
+use crate::digest::{build_digest_items, digest_due, digest_title, DIGEST_REMINDER_TYPE};
+use crate::dispatch::ReminderDispatcher;
+use crate::due::{due_alert_slot, due_description, due_record_id, DUE_REMINDER_TYPE};
 use crate::errors::AppError;
-use crate::models::{NotificationPayload, RecurringTask, Task};
+use crate::habits::is_acknowledged_action;
+use crate::leads::{lead_deadline, lead_description, LEAD_REMINDER_TYPE};
+use crate::models::{
+    AppSettings, NotificationAction, NotificationPayload, PauseState, ReconcileSummary,
+    RecurringTask, ReminderRecord, Task, Timer, UpcomingReminder,
+};
+use crate::notification::{close_notification, emit_notification};
+use crate::paths;
+use crate::pause::{GLOBAL_PAUSE_ID, MISSED_POLICY_SKIP};
+use crate::queue::{DeadlineQueue, JobKey, JobKind};
 use crate::recurrence::{compute_next_trigger, sanitize_recurring_task, should_trigger_now};
+use crate::report::{
+    build_weekly_report, render_report, report_due, report_file_name, write_report,
+};
 use crate::sync::CloudSyncService;
+use crate::timers::{
+    ends_at_after, normalize_timer_seconds, notify_timers_changed, remaining_seconds, timer_label,
+    TIMER_PAUSED, TIMER_REMINDER_TYPE, TIMER_RUNNING,
+};
+use crate::tray;
+
+const PERIODIC_CHECK_SECS: u64 = 60;
+/// 调度循环最长的休眠时间。休眠或改时间后，最迟这么久就会按新的墙上时间重新检查到期任务。
+const WALL_CLOCK_CHECK_SECS: i64 = 5;
+/// 墙上时间与单调时钟的偏差超过该秒数即视为时钟跳变或从休眠恢复。
+const CLOCK_JUMP_TOLERANCE_SECS: i64 = 3;
+pub const SCHEDULE_CHANGED_EVENT: &str = "schedule-changed";
+pub const SCHEDULE_RECONCILED_EVENT: &str = "schedule-reconciled";
+
+/// 其他服务注册的到期回调，参数为任务 id。
+pub type DueHandler = Arc<dyn Fn(&str) -> Result<(), AppError> + Send + Sync>;
+
+/// 调度器产生的副作用：弹窗、外部渠道与同步。
+/// 运行时由 `AppReminderOutput` 实现，测试中替换为记录事件的实现。
+pub trait ReminderOutput: Send + Sync {
+    fn show(&self, settings: &AppSettings, payload: &NotificationPayload) -> Result<(), AppError>;
+    fn reminder_fired(&self, record: &ReminderRecord, payload: &NotificationPayload);
+    fn reminder_closed(&self, record: &ReminderRecord);
+    /// 收起正在显示的这条提醒的弹窗。
+    fn close(&self, record_id: &str);
+    fn local_changed(&self) -> Result<(), AppError>;
+    fn timers_changed(&self);
+    /// 调度队列有变化，同一轮调度中的多次变化只通知一次。
+    fn schedule_changed(&self);
+    fn data_dir(&self) -> Result<PathBuf, AppError>;
+}
+
+pub struct AppReminderOutput {
+    app: AppHandle,
+    sync: CloudSyncService,
+    dispatcher: ReminderDispatcher,
+}
+
+impl ReminderOutput for AppReminderOutput {
+    fn show(&self, settings: &AppSettings, payload: &NotificationPayload) -> Result<(), AppError> {
+        emit_notification(&self.app, settings, payload)
+    }
+
+    fn reminder_fired(&self, record: &ReminderRecord, payload: &NotificationPayload) {
+        self.dispatcher.reminder_fired(record, payload);
+    }
+
+    fn reminder_closed(&self, record: &ReminderRecord) {
+        self.dispatcher.reminder_closed(record);
+    }
+
+    fn close(&self, record_id: &str) {
+        close_notification(&self.app, record_id);
+    }
+
+    fn local_changed(&self) -> Result<(), AppError> {
+        self.sync.notify_local_change()
+    }
+
+    fn timers_changed(&self) {
+        notify_timers_changed(&self.app);
+    }
+
+    fn schedule_changed(&self) {
+        let _ = self.app.emit(SCHEDULE_CHANGED_EVENT, ());
+        tray::refresh_tray_menu(&self.app);
+    }
+
+    fn data_dir(&self) -> Result<PathBuf, AppError> {
+        paths::resolve_data_dir(&self.app)
+    }
+}
+
+/// 锁屏或离开期间暂缓的弹窗。带提醒记录的在补发时标记为 DELIVERED_LATE。
+struct HeldNotification {
+    record: Option<ReminderRecord>,
+    payload: NotificationPayload,
+}
 
 #[derive(Clone)]
 pub struct ReminderScheduler {
-    app: AppHandle,
     db: DbManager,
-    sync: CloudSyncService,
-    recurring_jobs: Arc<Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>>,
-    task_jobs: Arc<Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>>,
+    clock: SharedClock,
+    output: Arc<dyn ReminderOutput>,
+    queue: Arc<Mutex<DeadlineQueue>>,
+    wake: Arc<Notify>,
+    handlers: Arc<Mutex<HashMap<&'static str, DueHandler>>>,
+    /// 由稍后提醒安排的任务，重新安排或到期后移除。
+    snoozed: Arc<Mutex<HashSet<JobKey>>>,
+    schedule_dirty: Arc<AtomicBool>,
     snapshot: Arc<Mutex<Option<NotificationPayload>>>,
+    away: Arc<AtomicBool>,
+    held: Arc<Mutex<VecDeque<HeldNotification>>>,
+    /// 全局暂停期间到期的计时器与专注阶段，恢复时立即执行。
+    paused_jobs: Arc<Mutex<Vec<JobKey>>>,
 }
 
 impl ReminderScheduler {
@@ -26,77 +133,658 @@ impl ReminderScheduler {
         app: AppHandle,
         db: DbManager,
         sync: CloudSyncService,
+        dispatcher: ReminderDispatcher,
         snapshot: Arc<Mutex<Option<NotificationPayload>>>,
     ) -> Self {
-        Self {
+        let output = AppReminderOutput {
             app,
-            db,
             sync,
-            recurring_jobs: Arc::new(Mutex::new(HashMap::new())),
-            task_jobs: Arc::new(Mutex::new(HashMap::new())),
+            dispatcher,
+        };
+        Self::with_output(db, Arc::new(output), snapshot)
+    }
+
+    /// 时钟取自 `db`，与数据库写入的时间保持一致。
+    pub fn with_output(
+        db: DbManager,
+        output: Arc<dyn ReminderOutput>,
+        snapshot: Arc<Mutex<Option<NotificationPayload>>>,
+    ) -> Self {
+        Self {
+            clock: db.clock(),
+            db,
+            output,
+            queue: Arc::new(Mutex::new(DeadlineQueue::default())),
+            wake: Arc::new(Notify::new()),
+            handlers: Arc::new(Mutex::new(HashMap::new())),
+            snoozed: Arc::new(Mutex::new(HashSet::new())),
+            schedule_dirty: Arc::new(AtomicBool::new(false)),
             snapshot,
+            away: Arc::new(AtomicBool::new(false)),
+            held: Arc::new(Mutex::new(VecDeque::new())),
+            paused_jobs: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
     pub fn schedule_existing(&self) -> Result<(), AppError> {
-        let recurring = self.db.list_recurring_tasks()?;
+        self.reconcile()?;
+        // 关机期间已到点的计时器在启动后立即触发。
+        for timer in self.db.list_timers()? {
+            if timer.status == TIMER_RUNNING {
+                self.schedule_timer(&timer)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 按数据库中的任务与循环任务核对调度队列：补上缺少的、改期时间变了的、取消不再需要的。
+    /// 启动时与同步合并了其他设备的改动后调用。计时器只在本机，不参与核对。
+    pub fn reconcile(&self) -> Result<ReconcileSummary, AppError> {
+        let now = self.clock.now();
+        self.db.refresh_task_reminder_times()?;
+        // 稍后提醒的任务不再提前提醒。
+        let snoozed = self.snoozed.lock().unwrap().clone();
+        let pause = self.db.load_pause_state()?;
+        let mut desired = HashMap::new();
+        // 全局暂停期间只保留到点自动恢复的安排。
+        if pause.paused {
+            if let Some(resume_at) = pause.resume_at.as_deref() {
+                desired.insert(
+                    JobKey::new(JobKind::Resume, GLOBAL_PAUSE_ID),
+                    parse_datetime(resume_at)?,
+                );
+            }
+        }
+        let recurring = if pause.paused {
+            Vec::new()
+        } else {
+            self.db.list_recurring_tasks()?
+        };
+        let tasks = if pause.paused {
+            Vec::new()
+        } else {
+            self.db.list_active_tasks()?
+        };
         for task in recurring {
             if !task.is_paused {
-                self.schedule_recurring(task)?;
+                let key = JobKey::new(JobKind::Recurring, &task.id);
+                let deadline = parse_datetime(&task.next_trigger)?;
+                if !snoozed.contains(&key) {
+                    desired.extend(lead_jobs(
+                        &task.id,
+                        deadline,
+                        &task.notify_options.lead_minutes,
+                        now,
+                    ));
+                }
+                desired.insert(key, deadline);
             }
         }
-        let tasks = self.db.list_active_tasks()?;
         for task in tasks {
-            if let Some(reminder) = &task.reminder_time {
-                if is_future(reminder)? {
-                    self.schedule_task(task)?;
+            if let Some(alert_at) = self.next_due_alert(&task, now)? {
+                desired.insert(JobKey::new(JobKind::Due, &task.id), alert_at);
+            }
+            let pending = self.pending_reminders(&task.id, now)?;
+            if let Some((reminder_id, deadline)) = pending.first() {
+                let key = JobKey::new(JobKind::Task(reminder_id.clone()), &task.id);
+                if !snoozed.contains(&key) {
+                    desired.extend(lead_jobs(
+                        &task.id,
+                        *deadline,
+                        &task.notify_options.lead_minutes,
+                        now,
+                    ));
                 }
             }
+            for (reminder_id, deadline) in pending {
+                desired.insert(JobKey::new(JobKind::Task(reminder_id), &task.id), deadline);
+            }
         }
-        Ok(())
+
+        let current: HashMap<JobKey, NaiveDateTime> = self
+            .queue
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, key)| {
+                matches!(
+                    key.kind,
+                    JobKind::Task(_)
+                        | JobKind::Recurring
+                        | JobKind::Lead(_)
+                        | JobKind::Due
+                        | JobKind::Resume
+                )
+            })
+            .map(|(deadline, key)| (key.clone(), *deadline))
+            .collect();
+
+        // 提前提醒、逾期提醒与所属任务共用 id，摘要中每个任务只列一次。
+        let mut summary = ReconcileSummary::default();
+        // 改动提醒时间会换成新的提醒时间记录，同一任务既有新的又有取消的提醒时间时算作改期。
+        let mut armed_reminders = HashSet::new();
+        let mut cancelled_reminders = HashSet::new();
+        for (key, deadline) in &desired {
+            match current.get(key) {
+                Some(existing) if existing == deadline => {
+                    if matches!(key.kind, JobKind::Task(_) | JobKind::Recurring) {
+                        summary.unchanged += 1;
+                    }
+                }
+                Some(_) => {
+                    summary.rescheduled.push(key.id.clone());
+                    self.schedule_job(key.clone(), *deadline);
+                }
+                None => {
+                    if matches!(key.kind, JobKind::Task(_)) {
+                        armed_reminders.insert(key.id.clone());
+                    }
+                    summary.armed.push(key.id.clone());
+                    self.schedule_job(key.clone(), *deadline);
+                }
+            }
+        }
+        for key in current.keys() {
+            if !desired.contains_key(key) {
+                if matches!(key.kind, JobKind::Task(_)) {
+                    cancelled_reminders.insert(key.id.clone());
+                }
+                summary.cancelled.push(key.id.clone());
+                self.cancel_job(key);
+            }
+        }
+        let moved: Vec<String> = armed_reminders
+            .intersection(&cancelled_reminders)
+            .cloned()
+            .collect();
+        summary.armed.retain(|id| !moved.contains(id));
+        summary.cancelled.retain(|id| !moved.contains(id));
+        summary.rescheduled.extend(moved);
+        for ids in [
+            &mut summary.armed,
+            &mut summary.rescheduled,
+            &mut summary.cancelled,
+        ] {
+            ids.sort();
+            ids.dedup();
+        }
+        Ok(summary)
+    }
+
+    /// 时钟跳变后调用，应先执行一次到期任务：拨快错过的触发已按补发策略处理，
+    /// 拨回时按当前时间重新计算循环任务更早的下一次触发时间，再核对调度队列。
+    pub fn clock_jumped(&self) -> Result<ReconcileSummary, AppError> {
+        let now = self.clock.now();
+        let mut changed = false;
+        for mut task in self.db.list_recurring_tasks()? {
+            if task.is_paused {
+                continue;
+            }
+            let next = compute_next_trigger(&task, now)?;
+            if parse_datetime(&next)? < parse_datetime(&task.next_trigger)? {
+                task.next_trigger = next;
+                self.db.update_recurring_task(&task)?;
+                changed = true;
+            }
+        }
+        if changed {
+            self.output.local_changed()?;
+        }
+        self.reconcile()
     }
 
     pub fn schedule_recurring(&self, task: RecurringTask) -> Result<(), AppError> {
         self.cancel_recurring(&task.id);
-        if task.is_paused {
+        if task.is_paused || self.is_paused()? {
             return Ok(());
         }
-        let delay = seconds_until(&task.next_trigger)?;
-        let scheduler = self.clone();
-        let task_id = task.id.clone();
-        let handle = tauri::async_runtime::spawn(async move {
-            sleep(std::time::Duration::from_secs(delay)).await;
-            let _ = scheduler.handle_recurring(task_id);
-        });
-        self.recurring_jobs.lock().unwrap().insert(task.id, handle);
+        let deadline = parse_datetime(&task.next_trigger)?;
+        self.schedule_job(JobKey::new(JobKind::Recurring, &task.id), deadline);
+        self.schedule_leads(&task.id, deadline, &task.notify_options.lead_minutes);
         Ok(())
     }
 
+    /// 安排任务的各个提醒时间、逾期提醒，以及下一个提醒时间的提前提醒。
+    /// 已触发或已过的提醒时间不再安排。
     pub fn schedule_task(&self, task: Task) -> Result<(), AppError> {
         self.cancel_task(&task.id);
-        let Some(reminder_time) = task.reminder_time.clone() else {
+        if self.is_paused()? {
+            return Ok(());
+        }
+        let now = self.clock.now();
+        if let Some(alert_at) = self.next_due_alert(&task, now)? {
+            self.schedule_job(JobKey::new(JobKind::Due, &task.id), alert_at);
+        }
+        let pending = self.pending_reminders(&task.id, now)?;
+        if let Some((_, deadline)) = pending.first() {
+            self.schedule_leads(&task.id, *deadline, &task.notify_options.lead_minutes);
+        }
+        for (reminder_id, deadline) in pending {
+            self.schedule_job(JobKey::new(JobKind::Task(reminder_id), &task.id), deadline);
+        }
+        Ok(())
+    }
+
+    /// 任务尚未触发且晚于 `now` 的提醒时间（id 与时间），从早到晚排列。
+    fn pending_reminders(
+        &self,
+        task_id: &str,
+        now: NaiveDateTime,
+    ) -> Result<Vec<(String, NaiveDateTime)>, AppError> {
+        let mut pending = Vec::new();
+        for reminder in self.db.list_task_reminders_with_snoozes(task_id)? {
+            let remind_at = parse_datetime(&reminder.remind_at)?;
+            if reminder.fired_at.is_none() && remind_at > now {
+                pending.push((reminder.id, remind_at));
+            }
+        }
+        Ok(pending)
+    }
+
+    pub fn schedule_timer(&self, timer: &Timer) -> Result<(), AppError> {
+        self.cancel_timer(&timer.id);
+        let Some(ends_at) = timer.ends_at.as_deref() else {
             return Ok(());
         };
-        let delay = seconds_until(&reminder_time)?;
-        let scheduler = self.clone();
-        let task_id = task.id.clone();
-        let handle = tauri::async_runtime::spawn(async move {
-            sleep(std::time::Duration::from_secs(delay)).await;
-            let _ = scheduler.handle_task(task_id);
-        });
-        self.task_jobs.lock().unwrap().insert(task.id, handle);
+        let deadline = parse_datetime(ends_at)?;
+        self.schedule_job(JobKey::new(JobKind::Timer, &timer.id), deadline);
         Ok(())
     }
 
+    pub fn cancel_timer(&self, timer_id: &str) {
+        self.cancel_job(&JobKey::new(JobKind::Timer, timer_id));
+    }
+
     pub fn cancel_recurring(&self, task_id: &str) {
-        if let Some(handle) = self.recurring_jobs.lock().unwrap().remove(task_id) {
-            handle.abort();
-        }
+        self.cancel_job(&JobKey::new(JobKind::Recurring, task_id));
+        self.cancel_leads(task_id);
     }
 
     pub fn cancel_task(&self, task_id: &str) {
-        if let Some(handle) = self.task_jobs.lock().unwrap().remove(task_id) {
-            handle.abort();
+        self.cancel_matching(task_id, |kind| {
+            matches!(kind, JobKind::Task(_) | JobKind::Lead(_) | JobKind::Due)
+        });
+    }
+
+    /// 下一次逾期提醒的时间：未开启、没有截止时间或已完成时为 None。
+    /// 最近一次已经提醒过（本机或其他设备）时顺延到第二天。
+    fn next_due_alert(
+        &self,
+        task: &Task,
+        now: NaiveDateTime,
+    ) -> Result<Option<NaiveDateTime>, AppError> {
+        if !task.notify_options.overdue_alert
+            || task.deleted_at.is_some()
+            || task.status == "COMPLETED"
+        {
+            return Ok(None);
+        }
+        let Some(due_at) = task.due_at.as_deref() else {
+            return Ok(None);
+        };
+        let due = parse_datetime(due_at)?;
+        let slot = due_alert_slot(due, now);
+        if slot <= now
+            && self
+                .db
+                .get_reminder_record(&due_record_id(&occurrence_id(&task.id, slot)))?
+                .is_some()
+        {
+            return Ok(Some(slot + Duration::days(1)));
+        }
+        Ok(Some(slot))
+    }
+
+    /// 为触发点安排各个提前提醒，已经过了的提前量不再补发。
+    fn schedule_leads(&self, task_id: &str, scheduled: NaiveDateTime, lead_minutes: &[i64]) {
+        for (key, deadline) in lead_jobs(task_id, scheduled, lead_minutes, self.clock.now()) {
+            self.schedule_job(key, deadline);
+        }
+    }
+
+    fn cancel_leads(&self, task_id: &str) {
+        self.cancel_matching(task_id, |kind| matches!(kind, JobKind::Lead(_)));
+    }
+
+    /// 取消同一 id 下类别满足条件的所有安排。
+    fn cancel_matching(&self, id: &str, matches_kind: impl Fn(&JobKind) -> bool) {
+        let keys: Vec<JobKey> = self
+            .queue
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, key)| key.id == id && matches_kind(&key.kind))
+            .map(|(_, key)| key.clone())
+            .collect();
+        for key in keys {
+            self.cancel_job(&key);
+        }
+    }
+
+    /// 注册 `JobKind::Handler(name)` 任务到期时的回调。
+    pub fn register_handler(&self, name: &'static str, handler: DueHandler) {
+        self.handlers.lock().unwrap().insert(name, handler);
+    }
+
+    pub fn schedule_job(&self, key: JobKey, deadline: NaiveDateTime) {
+        self.snoozed.lock().unwrap().remove(&key);
+        self.queue.lock().unwrap().schedule(key, deadline);
+        self.mark_schedule_changed();
+    }
+
+    pub fn cancel_job(&self, key: &JobKey) {
+        self.snoozed.lock().unwrap().remove(key);
+        if self.queue.lock().unwrap().cancel(key) {
+            self.mark_schedule_changed();
+        }
+    }
+
+    /// 稍后提醒：与普通安排相同，只是在即将触发的列表中标记为 SNOOZE，也不再提前提醒。
+    /// `reminder_id` 为稍后提醒新增的提醒时间。
+    pub fn snooze_task(&self, task: Task, reminder_id: &str) -> Result<(), AppError> {
+        let key = JobKey::new(JobKind::Task(reminder_id.to_string()), &task.id);
+        self.schedule_task(task)?;
+        self.cancel_leads(&key.id);
+        self.snoozed.lock().unwrap().insert(key);
+        Ok(())
+    }
+
+    pub fn snooze_recurring(&self, task: RecurringTask) -> Result<(), AppError> {
+        let key = JobKey::new(JobKind::Recurring, &task.id);
+        self.schedule_recurring(task)?;
+        self.cancel_leads(&key.id);
+        self.snoozed.lock().unwrap().insert(key);
+        Ok(())
+    }
+
+    pub fn is_paused(&self) -> Result<bool, AppError> {
+        Ok(self.db.load_pause_state()?.paused)
+    }
+
+    /// 全局暂停：取消所有提醒的安排，`resume_at` 为空时需手动恢复。
+    /// 已经暂停时只更新恢复时间，暂停开始的时间不变。计时器与专注时段照常计时，
+    /// 期间到点的提醒与阶段切换留到恢复后再执行。
+    pub fn pause_all(&self, resume_at: Option<NaiveDateTime>) -> Result<PauseState, AppError> {
+        let now = self.clock.now();
+        if resume_at.is_some_and(|resume_at| resume_at <= now) {
+            return Err(AppError::Invalid("恢复时间需晚于当前时间".to_string()));
+        }
+        let current = self.db.load_pause_state()?;
+        let paused_at = current.paused_at.unwrap_or_else(|| format_datetime(now));
+        let resume_at = resume_at.map(format_datetime);
+        self.db
+            .save_pause_state(Some(&paused_at), resume_at.as_deref())?;
+        self.output.local_changed()?;
+        self.reconcile()?;
+        self.mark_schedule_changed();
+        self.db.load_pause_state()
+    }
+
+    /// 结束全局暂停，按设置处理暂停期间错过的提醒后重新安排全部提醒。
+    pub fn resume_all(&self) -> Result<(), AppError> {
+        let pause = self.db.load_pause_state()?;
+        let Some(paused_at) = pause.paused_at.as_deref() else {
+            return Ok(());
+        };
+        let paused_at = parse_datetime(paused_at)?;
+        let now = self.clock.now();
+        let skip = self.db.load_settings()?.missed_policy == MISSED_POLICY_SKIP;
+        self.db.save_pause_state(None, None)?;
+
+        let mut missed = Vec::new();
+        if skip {
+            for mut task in self.db.list_recurring_tasks()? {
+                if task.is_paused || parse_datetime(&task.next_trigger)? > now {
+                    continue;
+                }
+                task.next_trigger = compute_next_trigger(&task, now)?;
+                self.db.update_recurring_task(&task)?;
+            }
+        } else {
+            // 循环任务的下一次触发时间停在暂停期间，重新安排后会立即补发一次。
+            // 一次性任务的提醒时间已过不会再安排，每个任务补发暂停期间的最后一个。
+            for task in self.db.list_active_tasks()? {
+                let mut latest = None;
+                for reminder in self.db.list_task_reminders_with_snoozes(&task.id)? {
+                    let remind_at = parse_datetime(&reminder.remind_at)?;
+                    if reminder.fired_at.is_none() && remind_at >= paused_at && remind_at <= now {
+                        latest = Some(reminder.id);
+                    }
+                }
+                if let Some(reminder_id) = latest {
+                    missed.push(JobKey::new(JobKind::Task(reminder_id), &task.id));
+                }
+            }
+        }
+        self.output.local_changed()?;
+        self.reconcile()?;
+        for key in missed {
+            self.schedule_job(key, now);
+        }
+        let held: Vec<JobKey> = self.paused_jobs.lock().unwrap().drain(..).collect();
+        for key in held {
+            self.schedule_job(key, now);
+        }
+        self.mark_schedule_changed();
+        Ok(())
+    }
+
+    /// 唤醒调度循环重新计算等待时间，并在本轮结束后通知队列变化。
+    fn mark_schedule_changed(&self) {
+        self.schedule_dirty.store(true, Ordering::SeqCst);
+        self.wake.notify_one();
+    }
+
+    pub fn next_deadline(&self) -> Option<NaiveDateTime> {
+        self.queue.lock().unwrap().next_deadline()
+    }
+
+    /// 按时钟的当前时间执行所有已到期的任务，调度循环与测试都通过它推进。
+    pub fn tick(&self) {
+        self.run_due(self.clock.now());
+        if self.schedule_dirty.swap(false, Ordering::SeqCst) {
+            self.output.schedule_changed();
+        }
+    }
+
+    /// 接下来最多 `limit` 次触发，按时间先后排列。专注时段等内部回调不列出。
+    pub fn upcoming(&self, limit: usize) -> Result<Vec<UpcomingReminder>, AppError> {
+        let entries: Vec<(NaiveDateTime, JobKey)> =
+            self.queue.lock().unwrap().iter().cloned().collect();
+        let snoozed = self.snoozed.lock().unwrap().clone();
+        let mut upcoming: Vec<(NaiveDateTime, UpcomingReminder)> = Vec::new();
+        for (deadline, key) in entries {
+            // 队列按时间排序，已有足够多不晚于当前时间点的条目时后面的都排不进前 `limit`。
+            if upcoming.iter().filter(|(at, _)| *at <= deadline).count() >= limit {
+                break;
+            }
+            let snooze = snoozed.contains(&key);
+            match key.kind {
+                JobKind::Task(_) => {
+                    let Some(task) = self.db.get_task(&key.id)? else {
+                        continue;
+                    };
+                    if task.deleted_at.is_some() || task.status == "COMPLETED" {
+                        continue;
+                    }
+                    upcoming.push(upcoming_item(
+                        deadline,
+                        &task.id,
+                        "TASK",
+                        snooze,
+                        &task.description,
+                    ));
+                }
+                JobKind::Recurring => {
+                    let Some(task) = self.db.get_recurring_task(&key.id)? else {
+                        continue;
+                    };
+                    if task.deleted_at.is_some() || task.is_paused {
+                        continue;
+                    }
+                    upcoming.push(upcoming_item(
+                        deadline,
+                        &task.id,
+                        "RECURRING",
+                        snooze,
+                        &task.description,
+                    ));
+                    let mut base = deadline;
+                    for _ in 1..limit {
+                        base = parse_datetime(&compute_next_trigger(&task, base)?)?;
+                        upcoming.push(upcoming_item(
+                            base,
+                            &task.id,
+                            "RECURRING",
+                            false,
+                            &task.description,
+                        ));
+                    }
+                }
+                JobKind::Due => {
+                    let Some(task) = self.db.get_task(&key.id)? else {
+                        continue;
+                    };
+                    let Some(due) = task.due_at.as_deref() else {
+                        continue;
+                    };
+                    if task.deleted_at.is_some() || task.status == "COMPLETED" {
+                        continue;
+                    }
+                    upcoming.push(upcoming_item(
+                        deadline,
+                        &task.id,
+                        DUE_REMINDER_TYPE,
+                        false,
+                        &due_description(&task.description, parse_datetime(due)?, deadline),
+                    ));
+                }
+                JobKind::Lead(minutes) => {
+                    let Some((_, description, _)) = self.lead_parent(&key.id)? else {
+                        continue;
+                    };
+                    upcoming.push(upcoming_item(
+                        deadline,
+                        &key.id,
+                        LEAD_REMINDER_TYPE,
+                        false,
+                        &lead_description(&description, minutes),
+                    ));
+                }
+                JobKind::Timer => {
+                    let Some(timer) = self.db.get_timer(&key.id)? else {
+                        continue;
+                    };
+                    upcoming.push(upcoming_item(
+                        deadline,
+                        &timer.id,
+                        TIMER_REMINDER_TYPE,
+                        false,
+                        &timer.label,
+                    ));
+                }
+                JobKind::Resume | JobKind::Handler(_) => {}
+            }
+        }
+        upcoming.sort_by_key(|(at, _)| *at);
+        upcoming.truncate(limit);
+        Ok(upcoming.into_iter().map(|(_, item)| item).collect())
+    }
+
+    /// 启动唯一的调度循环：按墙上时间取出到期任务，而不是为每个任务各睡一段单调时间，
+    /// 这样休眠唤醒、NTP 校时、手动改时间或切换时区后都会按新的时间重新判断。
+    pub fn start(&self) {
+        let scheduler = self.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut last_wall = scheduler.clock.now();
+            let mut last_instant = Instant::now();
+            loop {
+                let now = scheduler.clock.now();
+                let expected =
+                    last_wall + Duration::milliseconds(last_instant.elapsed().as_millis() as i64);
+                let jumped = (now - expected).num_seconds().abs() > CLOCK_JUMP_TOLERANCE_SECS;
+                if jumped {
+                    eprintln!(
+                        "[scheduler] 检测到时钟跳变或从休眠恢复: {} -> {}",
+                        expected.format("%Y-%m-%d %H:%M:%S"),
+                        now.format("%Y-%m-%d %H:%M:%S")
+                    );
+                }
+                last_wall = now;
+                last_instant = Instant::now();
+
+                scheduler.tick();
+                if jumped {
+                    if let Err(err) = scheduler.clock_jumped() {
+                        eprintln!("[scheduler] 时钟跳变后重新安排提醒失败: {}", err);
+                    }
+                }
+
+                let wait = scheduler
+                    .next_deadline()
+                    .map(|deadline| (deadline - now).num_milliseconds().max(0))
+                    .unwrap_or(i64::MAX)
+                    .min(WALL_CLOCK_CHECK_SECS * 1000);
+                // 新安排的任务早于当前队首时会提前唤醒。
+                let _ = timeout(
+                    std::time::Duration::from_millis(wait as u64),
+                    scheduler.wake.notified(),
+                )
+                .await;
+            }
+        });
+    }
+
+    fn run_due(&self, now: NaiveDateTime) {
+        let due = self.queue.lock().unwrap().pop_due(now);
+        if !due.is_empty() {
+            self.schedule_dirty.store(true, Ordering::SeqCst);
+        }
+        // 提醒类任务在暂停时已从队列取消，这里只需拦下计时器与专注阶段。
+        let paused = due
+            .iter()
+            .any(|(_, key)| matches!(key.kind, JobKind::Timer | JobKind::Handler(_)))
+            && self.is_paused().unwrap_or(false);
+        for (_, key) in due {
+            self.snoozed.lock().unwrap().remove(&key);
+            if paused && matches!(key.kind, JobKind::Timer | JobKind::Handler(_)) {
+                self.paused_jobs.lock().unwrap().push(key);
+                continue;
+            }
+            let result = match key.kind {
+                JobKind::Task(reminder_id) => self.handle_task(key.id, reminder_id),
+                JobKind::Recurring => self.handle_recurring(key.id),
+                JobKind::Lead(minutes) => self.handle_lead(key.id, minutes),
+                JobKind::Due => self.handle_due(key.id),
+                JobKind::Resume => self.handle_resume(),
+                JobKind::Timer => self.handle_timer(key.id),
+                JobKind::Handler(name) => {
+                    let handler = self.handlers.lock().unwrap().get(name).cloned();
+                    match handler {
+                        Some(handler) => handler(&key.id),
+                        None => Ok(()),
+                    }
+                }
+            };
+            if let Err(err) = result {
+                eprintln!("[scheduler] {}", err);
+            }
+        }
+    }
+
+    fn handle_resume(&self) -> Result<(), AppError> {
+        let pause = self.db.load_pause_state()?;
+        // 恢复时间已被改到以后（如同步合并）时按新的时间重新安排。
+        match pause.resume_at.as_deref() {
+            Some(resume_at) if pause.paused => {
+                let resume_at = parse_datetime(resume_at)?;
+                if resume_at > self.clock.now() {
+                    self.schedule_job(JobKey::new(JobKind::Resume, GLOBAL_PAUSE_ID), resume_at);
+                    return Ok(());
+                }
+                self.resume_all()
+            }
+            _ => Ok(()),
         }
     }
 
@@ -107,23 +795,31 @@ impl ReminderScheduler {
         if task.deleted_at.is_some() || task.is_paused {
             return Ok(());
         }
-        let now = Local::now().naive_local();
+        let now = self.clock.now();
         if !should_trigger_now(&task, now)? {
-            task.next_trigger = compute_next_trigger(&task, Some(now))?;
+            task.next_trigger = compute_next_trigger(&task, now)?;
             self.db.update_recurring_task(&task)?;
-            self.sync.notify_local_change()?;
+            self.output.local_changed()?;
             self.schedule_recurring(task)?;
             return Ok(());
         }
         sanitize_recurring_task(&mut task)?;
-        task.last_triggered = Some(now_string());
-        task.next_trigger = compute_next_trigger(&task, Some(now))?;
+        let scheduled = parse_datetime(&task.next_trigger)?;
+        task.last_triggered = Some(format_datetime(now));
+        task.next_trigger = next_trigger_after(&task, scheduled, now)?;
         self.db.update_recurring_task(&task)?;
 
-        let record = self
-            .db
-            .create_reminder_record(&task.id, &task.description, "RECURRING")?;
-        self.sync.notify_local_change()?;
+        let record = self.db.create_occurrence_record(
+            &task.id,
+            &task.description,
+            "RECURRING",
+            &occurrence_id(&task.id, scheduled),
+        )?;
+        self.output.local_changed()?;
+        if already_handled(&record) {
+            self.schedule_recurring(task)?;
+            return Ok(());
+        }
         let settings = self.db.load_settings()?;
         let payload = NotificationPayload {
             record_id: record.id.clone(),
@@ -131,31 +827,50 @@ impl ReminderScheduler {
             reminder_type: "RECURRING".to_string(),
             description: task.description.clone(),
             snooze_minutes: settings.snooze_minutes,
+            actions: recurring_actions(&task),
+            digest_items: Vec::new(),
         };
-        *self.snapshot.lock().unwrap() = Some(payload.clone());
-        emit_notification(&self.app, &payload)?;
+        self.present(&settings, &record, &payload)?;
 
         self.schedule_recurring(task)?;
         Ok(())
     }
 
-    fn handle_task(&self, task_id: String) -> Result<(), AppError> {
+    fn handle_task(&self, task_id: String, reminder_id: String) -> Result<(), AppError> {
         let Some(task) = self.db.get_task(&task_id)? else {
             return Ok(());
         };
         if task.deleted_at.is_some() || task.status == "COMPLETED" {
             return Ok(());
         }
-        if let Some(reminder_time) = &task.reminder_time {
-            if !is_future(reminder_time)? {
-                return Ok(());
-            }
+        let Some(reminder) = self.db.get_task_reminder(&reminder_id)? else {
+            return Ok(());
+        };
+        // 提醒时间已删除、已在其他设备触发，或被改到以后（队列中的旧安排已被替换）时不触发。
+        let now = self.clock.now();
+        if reminder.task_id != task.id
+            || reminder.deleted_at.is_some()
+            || reminder.fired_at.is_some()
+            || is_future(&reminder.remind_at, now)?
+        {
+            return Ok(());
         }
+        let scheduled = parse_datetime(&reminder.remind_at)?;
 
-        let record = self
-            .db
-            .create_reminder_record(&task.id, &task.description, "TASK")?;
-        self.sync.notify_local_change()?;
+        let record = self.db.create_occurrence_record(
+            &task.id,
+            &task.description,
+            "TASK",
+            &occurrence_id(&task.id, scheduled),
+        )?;
+        self.db.mark_task_reminder_fired(&task.id, &reminder.id)?;
+        self.output.local_changed()?;
+        if let Some((_, next)) = self.pending_reminders(&task.id, now)?.first() {
+            self.schedule_leads(&task.id, *next, &task.notify_options.lead_minutes);
+        }
+        if already_handled(&record) {
+            return Ok(());
+        }
         let settings = self.db.load_settings()?;
         let payload = NotificationPayload {
             record_id: record.id.clone(),
@@ -163,84 +878,523 @@ impl ReminderScheduler {
             reminder_type: "TASK".to_string(),
             description: task.description.clone(),
             snooze_minutes: settings.snooze_minutes,
+            actions: task_actions(&task),
+            digest_items: Vec::new(),
         };
-        *self.snapshot.lock().unwrap() = Some(payload.clone());
-        emit_notification(&self.app, &payload)?;
+        self.present(&settings, &record, &payload)?;
         Ok(())
     }
-}
 
-fn emit_notification(app: &AppHandle, payload: &NotificationPayload) -> Result<(), AppError> {
-    let notification_width = 392.0;
-    let notification_height = if cfg!(target_os = "linux") {
-        228.0
-    } else {
-        248.0
-    };
-    let window = if let Some(existing) = app.get_webview_window("notification") {
-        existing
-    } else {
-        WebviewWindowBuilder::new(
-            app,
-            "notification",
-            WebviewUrl::App("notification.html".into()),
-        )
-        .title("提醒通知")
-        .decorations(false)
-        .transparent(true)
-        .shadow(false)
-        .always_on_top(true)
-        .resizable(false)
-        .inner_size(notification_width, notification_height)
-        .build()
-        .map_err(|e| AppError::System(e.to_string()))?
-    };
-    let _ = window.set_size(tauri::Size::Logical(tauri::LogicalSize::new(
-        notification_width,
-        notification_height,
-    )));
-    let _ = window.set_shadow(false);
-
-    if let Ok(monitor) = window.current_monitor() {
-        if let Some(monitor) = monitor {
-            let size = monitor.size();
-            let bottom_margin = if cfg!(target_os = "windows") { 84 } else { 48 };
-            let pos_x = size
-                .width
-                .saturating_sub((notification_width as u32).saturating_add(16))
-                as f64;
-            let pos_y = size
-                .height
-                .saturating_sub(notification_height as u32 + bottom_margin)
-                as f64;
-            let _ = window.set_position(tauri::LogicalPosition { x: pos_x, y: pos_y });
-        }
-    }
-
-    window.emit("notification", payload).ok();
-    window.show().ok();
-    window.set_focus().ok();
-    Ok(())
+    fn handle_due(&self, task_id: String) -> Result<(), AppError> {
+        let Some(task) = self.db.get_task(&task_id)? else {
+            return Ok(());
+        };
+        if task.deleted_at.is_some()
+            || task.status == "COMPLETED"
+            || !task.notify_options.overdue_alert
+        {
+            return Ok(());
+        }
+        let Some(due_at) = task.due_at.as_deref() else {
+            return Ok(());
+        };
+        let due = parse_datetime(due_at)?;
+        let now = self.clock.now();
+        let key = JobKey::new(JobKind::Due, &task.id);
+        // 截止时间已被改到以后（如同步合并），按新的时间重新安排。
+        if due > now {
+            self.schedule_job(key, due);
+            return Ok(());
+        }
+        let slot = due_alert_slot(due, now);
+        let description = due_description(&task.description, due, slot);
+        let record = self.db.create_occurrence_record(
+            &task.id,
+            &description,
+            DUE_REMINDER_TYPE,
+            &due_record_id(&occurrence_id(&task.id, slot)),
+        )?;
+        self.output.local_changed()?;
+        self.schedule_job(key, slot + Duration::days(1));
+        if already_handled(&record) {
+            return Ok(());
+        }
+        let settings = self.db.load_settings()?;
+        let payload = NotificationPayload {
+            record_id: record.id.clone(),
+            reminder_id: task.id.clone(),
+            reminder_type: DUE_REMINDER_TYPE.to_string(),
+            description,
+            snooze_minutes: settings.snooze_minutes,
+            actions: task_actions(&task),
+            digest_items: Vec::new(),
+        };
+        self.present(&settings, &record, &payload)
+    }
+
+    fn handle_lead(&self, task_id: String, minutes: i64) -> Result<(), AppError> {
+        let Some((scheduled, description, actions)) = self.lead_parent(&task_id)? else {
+            return Ok(());
+        };
+        // 所属触发点已改期，或提前量对应的时间还没到（队列中的旧安排已被替换）时不触发。
+        let now = self.clock.now();
+        if scheduled <= now || lead_deadline(scheduled, minutes) > now {
+            return Ok(());
+        }
+        let description = lead_description(&description, minutes);
+        let record = self.db.create_lead_record(
+            &task_id,
+            &description,
+            &occurrence_id(&task_id, scheduled),
+            minutes,
+        )?;
+        self.output.local_changed()?;
+        if already_handled(&record) {
+            return Ok(());
+        }
+        let settings = self.db.load_settings()?;
+        let payload = NotificationPayload {
+            record_id: record.id.clone(),
+            reminder_id: task_id,
+            reminder_type: LEAD_REMINDER_TYPE.to_string(),
+            description,
+            snooze_minutes: settings.snooze_minutes,
+            actions,
+            digest_items: Vec::new(),
+        };
+        self.present(&settings, &record, &payload)
+    }
+
+    /// 提前提醒所属的任务或循环任务：计划触发时间、描述与通知按钮。已完成、暂停或删除的返回 None。
+    fn lead_parent(
+        &self,
+        task_id: &str,
+    ) -> Result<Option<(NaiveDateTime, String, Vec<NotificationAction>)>, AppError> {
+        if let Some(task) = self.db.get_task(task_id)? {
+            if task.deleted_at.is_some() || task.status == "COMPLETED" {
+                return Ok(None);
+            }
+            let pending = self.pending_reminders(&task.id, self.clock.now())?;
+            let Some((_, scheduled)) = pending.first() else {
+                return Ok(None);
+            };
+            return Ok(Some((
+                *scheduled,
+                task.description.clone(),
+                task_actions(&task),
+            )));
+        }
+        let Some(task) = self.db.get_recurring_task(task_id)? else {
+            return Ok(None);
+        };
+        if task.deleted_at.is_some() || task.is_paused {
+            return Ok(None);
+        }
+        let scheduled = parse_datetime(&task.next_trigger)?;
+        Ok(Some((
+            scheduled,
+            task.description.clone(),
+            recurring_actions(&task),
+        )))
+    }
+
+    fn handle_timer(&self, timer_id: String) -> Result<(), AppError> {
+        let Some(timer) = self.db.get_timer(&timer_id)? else {
+            return Ok(());
+        };
+        if timer.status != TIMER_RUNNING {
+            return Ok(());
+        }
+        // 计时器到点后即删除，之后只保留提醒记录。
+        self.db.delete_timer(&timer.id)?;
+        self.output.timers_changed();
+        self.present_system_reminder(&timer.id, TIMER_REMINDER_TYPE, &timer.label)
+    }
+
+    /// 计时器、休息提醒等不对应任务的提醒：生成提醒记录并弹窗。
+    pub fn present_system_reminder(
+        &self,
+        reminder_id: &str,
+        reminder_type: &str,
+        description: &str,
+    ) -> Result<(), AppError> {
+        let record = self
+            .db
+            .create_reminder_record(reminder_id, description, reminder_type)?;
+        self.output.local_changed()?;
+        let settings = self.db.load_settings()?;
+        let payload = NotificationPayload {
+            record_id: record.id.clone(),
+            reminder_id: reminder_id.to_string(),
+            reminder_type: reminder_type.to_string(),
+            description: description.to_string(),
+            snooze_minutes: settings.snooze_minutes,
+            actions: Vec::new(),
+            digest_items: Vec::new(),
+        };
+        self.present(&settings, &record, &payload)
+    }
+
+    /// 弹窗中是否还有未处理的提醒。
+    pub fn has_pending_notification(&self) -> bool {
+        self.snapshot.lock().unwrap().is_some()
+    }
+
+    /// 每分钟按墙上时间检查一次每日简报与周报，休眠或关机错过时间后会补发。
+    pub fn start_periodic_jobs(&self) {
+        let scheduler = self.clone();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                if let Err(err) = scheduler.check_digest() {
+                    eprintln!("[digest] {}", err);
+                }
+                if let Err(err) = scheduler.check_report() {
+                    eprintln!("[report] {}", err);
+                }
+                sleep(std::time::Duration::from_secs(PERIODIC_CHECK_SECS)).await;
+            }
+        });
+    }
+
+    /// 到点时生成本周周报；写入失败不记录生成日期，下次检查时重试。
+    pub(crate) fn check_report(&self) -> Result<(), AppError> {
+        let settings = self.db.load_settings()?;
+        let now = self.clock.now();
+        if !report_due(&settings, now) {
+            return Ok(());
+        }
+        let report = build_weekly_report(&self.db, now.date(), now.date())?;
+        let target = if settings.report_dir.trim().is_empty() {
+            paths::default_reports_dir(&self.output.data_dir()?)
+        } else {
+            PathBuf::from(settings.report_dir.trim())
+        };
+        write_report(
+            &target,
+            &report_file_name(&report, &settings.report_format),
+            &render_report(&report, &settings.report_format),
+        )?;
+        self.db
+            .update_report_last_date(&now.format("%Y-%m-%d").to_string())?;
+        Ok(())
+    }
+
+    fn check_digest(&self) -> Result<(), AppError> {
+        let settings = self.db.load_settings()?;
+        let now = self.clock.now();
+        if !digest_due(&settings, now) || self.is_paused()? {
+            return Ok(());
+        }
+        // 不覆盖尚未处理的提醒，等弹窗关闭后的下一次检查再发送。
+        if self.has_pending_notification() {
+            return Ok(());
+        }
+        self.db
+            .update_digest_last_date(&now.format("%Y-%m-%d").to_string())?;
+        self.send_digest(&settings)
+    }
+
+    pub fn send_digest(&self, settings: &AppSettings) -> Result<(), AppError> {
+        let now = self.clock.now();
+        let items = build_digest_items(
+            now,
+            &self.db.list_active_tasks()?,
+            &self.db.list_recurring_tasks()?,
+            &self.db.list_sticky_notes()?,
+        );
+        let payload = NotificationPayload {
+            record_id: format!("digest-{}", now.format("%Y-%m-%d")),
+            reminder_id: String::new(),
+            reminder_type: DIGEST_REMINDER_TYPE.to_string(),
+            description: digest_title(&items),
+            snooze_minutes: settings.snooze_minutes,
+            actions: Vec::new(),
+            digest_items: items,
+        };
+        self.present_transient(settings, &payload)
+    }
+
+    /// 显示不产生提醒记录、也不走外部渠道的弹窗（简报、专注时段等），只占用弹窗。
+    pub fn present_transient(
+        &self,
+        settings: &AppSettings,
+        payload: &NotificationPayload,
+    ) -> Result<(), AppError> {
+        if self.hold_if_away(None, payload) {
+            return Ok(());
+        }
+        let previous = self.snapshot.lock().unwrap().replace(payload.clone());
+        if let Some(previous) = previous {
+            if previous.record_id != payload.record_id {
+                self.mark_missed(&previous.record_id)?;
+            }
+        }
+        self.output.show(settings, payload)
+    }
 }
 
-fn seconds_until(value: &str) -> Result<u64, AppError> {
-    let target = parse_datetime(value)?;
-    let now = Local::now().naive_local();
-    let diff = target.signed_duration_since(now).num_seconds();
-    Ok(diff.max(0) as u64)
+impl ReminderScheduler {
+    pub fn start_timer(&self, label: Option<&str>, seconds: i64) -> Result<Timer, AppError> {
+        let seconds = normalize_timer_seconds(seconds)?;
+        let now = self.clock.now();
+        let timer = self.db.create_timer(
+            &timer_label(label, seconds),
+            seconds,
+            &ends_at_after(seconds, now),
+        )?;
+        self.schedule_timer(&timer)?;
+        self.output.timers_changed();
+        Ok(timer)
+    }
+
+    pub fn pause_timer(&self, timer_id: &str) -> Result<(), AppError> {
+        let timer = self.require_timer(timer_id)?;
+        if timer.status != TIMER_RUNNING {
+            return Ok(());
+        }
+        self.cancel_timer(timer_id);
+        let remaining = remaining_seconds(&timer, self.clock.now()).max(1);
+        self.db
+            .update_timer_state(timer_id, TIMER_PAUSED, None, remaining)?;
+        self.output.timers_changed();
+        Ok(())
+    }
+
+    pub fn resume_timer(&self, timer_id: &str) -> Result<(), AppError> {
+        let mut timer = self.require_timer(timer_id)?;
+        if timer.status != TIMER_PAUSED {
+            return Ok(());
+        }
+        let ends_at = ends_at_after(timer.remaining_seconds, self.clock.now());
+        self.db.update_timer_state(
+            timer_id,
+            TIMER_RUNNING,
+            Some(&ends_at),
+            timer.remaining_seconds,
+        )?;
+        timer.status = TIMER_RUNNING.to_string();
+        timer.ends_at = Some(ends_at);
+        self.schedule_timer(&timer)?;
+        self.output.timers_changed();
+        Ok(())
+    }
+
+    pub fn stop_timer(&self, timer_id: &str) -> Result<(), AppError> {
+        self.cancel_timer(timer_id);
+        self.db.delete_timer(timer_id)?;
+        self.output.timers_changed();
+        Ok(())
+    }
+
+    fn require_timer(&self, timer_id: &str) -> Result<Timer, AppError> {
+        self.db
+            .get_timer(timer_id)?
+            .ok_or_else(|| AppError::Invalid("计时器不存在或已结束".to_string()))
+    }
+
+    fn present(
+        &self,
+        settings: &AppSettings,
+        record: &ReminderRecord,
+        payload: &NotificationPayload,
+    ) -> Result<(), AppError> {
+        if self.hold_if_away(Some(record), payload) {
+            return Ok(());
+        }
+        let previous = self.snapshot.lock().unwrap().replace(payload.clone());
+        // 弹窗只有一个，新提醒覆盖尚未处理的旧提醒时，旧提醒视为错过。
+        if let Some(previous) = previous {
+            if previous.record_id != payload.record_id {
+                self.mark_missed(&previous.record_id)?;
+            }
+        }
+        self.output.show(settings, payload)?;
+        self.output.reminder_fired(record, payload);
+        Ok(())
+    }
+
+    /// 由活动监测更新用户是否在电脑前（锁屏或长时间无输入视为离开）。
+    pub fn set_away(&self, away: bool) {
+        self.away.store(away, Ordering::SeqCst);
+    }
+
+    pub fn is_away(&self) -> bool {
+        self.away.load(Ordering::SeqCst)
+    }
+
+    fn hold_if_away(&self, record: Option<&ReminderRecord>, payload: &NotificationPayload) -> bool {
+        if !self.is_away() {
+            return false;
+        }
+        self.held.lock().unwrap().push_back(HeldNotification {
+            record: record.cloned(),
+            payload: payload.clone(),
+        });
+        true
+    }
+
+    /// 用户回来后逐条补发暂缓的提醒，每次只在弹窗空闲时弹出一条，避免互相覆盖成“错过”。
+    pub fn deliver_held(&self) -> Result<(), AppError> {
+        if self.is_away() || self.has_pending_notification() {
+            return Ok(());
+        }
+        let Some(held) = self.held.lock().unwrap().pop_front() else {
+            return Ok(());
+        };
+        let settings = self.db.load_settings()?;
+        let Some(record) = held.record else {
+            return self.present_transient(&settings, &held.payload);
+        };
+        // 暂缓期间可能已在其他设备上处理或被删除。
+        let Some(record) = self
+            .db
+            .mark_reminder_record_delivered_late(&record.id)?
+            .filter(|record| record.action == "PENDING" && record.deleted_at.is_none())
+        else {
+            return Ok(());
+        };
+        self.output.local_changed()?;
+        self.present(&settings, &record, &held.payload)
+    }
+
+    /// 同步合并后调用：收起已在其他设备上处理过的弹窗，并从暂缓队列中去掉已处理的提醒。
+    /// 返回被收起或丢弃的记录 id。
+    pub fn close_handled_elsewhere(&self) -> Result<Vec<String>, AppError> {
+        let mut closed = Vec::new();
+        let showing = self.snapshot.lock().unwrap().clone();
+        if let Some(payload) = showing {
+            if self.is_handled(&payload.record_id)? {
+                let mut snapshot = self.snapshot.lock().unwrap();
+                if snapshot
+                    .as_ref()
+                    .is_some_and(|current| current.record_id == payload.record_id)
+                {
+                    *snapshot = None;
+                }
+                drop(snapshot);
+                self.output.close(&payload.record_id);
+                closed.push(payload.record_id);
+            }
+        }
+
+        let held_ids: Vec<String> = self
+            .held
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|held| held.record.as_ref().map(|record| record.id.clone()))
+            .collect();
+        let mut handled = HashSet::new();
+        for record_id in held_ids {
+            if self.is_handled(&record_id)? {
+                handled.insert(record_id);
+            }
+        }
+        if !handled.is_empty() {
+            self.held.lock().unwrap().retain(|held| {
+                !held
+                    .record
+                    .as_ref()
+                    .is_some_and(|record| handled.contains(&record.id))
+            });
+            closed.extend(handled);
+        }
+        Ok(closed)
+    }
+
+    fn is_handled(&self, record_id: &str) -> Result<bool, AppError> {
+        Ok(self
+            .db
+            .get_reminder_record(record_id)?
+            .is_some_and(|record| already_handled(&record)))
+    }
+
+    fn mark_missed(&self, record_id: &str) -> Result<(), AppError> {
+        let Some(record) = self.db.get_reminder_record(record_id)? else {
+            return Ok(());
+        };
+        if record.action != "PENDING" {
+            return Ok(());
+        }
+        self.db.update_reminder_record_action(record_id, "MISSED")?;
+        self.output.local_changed()?;
+        if let Some(record) = self.db.get_reminder_record(record_id)? {
+            self.output.reminder_closed(&record);
+        }
+        Ok(())
+    }
 }
 
 fn parse_datetime(value: &str) -> Result<NaiveDateTime, AppError> {
     parse_datetime_any(value).ok_or_else(|| AppError::Invalid(format!("无法解析时间: {}", value)))
 }
 
-fn now_string() -> String {
-    Local::now().format("%Y-%m-%dT%H:%M:%S").to_string()
+/// 一次触发的标识：任务 id 加计划触发时间，各设备对同一次触发得到相同的标识。
+pub fn occurrence_id(reminder_id: &str, scheduled: NaiveDateTime) -> String {
+    format!("{}@{}", reminder_id, format_datetime(scheduled))
+}
+
+/// 同一次触发已在其他设备上处理或删除，本机不再弹出。
+fn already_handled(record: &ReminderRecord) -> bool {
+    record.deleted_at.is_some() || is_acknowledged_action(&record.action)
+}
+
+/// 从计划触发时间推算下一次，各设备算出的结果一致；已错过下一次（如休眠）时改从当前时间推算。
+fn next_trigger_after(
+    task: &RecurringTask,
+    scheduled: NaiveDateTime,
+    now: NaiveDateTime,
+) -> Result<String, AppError> {
+    let next = compute_next_trigger(task, scheduled)?;
+    if parse_datetime(&next)? > now {
+        return Ok(next);
+    }
+    compute_next_trigger(task, now)
+}
+
+fn lead_jobs(
+    task_id: &str,
+    scheduled: NaiveDateTime,
+    lead_minutes: &[i64],
+    now: NaiveDateTime,
+) -> Vec<(JobKey, NaiveDateTime)> {
+    lead_minutes
+        .iter()
+        .map(|&minutes| {
+            (
+                JobKey::new(JobKind::Lead(minutes), task_id),
+                lead_deadline(scheduled, minutes),
+            )
+        })
+        .filter(|(_, deadline)| *deadline > now)
+        .collect()
+}
+
+fn upcoming_item(
+    fire_at: NaiveDateTime,
+    reminder_id: &str,
+    reminder_type: &str,
+    snoozed: bool,
+    description: &str,
+) -> (NaiveDateTime, UpcomingReminder) {
+    let source = if snoozed { "SNOOZE" } else { reminder_type };
+    let item = UpcomingReminder {
+        reminder_id: reminder_id.to_string(),
+        reminder_type: reminder_type.to_string(),
+        source: source.to_string(),
+        description: description.to_string(),
+        fire_at: format_datetime(fire_at),
+    };
+    (fire_at, item)
+}
+
+fn format_datetime(value: NaiveDateTime) -> String {
+    value.format("%Y-%m-%dT%H:%M:%S").to_string()
 }
 
-pub fn is_future(value: &str) -> Result<bool, AppError> {
+pub fn is_future(value: &str, now: NaiveDateTime) -> Result<bool, AppError> {
     let target = parse_datetime(value)?;
-    Ok(target > Local::now().naive_local())
+    Ok(target > now)
 }
 
 fn parse_datetime_any(value: &str) -> Option<NaiveDateTime> {
@@ -0,0 +1,1222 @@
+//! 调度器的确定性模拟：用手动时钟推进虚拟时间，断言触发了哪些提醒、写入了哪些记录。
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, NaiveDateTime};
+use uuid::Uuid;
+
+use crate::clock::ManualClock;
+use crate::db::{DbManager, TaskEvent};
+use crate::due::{
+    DUE_REMINDER_TYPE, TASK_FILTER_DUE_TODAY, TASK_FILTER_NO_DUE, TASK_FILTER_OVERDUE,
+    TASK_SORT_DUE,
+};
+use crate::errors::AppError;
+use crate::leads::LEAD_REMINDER_TYPE;
+use crate::models::{
+    AppSettings, NotificationPayload, NotifyOptions, RecurringTask, ReminderRecord, Task,
+};
+use crate::pause::MISSED_POLICY_SKIP;
+use crate::queue::{JobKey, JobKind};
+use crate::recurrence::{compute_next_trigger, sanitize_recurring_task};
+use crate::scheduler::{occurrence_id, ReminderOutput, ReminderScheduler};
+
+/// 记录调度器的所有输出，代替真实的弹窗与外部渠道。
+#[derive(Default)]
+pub struct RecordingOutput {
+    pub shown: Mutex<Vec<NotificationPayload>>,
+    pub fired: Mutex<Vec<String>>,
+    pub closed: Mutex<Vec<ReminderRecord>>,
+    pub closed_popups: Mutex<Vec<String>>,
+    pub schedule_changes: Mutex<usize>,
+}
+
+impl ReminderOutput for RecordingOutput {
+    fn show(&self, _settings: &AppSettings, payload: &NotificationPayload) -> Result<(), AppError> {
+        self.shown.lock().unwrap().push(payload.clone());
+        Ok(())
+    }
+
+    fn reminder_fired(&self, record: &ReminderRecord, _payload: &NotificationPayload) {
+        self.fired.lock().unwrap().push(record.id.clone());
+    }
+
+    fn reminder_closed(&self, record: &ReminderRecord) {
+        self.closed.lock().unwrap().push(record.clone());
+    }
+
+    fn close(&self, record_id: &str) {
+        self.closed_popups
+            .lock()
+            .unwrap()
+            .push(record_id.to_string());
+    }
+
+    fn local_changed(&self) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    fn timers_changed(&self) {}
+
+    fn schedule_changed(&self) {
+        *self.schedule_changes.lock().unwrap() += 1;
+    }
+
+    fn data_dir(&self) -> Result<PathBuf, AppError> {
+        Ok(std::env::temp_dir())
+    }
+}
+
+pub struct Simulation {
+    pub clock: Arc<ManualClock>,
+    pub db: DbManager,
+    pub scheduler: ReminderScheduler,
+    pub output: Arc<RecordingOutput>,
+    snapshot: Arc<Mutex<Option<NotificationPayload>>>,
+    db_path: PathBuf,
+}
+
+impl Simulation {
+    /// 以 `start`（如 "2026-01-05T08:00:00"）为虚拟起始时间，使用临时数据库。
+    pub fn new(start: &str) -> Self {
+        let clock = ManualClock::new(at(start));
+        let db_path = std::env::temp_dir().join(format!("task-reminder-sim-{}.db", Uuid::new_v4()));
+        let db = DbManager::with_clock(db_path.clone(), clock.clone()).unwrap();
+        let output = Arc::new(RecordingOutput::default());
+        let snapshot = Arc::new(Mutex::new(None));
+        let scheduler =
+            ReminderScheduler::with_output(db.clone(), output.clone(), snapshot.clone());
+        Self {
+            clock,
+            db,
+            scheduler,
+            output,
+            snapshot,
+            db_path,
+        }
+    }
+
+    pub fn now(&self) -> NaiveDateTime {
+        crate::clock::Clock::now(self.clock.as_ref())
+    }
+
+    /// 模拟应用重启：内存中的调度队列清空，新的调度器从数据库恢复全部安排。
+    pub fn restart(&mut self) {
+        self.scheduler = ReminderScheduler::with_output(
+            self.db.clone(),
+            self.output.clone(),
+            self.snapshot.clone(),
+        );
+        self.scheduler.schedule_existing().unwrap();
+    }
+
+    /// 推进虚拟时间，途经的每个到期时间点都会让调度器处理一次，相当于电脑一直醒着。
+    pub fn advance(&self, duration: Duration) {
+        let target = self.now() + duration;
+        while let Some(next) = self
+            .scheduler
+            .next_deadline()
+            .filter(|next| *next <= target)
+        {
+            self.clock.set(next.max(self.now()));
+            self.scheduler.tick();
+        }
+        self.clock.set(target);
+        self.scheduler.tick();
+    }
+
+    /// 时钟直接跳过 `duration`（可为负）再按调度循环检测到跳变时的方式处理一次，
+    /// 模拟休眠唤醒或手动改时间。
+    pub fn jump(&self, duration: Duration) {
+        self.clock.advance(duration);
+        self.scheduler.tick();
+        self.scheduler.clock_jumped().unwrap();
+    }
+
+    pub fn add_task(&self, description: &str, reminder_time: &str) -> Task {
+        let task = self.db.create_task(description, None).unwrap();
+        self.db
+            .update_task(
+                &task.id,
+                description,
+                None,
+                &[reminder_time.to_string()],
+                None,
+                None,
+            )
+            .unwrap();
+        let task = self.db.get_task(&task.id).unwrap().unwrap();
+        self.scheduler.schedule_task(task.clone()).unwrap();
+        task
+    }
+
+    pub fn add_daily(&self, description: &str, time: &str) -> RecurringTask {
+        let mut draft = RecurringTask {
+            id: String::new(),
+            description: description.to_string(),
+            task_type: "RECURRING".to_string(),
+            status: "PENDING".to_string(),
+            created_at: String::new(),
+            completed_at: None,
+            reminder_time: None,
+            updated_at: None,
+            deleted_at: None,
+            interval_minutes: 60,
+            last_triggered: None,
+            next_trigger: String::new(),
+            is_paused: false,
+            start_time: None,
+            end_time: None,
+            repeat_mode: "DAILY".to_string(),
+            schedule_time: Some(time.to_string()),
+            schedule_weekday: None,
+            schedule_day: None,
+            cron_expression: None,
+            notify_options: NotifyOptions::default(),
+        };
+        sanitize_recurring_task(&mut draft).unwrap();
+        draft.next_trigger = compute_next_trigger(&draft, self.now()).unwrap();
+        let task = self.db.create_recurring_task(&draft).unwrap();
+        self.scheduler.schedule_recurring(task.clone()).unwrap();
+        task
+    }
+
+    /// 给任务或循环任务设置提前提醒并重新安排。
+    pub fn set_leads(&self, task_id: &str, minutes: &[i64]) {
+        if let Some(mut task) = self.db.get_task(task_id).unwrap() {
+            task.notify_options.lead_minutes = minutes.to_vec();
+            self.db
+                .update_task_notify_options(task_id, &task.notify_options)
+                .unwrap();
+            self.scheduler.schedule_task(task).unwrap();
+            return;
+        }
+        let mut task = self.db.get_recurring_task(task_id).unwrap().unwrap();
+        task.notify_options.lead_minutes = minutes.to_vec();
+        self.db.update_recurring_task(&task).unwrap();
+        self.scheduler.schedule_recurring(task).unwrap();
+    }
+
+    /// 替换任务的全部提醒时间并重新安排。
+    pub fn set_reminders(&self, task_id: &str, times: &[&str]) {
+        let task = self.db.get_task(task_id).unwrap().unwrap();
+        let times: Vec<String> = times.iter().map(|time| time.to_string()).collect();
+        self.db
+            .update_task(task_id, &task.description, None, &times, None, None)
+            .unwrap();
+        let task = self.db.get_task(task_id).unwrap().unwrap();
+        self.scheduler.schedule_task(task).unwrap();
+    }
+
+    /// 设置任务的截止时间与是否逾期提醒，并重新安排。
+    pub fn set_due(&self, task_id: &str, due_at: Option<&str>, overdue_alert: bool) {
+        let mut task = self.db.get_task(task_id).unwrap().unwrap();
+        task.notify_options.overdue_alert = overdue_alert;
+        let times: Vec<String> = self
+            .db
+            .list_task_reminders(task_id)
+            .unwrap()
+            .into_iter()
+            .map(|reminder| reminder.remind_at)
+            .collect();
+        self.db
+            .update_task(
+                task_id,
+                &task.description,
+                None,
+                &times,
+                Some(due_at),
+                Some(&task.notify_options),
+            )
+            .unwrap();
+        let task = self.db.get_task(task_id).unwrap().unwrap();
+        self.scheduler.schedule_task(task).unwrap();
+    }
+
+    /// 用户关闭当前弹窗。
+    pub fn dismiss(&self) {
+        if let Some(payload) = self.snapshot.lock().unwrap().take() {
+            let _ = self
+                .db
+                .update_reminder_record_action(&payload.record_id, "DISMISSED");
+        }
+    }
+
+    pub fn shown(&self) -> Vec<String> {
+        self.output
+            .shown
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|payload| payload.description.clone())
+            .collect()
+    }
+
+    /// 按触发时间先后排列的提醒记录。
+    pub fn records(&self) -> Vec<ReminderRecord> {
+        let mut records = self.db.list_reminder_records().unwrap();
+        records.reverse();
+        records
+    }
+}
+
+impl Drop for Simulation {
+    fn drop(&mut self) {
+        for suffix in ["", "-wal", "-shm"] {
+            let mut path = self.db_path.clone().into_os_string();
+            path.push(suffix);
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+pub fn at(value: &str) -> NaiveDateTime {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::Clock;
+
+    #[test]
+    fn task_fires_once_at_reminder_time() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        sim.add_task("交周报", "2026-01-05T08:30:00");
+
+        sim.advance(Duration::minutes(29));
+        assert!(sim.shown().is_empty());
+
+        sim.advance(Duration::minutes(60));
+        assert_eq!(sim.shown(), vec!["交周报"]);
+        let records = sim.records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].trigger_time, "2026-01-05T08:30:00");
+        assert_eq!(records[0].reminder_type, "TASK");
+        assert_eq!(records[0].action, "PENDING");
+    }
+
+    #[test]
+    fn daily_recurring_fires_every_day_at_schedule_time() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        sim.add_daily("喝水", "09:00");
+
+        sim.advance(Duration::days(3));
+
+        let times: Vec<String> = sim
+            .records()
+            .into_iter()
+            .map(|record| record.trigger_time)
+            .collect();
+        assert_eq!(
+            times,
+            vec![
+                "2026-01-05T09:00:00",
+                "2026-01-06T09:00:00",
+                "2026-01-07T09:00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn resume_after_suspend_fires_overdue_reminders_once() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        sim.add_task("开会", "2026-01-05T10:00:00");
+        let daily = sim.add_daily("站会", "09:00");
+
+        // 休眠两天，期间调度器没有机会运行。
+        sim.jump(Duration::days(2));
+
+        let records = sim.records();
+        assert_eq!(records.len(), 2);
+        assert!(records
+            .iter()
+            .all(|record| record.trigger_time == "2026-01-07T08:00:00"));
+        let daily = sim.db.get_recurring_task(&daily.id).unwrap().unwrap();
+        assert_eq!(daily.next_trigger, "2026-01-07T09:00:00");
+        assert_eq!(
+            sim.scheduler.next_deadline(),
+            Some(at("2026-01-07T09:00:00"))
+        );
+    }
+
+    #[test]
+    fn clock_set_back_does_not_fire_early() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        sim.add_task("交电费", "2026-01-05T09:00:00");
+
+        sim.clock.set(at("2026-01-05T07:00:00"));
+        sim.advance(Duration::minutes(90));
+        assert!(sim.shown().is_empty());
+
+        sim.advance(Duration::minutes(30));
+        assert_eq!(sim.shown(), vec!["交电费"]);
+    }
+
+    #[test]
+    fn clock_jumps_recompute_the_recurring_next_trigger() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let daily = sim.add_daily("站会", "09:00");
+        sim.advance(Duration::hours(2));
+        assert_eq!(sim.shown(), vec!["站会"]);
+
+        // 拨回一天后，当天的 09:00 重新成为下一次触发。
+        sim.jump(Duration::days(-1));
+        let task = sim.db.get_recurring_task(&daily.id).unwrap().unwrap();
+        assert_eq!(task.next_trigger, "2026-01-05T09:00:00");
+        assert_eq!(
+            sim.scheduler.next_deadline(),
+            Some(at("2026-01-05T09:00:00"))
+        );
+
+        // 拨快两天，错过的触发补发一次后按新的时间排到下一天。
+        sim.jump(Duration::days(2));
+        assert_eq!(sim.shown(), vec!["站会", "站会"]);
+        let task = sim.db.get_recurring_task(&daily.id).unwrap().unwrap();
+        assert_eq!(task.next_trigger, "2026-01-07T09:00:00");
+        assert_eq!(
+            sim.scheduler.next_deadline(),
+            Some(at("2026-01-07T09:00:00"))
+        );
+    }
+
+    #[test]
+    fn weekly_report_date_is_recorded_only_after_the_file_is_written() {
+        let sim = Simulation::new("2026-01-05T18:00:00");
+        let blocker = std::env::temp_dir().join(format!("task-reminder-report-{}", Uuid::new_v4()));
+        std::fs::write(&blocker, "").unwrap();
+        let mut settings = sim.db.load_settings().unwrap();
+        settings.report_enabled = true;
+        settings.report_weekday = 1;
+        settings.report_time = "17:00".to_string();
+        settings.report_dir = blocker.join("reports").to_string_lossy().to_string();
+        sim.db.save_settings(&settings).unwrap();
+
+        assert!(sim.scheduler.check_report().is_err());
+        assert_eq!(sim.db.load_settings().unwrap().report_last_date, None);
+
+        let dir = std::env::temp_dir().join(format!("task-reminder-reports-{}", Uuid::new_v4()));
+        settings.report_dir = dir.to_string_lossy().to_string();
+        sim.db.save_settings(&settings).unwrap();
+        sim.scheduler.check_report().unwrap();
+        assert_eq!(
+            sim.db.load_settings().unwrap().report_last_date.as_deref(),
+            Some("2026-01-05")
+        );
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        let _ = std::fs::remove_file(blocker);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn rescheduled_task_only_fires_at_new_time() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let task = sim.add_task("打电话", "2026-01-05T08:30:00");
+        sim.db
+            .update_task(
+                &task.id,
+                "打电话",
+                None,
+                &["2026-01-05T09:00:00".to_string()],
+                None,
+                None,
+            )
+            .unwrap();
+        sim.scheduler
+            .schedule_task(sim.db.get_task(&task.id).unwrap().unwrap())
+            .unwrap();
+
+        sim.advance(Duration::minutes(45));
+        assert!(sim.shown().is_empty());
+        sim.advance(Duration::minutes(15));
+        assert_eq!(sim.records()[0].trigger_time, "2026-01-05T09:00:00");
+    }
+
+    #[test]
+    fn cancelled_task_does_not_fire() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let task = sim.add_task("取快递", "2026-01-05T08:30:00");
+        sim.scheduler.cancel_task(&task.id);
+
+        sim.advance(Duration::hours(1));
+        assert!(sim.shown().is_empty());
+        assert!(sim.records().is_empty());
+    }
+
+    #[test]
+    fn unhandled_reminder_is_missed_when_the_next_one_pops_up() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        sim.add_task("第一条", "2026-01-05T08:10:00");
+        sim.add_task("第二条", "2026-01-05T08:20:00");
+
+        sim.advance(Duration::minutes(30));
+
+        let records = sim.records();
+        assert_eq!(records[0].action, "MISSED");
+        assert_eq!(records[1].action, "PENDING");
+        assert_eq!(sim.output.closed.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn reminders_are_held_while_away_and_delivered_late() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        sim.add_task("回邮件", "2026-01-05T08:30:00");
+        sim.add_task("订会议室", "2026-01-05T08:40:00");
+
+        sim.scheduler.set_away(true);
+        sim.advance(Duration::hours(1));
+        assert!(sim.shown().is_empty());
+        assert_eq!(sim.records().len(), 2);
+
+        sim.scheduler.set_away(false);
+        sim.scheduler.deliver_held().unwrap();
+        assert_eq!(sim.shown(), vec!["回邮件"]);
+        // 弹窗未关闭前不补发下一条，避免把上一条覆盖成错过。
+        sim.scheduler.deliver_held().unwrap();
+        assert_eq!(sim.shown().len(), 1);
+
+        sim.dismiss();
+        sim.advance(Duration::minutes(5));
+        sim.scheduler.deliver_held().unwrap();
+        assert_eq!(sim.shown(), vec!["回邮件", "订会议室"]);
+
+        let records = sim.records();
+        assert_eq!(records[0].trigger_time, "2026-01-05T08:30:00");
+        assert_eq!(records[0].delivery_state.as_deref(), Some("DELIVERED_LATE"));
+        assert_eq!(
+            records[0].delivered_at.as_deref(),
+            Some("2026-01-05T09:00:00")
+        );
+        assert_eq!(
+            records[1].delivered_at.as_deref(),
+            Some("2026-01-05T09:05:00")
+        );
+    }
+
+    #[test]
+    fn upcoming_merges_tasks_recurring_and_timers_in_time_order() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        sim.add_task("交周报", "2026-01-05T10:00:00");
+        sim.add_daily("喝水", "09:00");
+        sim.scheduler.start_timer(Some("泡茶"), 5 * 60).unwrap();
+
+        let upcoming = sim.scheduler.upcoming(4).unwrap();
+        let summary: Vec<(&str, &str, &str)> = upcoming
+            .iter()
+            .map(|item| {
+                (
+                    item.source.as_str(),
+                    item.description.as_str(),
+                    item.fire_at.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("TIMER", "泡茶", "2026-01-05T08:05:00"),
+                ("RECURRING", "喝水", "2026-01-05T09:00:00"),
+                ("TASK", "交周报", "2026-01-05T10:00:00"),
+                ("RECURRING", "喝水", "2026-01-06T09:00:00"),
+            ]
+        );
+        assert_eq!(sim.scheduler.upcoming(1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn snoozed_task_is_listed_as_snooze_until_it_fires() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let task = sim.add_task("回电话", "2026-01-05T08:10:00");
+        sim.advance(Duration::minutes(10));
+
+        let snoozed_until = "2026-01-05T08:20:00".to_string();
+        let reminder = sim.db.add_task_snooze(&task.id, &snoozed_until).unwrap();
+        let task = sim.db.get_task(&task.id).unwrap().unwrap();
+        sim.scheduler.snooze_task(task, &reminder.id).unwrap();
+        let upcoming = sim.scheduler.upcoming(5).unwrap();
+        assert_eq!(upcoming[0].source, "SNOOZE");
+        assert_eq!(upcoming[0].reminder_type, "TASK");
+        assert_eq!(upcoming[0].fire_at, snoozed_until);
+
+        sim.advance(Duration::minutes(10));
+        assert_eq!(sim.shown(), vec!["回电话", "回电话"]);
+        assert!(sim.scheduler.upcoming(5).unwrap().is_empty());
+    }
+
+    #[test]
+    fn repeated_snoozes_do_not_count_as_reminder_times() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let task = sim.add_task("回电话", "2026-01-05T08:10:00");
+        for _ in 0..11 {
+            sim.advance(Duration::minutes(10));
+            let remind_at = (sim.clock.now() + Duration::minutes(5))
+                .format("%Y-%m-%dT%H:%M:%S")
+                .to_string();
+            let reminder = sim.db.add_task_snooze(&task.id, &remind_at).unwrap();
+            let task = sim.db.get_task(&task.id).unwrap().unwrap();
+            sim.scheduler.snooze_task(task, &reminder.id).unwrap();
+        }
+        assert_eq!(sim.shown().len(), 11);
+
+        let times: Vec<String> = sim
+            .db
+            .list_task_reminders(&task.id)
+            .unwrap()
+            .into_iter()
+            .map(|reminder| reminder.remind_at)
+            .collect();
+        assert_eq!(times, vec!["2026-01-05T08:10:00"]);
+        let times = crate::task_reminders::normalize_reminder_times(&times).unwrap();
+        sim.db
+            .update_task(&task.id, "回电话", None, &times, None, None)
+            .unwrap();
+        let task = sim.db.get_task(&task.id).unwrap().unwrap();
+        sim.scheduler.schedule_task(task).unwrap();
+
+        sim.advance(Duration::minutes(5));
+        assert_eq!(sim.shown().len(), 12);
+    }
+
+    #[test]
+    fn custom_sticky_note_is_announced_as_a_created_task() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let created = Arc::new(Mutex::new(Vec::new()));
+        let events = created.clone();
+        sim.db.subscribe(move |event| {
+            if let TaskEvent::Created(task_id) = event {
+                events.lock().unwrap().push(task_id.clone());
+            }
+        });
+        let task = sim.db.create_task("交周报", None).unwrap();
+        let note = sim
+            .db
+            .create_custom_sticky_note("购物清单", None, None, None, None, None)
+            .unwrap();
+        assert_eq!(*created.lock().unwrap(), vec![task.id, note.task_id]);
+    }
+
+    #[test]
+    fn schedule_changes_are_reported_once_per_tick() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        sim.add_task("第一条", "2026-01-05T09:00:00");
+        sim.add_task("第二条", "2026-01-05T10:00:00");
+        let changes = || *sim.output.schedule_changes.lock().unwrap();
+
+        sim.scheduler.tick();
+        assert_eq!(changes(), 1);
+        sim.scheduler.tick();
+        assert_eq!(changes(), 1);
+
+        sim.advance(Duration::hours(1));
+        assert_eq!(changes(), 2);
+    }
+
+    #[test]
+    fn reconcile_applies_changes_merged_from_another_device() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let edited = sim.add_task("改时间", "2026-01-05T09:00:00");
+        let deleted = sim.add_task("已删除", "2026-01-05T09:30:00");
+        sim.add_task("不变", "2026-01-05T10:00:00");
+        let paused = sim.add_daily("已暂停", "11:00");
+
+        // 模拟同步合并：直接改写数据库，调度队列还是旧的。
+        let created = sim.db.create_task("新建", None).unwrap();
+        sim.db
+            .update_task(
+                &created.id,
+                "新建",
+                None,
+                &["2026-01-05T08:30:00".to_string()],
+                None,
+                None,
+            )
+            .unwrap();
+        sim.db
+            .update_task(
+                &edited.id,
+                "改时间",
+                None,
+                &["2026-01-05T12:00:00".to_string()],
+                None,
+                None,
+            )
+            .unwrap();
+        sim.db.delete_task(&deleted.id).unwrap();
+        let mut paused = sim.db.get_recurring_task(&paused.id).unwrap().unwrap();
+        paused.is_paused = true;
+        sim.db.update_recurring_task(&paused).unwrap();
+
+        let summary = sim.scheduler.reconcile().unwrap();
+        assert_eq!(summary.armed, vec![created.id.clone()]);
+        assert_eq!(summary.rescheduled, vec![edited.id.clone()]);
+        let mut cancelled = summary.cancelled.clone();
+        cancelled.sort();
+        let mut expected = vec![deleted.id.clone(), paused.id.clone()];
+        expected.sort();
+        assert_eq!(cancelled, expected);
+        assert_eq!(summary.unchanged, 1);
+        assert!(sim.scheduler.reconcile().unwrap().is_empty());
+
+        sim.advance(Duration::hours(5));
+        assert_eq!(sim.shown(), vec!["新建", "不变", "改时间"]);
+    }
+
+    #[test]
+    fn reconcile_does_not_arm_reminders_already_in_the_past() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let task = sim.db.create_task("其他设备已提醒", None).unwrap();
+        sim.db
+            .update_task(
+                &task.id,
+                "其他设备已提醒",
+                None,
+                &["2026-01-05T07:00:00".to_string()],
+                None,
+                None,
+            )
+            .unwrap();
+
+        let summary = sim.scheduler.reconcile().unwrap();
+        assert!(summary.is_empty());
+        sim.advance(Duration::hours(1));
+        assert!(sim.shown().is_empty());
+    }
+
+    #[test]
+    fn occurrence_handled_on_another_device_does_not_pop_up() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let daily = sim.add_daily("站会", "09:00");
+        let occurrence = occurrence_id(&daily.id, at("2026-01-05T09:00:00"));
+
+        // 另一台设备先触发并已关闭，记录随同步到达本机。
+        let remote = sim
+            .db
+            .create_occurrence_record(&daily.id, "站会", "RECURRING", &occurrence)
+            .unwrap();
+        sim.db
+            .update_reminder_record_action(&remote.id, "DISMISSED")
+            .unwrap();
+
+        sim.advance(Duration::hours(2));
+        assert!(sim.shown().is_empty());
+        let records = sim.records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].action, "DISMISSED");
+        let daily = sim.db.get_recurring_task(&daily.id).unwrap().unwrap();
+        assert_eq!(daily.next_trigger, "2026-01-06T09:00:00");
+    }
+
+    #[test]
+    fn firing_records_carry_the_occurrence_id() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let task = sim.add_task("交周报", "2026-01-05T08:30:00");
+
+        sim.advance(Duration::hours(1));
+        let record = &sim.records()[0];
+        let expected = format!("{}@2026-01-05T08:30:00", task.id);
+        assert_eq!(record.id, expected);
+        assert_eq!(record.occurrence_id.as_deref(), Some(expected.as_str()));
+    }
+
+    #[test]
+    fn late_firing_keeps_the_next_trigger_on_schedule() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let mut draft = sim.add_daily("喝水", "09:00");
+        draft.repeat_mode = "INTERVAL_RANGE".to_string();
+        draft.interval_minutes = 30;
+        draft.next_trigger = "2026-01-05T08:30:00".to_string();
+        sim.db.update_recurring_task(&draft).unwrap();
+        sim.scheduler.schedule_recurring(draft.clone()).unwrap();
+
+        // 调度循环晚了几秒才醒来，下一次仍按计划时间推算，两台设备结果一致。
+        sim.jump(Duration::seconds(30 * 60 + 7));
+        let task = sim.db.get_recurring_task(&draft.id).unwrap().unwrap();
+        assert_eq!(task.next_trigger, "2026-01-05T09:00:00");
+    }
+
+    #[test]
+    fn popup_closes_when_the_record_was_acknowledged_on_another_device() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let task = sim.add_task("交周报", "2026-01-05T08:30:00");
+        sim.advance(Duration::hours(1));
+        assert!(sim.scheduler.has_pending_notification());
+        let record_id = occurrence_id(&task.id, at("2026-01-05T08:30:00"));
+
+        assert!(sim.scheduler.close_handled_elsewhere().unwrap().is_empty());
+
+        // 同步合并带来了另一台设备上的“已关闭”。
+        sim.db
+            .update_reminder_record_action(&record_id, "DISMISSED")
+            .unwrap();
+        let closed = sim.scheduler.close_handled_elsewhere().unwrap();
+        assert_eq!(closed, vec![record_id.clone()]);
+        assert!(!sim.scheduler.has_pending_notification());
+        assert_eq!(*sim.output.closed_popups.lock().unwrap(), vec![record_id]);
+    }
+
+    #[test]
+    fn held_reminders_acknowledged_elsewhere_are_dropped() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let first = sim.add_task("回邮件", "2026-01-05T08:30:00");
+        sim.add_task("订会议室", "2026-01-05T08:40:00");
+        sim.scheduler.set_away(true);
+        sim.advance(Duration::hours(1));
+
+        let first_record = occurrence_id(&first.id, at("2026-01-05T08:30:00"));
+        sim.db
+            .update_reminder_record_action(&first_record, "COMPLETED")
+            .unwrap();
+        let closed = sim.scheduler.close_handled_elsewhere().unwrap();
+        assert_eq!(closed, vec![first_record]);
+
+        sim.scheduler.set_away(false);
+        sim.scheduler.deliver_held().unwrap();
+        assert_eq!(sim.shown(), vec!["订会议室"]);
+        assert!(sim.output.closed_popups.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn lead_reminders_fire_before_the_task_and_link_to_its_occurrence() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let task = sim.add_task("交周报", "2026-01-06T10:00:00");
+        sim.set_leads(&task.id, &[1440, 60, 10]);
+
+        sim.advance(Duration::days(2));
+        assert_eq!(
+            sim.shown(),
+            vec![
+                "1 天后: 交周报",
+                "1 小时后: 交周报",
+                "10 分钟后: 交周报",
+                "交周报",
+            ]
+        );
+        let occurrence = occurrence_id(&task.id, at("2026-01-06T10:00:00"));
+        let records = sim.records();
+        assert_eq!(records.len(), 4);
+        assert!(records
+            .iter()
+            .all(|record| record.occurrence_id.as_deref() == Some(occurrence.as_str())));
+        assert_eq!(records[0].reminder_type, LEAD_REMINDER_TYPE);
+        assert_eq!(records[0].trigger_time, "2026-01-05T10:00:00");
+        assert_eq!(records[3].id, occurrence);
+    }
+
+    #[test]
+    fn lead_times_already_passed_are_skipped() {
+        let sim = Simulation::new("2026-01-05T09:30:00");
+        let task = sim.add_task("开会", "2026-01-05T10:00:00");
+        sim.set_leads(&task.id, &[60, 10]);
+
+        sim.advance(Duration::hours(1));
+        assert_eq!(sim.shown(), vec!["10 分钟后: 开会", "开会"]);
+    }
+
+    #[test]
+    fn recurring_lead_reminders_are_armed_for_each_occurrence() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let daily = sim.add_daily("站会", "09:00");
+        sim.set_leads(&daily.id, &[15]);
+
+        sim.advance(Duration::days(2));
+        let fired: Vec<(String, String)> = sim
+            .records()
+            .into_iter()
+            .map(|record| (record.trigger_time, record.reminder_type))
+            .collect();
+        assert_eq!(
+            fired,
+            vec![
+                ("2026-01-05T08:45:00".to_string(), "LEAD".to_string()),
+                ("2026-01-05T09:00:00".to_string(), "RECURRING".to_string()),
+                ("2026-01-06T08:45:00".to_string(), "LEAD".to_string()),
+                ("2026-01-06T09:00:00".to_string(), "RECURRING".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn completing_or_rescheduling_a_task_moves_its_lead_reminders() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let moved = sim.add_task("改期", "2026-01-05T10:00:00");
+        sim.set_leads(&moved.id, &[30]);
+        let done = sim.add_task("已完成", "2026-01-05T11:00:00");
+        sim.set_leads(&done.id, &[30]);
+
+        sim.db
+            .update_task(
+                &moved.id,
+                "改期",
+                None,
+                &["2026-01-05T12:00:00".to_string()],
+                None,
+                None,
+            )
+            .unwrap();
+        let moved = sim.db.get_task(&moved.id).unwrap().unwrap();
+        sim.scheduler.schedule_task(moved).unwrap();
+        sim.db.complete_task(&done.id).unwrap();
+        sim.scheduler.cancel_task(&done.id);
+
+        sim.advance(Duration::hours(5));
+        let times: Vec<String> = sim
+            .records()
+            .into_iter()
+            .map(|record| record.trigger_time)
+            .collect();
+        assert_eq!(times, vec!["2026-01-05T11:30:00", "2026-01-05T12:00:00"]);
+    }
+
+    #[test]
+    fn upcoming_and_reconcile_include_lead_reminders() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let task = sim.add_task("交周报", "2026-01-05T10:00:00");
+        sim.set_leads(&task.id, &[60]);
+
+        let upcoming = sim.scheduler.upcoming(5).unwrap();
+        assert_eq!(upcoming.len(), 2);
+        assert_eq!(upcoming[0].source, LEAD_REMINDER_TYPE);
+        assert_eq!(upcoming[0].fire_at, "2026-01-05T09:00:00");
+        assert_eq!(upcoming[0].description, "1 小时后: 交周报");
+
+        // 其他设备改了提前量，合并后只按新的提前量提醒。
+        let mut merged = sim.db.get_task(&task.id).unwrap().unwrap();
+        merged.notify_options.lead_minutes = vec![30];
+        sim.db
+            .update_task_notify_options(&task.id, &merged.notify_options)
+            .unwrap();
+        let summary = sim.scheduler.reconcile().unwrap();
+        assert_eq!(summary.armed, vec![task.id.clone()]);
+        assert_eq!(summary.cancelled, vec![task.id.clone()]);
+        assert_eq!(summary.unchanged, 1);
+
+        sim.advance(Duration::hours(3));
+        assert_eq!(sim.shown(), vec!["30 分钟后: 交周报", "交周报"]);
+    }
+
+    #[test]
+    fn overdue_task_alerts_at_the_due_time_then_daily_until_done() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let task = sim.add_task("交报销单", "2026-01-05T09:00:00");
+        sim.set_due(&task.id, Some("2026-01-05T17:00:00"), true);
+
+        sim.advance(Duration::hours(12));
+        sim.dismiss();
+        sim.advance(Duration::days(2));
+        assert_eq!(
+            sim.shown(),
+            vec![
+                "交报销单",
+                "已到期: 交报销单",
+                "已逾期 1 天: 交报销单",
+                "已逾期 2 天: 交报销单",
+            ]
+        );
+        let due_records: Vec<String> = sim
+            .records()
+            .into_iter()
+            .filter(|record| record.reminder_type == DUE_REMINDER_TYPE)
+            .map(|record| record.trigger_time)
+            .collect();
+        assert_eq!(
+            due_records,
+            vec![
+                "2026-01-05T17:00:00",
+                "2026-01-06T17:00:00",
+                "2026-01-07T17:00:00",
+            ]
+        );
+
+        sim.db.complete_task(&task.id).unwrap();
+        sim.scheduler.cancel_task(&task.id);
+        sim.advance(Duration::days(2));
+        assert_eq!(sim.shown().len(), 4);
+    }
+
+    #[test]
+    fn due_date_without_alert_policy_stays_silent() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let task = sim.add_task("整理文档", "2026-01-05T09:00:00");
+        sim.set_due(&task.id, Some("2026-01-05T12:00:00"), false);
+
+        sim.advance(Duration::days(1));
+        assert_eq!(sim.shown(), vec!["整理文档"]);
+        assert!(sim.db.get_task(&task.id).unwrap().unwrap().overdue);
+    }
+
+    #[test]
+    fn reconcile_after_restart_does_not_repeat_todays_overdue_alert() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let task = sim.add_task("续费域名", "2026-01-05T08:30:00");
+        sim.set_due(&task.id, Some("2026-01-03T10:00:00"), true);
+        sim.advance(Duration::minutes(1));
+        assert_eq!(sim.shown(), vec!["已逾期 1 天: 续费域名"]);
+
+        // 模拟重启：新的调度器从数据库重新安排。
+        let restarted = ReminderScheduler::with_output(
+            sim.db.clone(),
+            sim.output.clone(),
+            sim.snapshot.clone(),
+        );
+        restarted.schedule_existing().unwrap();
+        let upcoming = restarted.upcoming(5).unwrap();
+        assert_eq!(upcoming[0].reminder_type, "TASK");
+        assert_eq!(upcoming[1].reminder_type, DUE_REMINDER_TYPE);
+        assert_eq!(upcoming[1].fire_at, "2026-01-05T10:00:00");
+        assert_eq!(upcoming[1].description, "已逾期 2 天: 续费域名");
+    }
+
+    #[test]
+    fn active_tasks_can_be_sorted_and_filtered_by_due_date() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let late = sim.db.create_task("已逾期", None).unwrap();
+        let today = sim.db.create_task("今天到期", None).unwrap();
+        let later = sim.db.create_task("下周到期", None).unwrap();
+        sim.db.create_task("没有截止时间", None).unwrap();
+        sim.set_due(&later.id, Some("2026-01-12T09:00:00"), false);
+        sim.set_due(&today.id, Some("2026-01-05T18:00:00"), false);
+        sim.set_due(&late.id, Some("2026-01-04T18:00:00"), false);
+
+        let titles = |sort: &str, filter: &str| -> Vec<String> {
+            sim.db
+                .query_active_tasks(sort, filter)
+                .unwrap()
+                .into_iter()
+                .map(|task| task.description)
+                .collect()
+        };
+        assert_eq!(
+            titles(TASK_SORT_DUE, "ALL"),
+            vec!["已逾期", "今天到期", "下周到期", "没有截止时间"]
+        );
+        assert_eq!(titles(TASK_SORT_DUE, TASK_FILTER_OVERDUE), vec!["已逾期"]);
+        assert_eq!(
+            titles(TASK_SORT_DUE, TASK_FILTER_DUE_TODAY),
+            vec!["今天到期"]
+        );
+        assert_eq!(
+            titles(TASK_SORT_DUE, TASK_FILTER_NO_DUE),
+            vec!["没有截止时间"]
+        );
+
+        let overdue: Vec<String> = sim
+            .db
+            .list_active_tasks()
+            .unwrap()
+            .into_iter()
+            .filter(|task| task.overdue)
+            .map(|task| task.description)
+            .collect();
+        assert_eq!(overdue, vec!["已逾期"]);
+    }
+
+    #[test]
+    fn task_with_several_reminder_times_fires_at_each() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let task = sim.add_task("赶飞机", "2026-01-07T10:00:00");
+        sim.set_reminders(
+            &task.id,
+            &[
+                "2026-01-05T10:00:00",
+                "2026-01-06T21:00:00",
+                "2026-01-07T07:00:00",
+            ],
+        );
+        assert_eq!(
+            sim.db.get_task(&task.id).unwrap().unwrap().reminder_time,
+            Some("2026-01-05T10:00:00".to_string())
+        );
+
+        sim.advance(Duration::days(1));
+        assert_eq!(sim.shown(), vec!["赶飞机"]);
+        // 已触发的提醒时间不再安排，下一个提醒时间随之前移。
+        let reminders = sim.db.list_task_reminders(&task.id).unwrap();
+        assert!(reminders[0].fired_at.is_some());
+        assert!(reminders[1..].iter().all(|item| item.fired_at.is_none()));
+        assert_eq!(
+            sim.db.get_task(&task.id).unwrap().unwrap().reminder_time,
+            Some("2026-01-06T21:00:00".to_string())
+        );
+
+        sim.advance(Duration::days(2));
+        let times: Vec<String> = sim
+            .records()
+            .into_iter()
+            .map(|record| record.trigger_time)
+            .collect();
+        assert_eq!(
+            times,
+            vec![
+                "2026-01-05T10:00:00",
+                "2026-01-06T21:00:00",
+                "2026-01-07T07:00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn editing_reminder_times_keeps_fired_state_and_drops_removed_times() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let task = sim.add_task("交房租", "2026-01-05T09:00:00");
+        sim.set_reminders(&task.id, &["2026-01-05T09:00:00", "2026-01-05T12:00:00"]);
+        sim.advance(Duration::hours(2));
+        assert_eq!(sim.shown(), vec!["交房租"]);
+
+        sim.set_reminders(&task.id, &["2026-01-05T09:00:00", "2026-01-05T18:00:00"]);
+        let reminders = sim.db.list_task_reminders(&task.id).unwrap();
+        assert_eq!(reminders.len(), 2);
+        assert!(reminders[0].fired_at.is_some());
+        assert_eq!(
+            sim.scheduler.next_deadline(),
+            Some(at("2026-01-05T18:00:00"))
+        );
+
+        sim.advance(Duration::hours(12));
+        assert_eq!(sim.shown(), vec!["交房租", "交房租"]);
+        assert_eq!(sim.records()[1].trigger_time, "2026-01-05T18:00:00");
+    }
+
+    #[test]
+    fn lead_reminders_follow_the_next_reminder_time() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let task = sim.add_task("赶飞机", "2026-01-05T10:00:00");
+        sim.set_reminders(&task.id, &["2026-01-05T10:00:00", "2026-01-05T20:00:00"]);
+        sim.set_leads(&task.id, &[30]);
+
+        sim.advance(Duration::hours(14));
+        assert_eq!(
+            sim.shown(),
+            vec!["30 分钟后: 赶飞机", "赶飞机", "30 分钟后: 赶飞机", "赶飞机"]
+        );
+        let summary = sim.scheduler.reconcile().unwrap();
+        assert!(summary.armed.is_empty());
+    }
+
+    #[test]
+    fn global_pause_suppresses_firings_and_catches_up_once_on_resume() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let daily = sim.add_daily("喝水", "09:00");
+        let held = sim.add_daily("单独暂停", "10:00");
+        let mut held = sim.db.get_recurring_task(&held.id).unwrap().unwrap();
+        held.is_paused = true;
+        sim.db.update_recurring_task(&held).unwrap();
+        sim.scheduler.schedule_recurring(held.clone()).unwrap();
+        let task = sim.add_task("交房租", "2026-01-06T12:00:00");
+        sim.set_reminders(&task.id, &["2026-01-06T12:00:00", "2026-01-06T18:00:00"]);
+
+        sim.scheduler.pause_all(None).unwrap();
+        assert!(sim.scheduler.upcoming(5).unwrap().is_empty());
+        sim.advance(Duration::days(3));
+        assert!(sim.shown().is_empty());
+
+        sim.scheduler.resume_all().unwrap();
+        sim.scheduler.tick();
+        let mut shown = sim.shown();
+        shown.sort();
+        assert_eq!(shown, vec!["交房租", "喝水"]);
+        let records = sim.records();
+        assert!(records
+            .iter()
+            .any(|record| record.reminder_id == task.id
+                && record.trigger_time == "2026-01-08T08:00:00"));
+        let daily = sim.db.get_recurring_task(&daily.id).unwrap().unwrap();
+        assert_eq!(daily.next_trigger, "2026-01-08T09:00:00");
+        // 单独暂停的循环任务恢复后仍保持暂停。
+        assert!(
+            sim.db
+                .get_recurring_task(&held.id)
+                .unwrap()
+                .unwrap()
+                .is_paused
+        );
+        assert!(!sim.db.load_pause_state().unwrap().paused);
+    }
+
+    #[test]
+    fn global_pause_resumes_at_the_set_time_and_can_skip_missed_reminders() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let mut settings = sim.db.load_settings().unwrap();
+        settings.missed_policy = MISSED_POLICY_SKIP.to_string();
+        sim.db.save_settings(&settings).unwrap();
+        let daily = sim.add_daily("喝水", "09:00");
+        sim.add_task("交房租", "2026-01-06T12:00:00");
+
+        assert!(sim
+            .scheduler
+            .pause_all(Some(at("2026-01-05T07:00:00")))
+            .is_err());
+        let pause = sim
+            .scheduler
+            .pause_all(Some(at("2026-01-07T08:00:00")))
+            .unwrap();
+        assert_eq!(pause.resume_at.as_deref(), Some("2026-01-07T08:00:00"));
+
+        sim.advance(Duration::days(2) + Duration::minutes(30));
+        assert!(sim.shown().is_empty());
+        assert!(!sim.db.load_pause_state().unwrap().paused);
+        let daily = sim.db.get_recurring_task(&daily.id).unwrap().unwrap();
+        assert_eq!(daily.next_trigger, "2026-01-07T09:00:00");
+
+        sim.advance(Duration::hours(1));
+        assert_eq!(sim.shown(), vec!["喝水"]);
+    }
+
+    #[test]
+    fn global_pause_holds_timers_and_handlers_and_drops_reminder_jobs() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        sim.add_daily("喝水", "09:00");
+        let task = sim.add_task("交房租", "2026-01-05T10:00:00");
+        sim.set_leads(&task.id, &[30]);
+        sim.set_due(&task.id, Some("2026-01-05T11:00:00"), true);
+        sim.scheduler.start_timer(Some("泡茶"), 30 * 60).unwrap();
+        let phases = Arc::new(Mutex::new(Vec::new()));
+        let handled = phases.clone();
+        sim.scheduler.register_handler(
+            "phase",
+            Arc::new(move |id| {
+                handled.lock().unwrap().push(id.to_string());
+                Ok(())
+            }),
+        );
+        sim.scheduler.schedule_job(
+            JobKey::new(JobKind::Handler("phase"), "work"),
+            at("2026-01-05T08:45:00"),
+        );
+
+        sim.scheduler
+            .pause_all(Some(at("2026-01-05T12:00:00")))
+            .unwrap();
+        sim.advance(Duration::hours(3) + Duration::minutes(59));
+        assert!(sim.shown().is_empty());
+        assert!(phases.lock().unwrap().is_empty());
+
+        // 恢复时：计时器与专注阶段补上执行；提醒、循环与逾期按恢复规则重新安排，提前提醒不再补发。
+        sim.advance(Duration::minutes(1));
+        assert_eq!(*phases.lock().unwrap(), vec!["work"]);
+        let mut shown = sim.shown();
+        shown.sort();
+        assert_eq!(shown, vec!["交房租", "喝水", "已到期: 交房租", "泡茶"]);
+    }
+
+    #[test]
+    fn pause_merged_from_another_device_cancels_scheduled_reminders() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        sim.add_daily("喝水", "09:00");
+        sim.add_task("交房租", "2026-01-05T12:00:00");
+
+        // 模拟同步合并：另一台设备开启了全局暂停。
+        sim.db
+            .save_pause_state(Some("2026-01-05T07:30:00"), Some("2026-01-06T08:00:00"))
+            .unwrap();
+        sim.scheduler.reconcile().unwrap();
+        assert!(sim.scheduler.upcoming(5).unwrap().is_empty());
+        assert_eq!(
+            sim.scheduler.next_deadline(),
+            Some(at("2026-01-06T08:00:00"))
+        );
+
+        sim.advance(Duration::hours(12));
+        assert!(sim.shown().is_empty());
+    }
+}
@@ -0,0 +1,472 @@
+use std::f64::consts::PI;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::{NaiveDateTime, NaiveTime};
+use serde::Serialize;
+
+use crate::db::DbManager;
+use crate::errors::AppError;
+use crate::models::AppSettings;
+
+pub const SOUND_NONE: &str = "none";
+pub const DEFAULT_SOUND: &str = "builtin:chime";
+const BUILTIN_PREFIX: &str = "builtin:";
+const FILE_PREFIX: &str = "file:";
+const SAMPLE_RATE: u32 = 22050;
+const LOOP_GAP_MS: u64 = 1200;
+/// 与提醒弹窗的自动关闭时间保持一致，避免无人处理时一直响下去。
+const MAX_LOOP_DURATION_SECS: u64 = 15 * 60;
+const USER_SOUND_EXTENSIONS: [&str; 5] = ["wav", "mp3", "ogg", "oga", "flac"];
+const BUILTIN_SOUNDS: [(&str, &str); 4] = [
+    ("chime", "清脆"),
+    ("bell", "铃声"),
+    ("beep", "蜂鸣"),
+    ("soft", "柔和"),
+];
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoundInfo {
+    pub id: String,
+    pub label: String,
+    pub builtin: bool,
+}
+
+struct Playback {
+    record_id: String,
+    stop: Arc<AtomicBool>,
+}
+
+#[derive(Clone)]
+pub struct SoundService {
+    db: DbManager,
+    sounds_dir: PathBuf,
+    current: Arc<Mutex<Option<Playback>>>,
+}
+
+impl SoundService {
+    pub fn new(db: DbManager, data_dir: &Path) -> Self {
+        Self {
+            db,
+            sounds_dir: data_dir.join("sounds"),
+            current: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn list_sounds(&self) -> Vec<SoundInfo> {
+        let mut sounds: Vec<SoundInfo> = BUILTIN_SOUNDS
+            .iter()
+            .map(|(name, label)| SoundInfo {
+                id: format!("{}{}", BUILTIN_PREFIX, name),
+                label: label.to_string(),
+                builtin: true,
+            })
+            .collect();
+        let mut files: Vec<SoundInfo> = fs::read_dir(&self.sounds_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_file() && is_user_sound(path))
+                    .filter_map(|path| {
+                        let name = path.file_name()?.to_str()?.to_string();
+                        Some(SoundInfo {
+                            id: format!("{}{}", FILE_PREFIX, name),
+                            label: name,
+                            builtin: false,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        files.sort_by(|a, b| a.label.cmp(&b.label));
+        sounds.extend(files);
+        sounds
+    }
+
+    pub fn sounds_dir(&self) -> Result<PathBuf, AppError> {
+        fs::create_dir_all(&self.sounds_dir)?;
+        Ok(self.sounds_dir.clone())
+    }
+
+    /// 为提醒开始播放；`sound` 为任务单独选择的声音，为空时使用全局默认。
+    pub fn play_for_reminder(&self, record_id: &str, sound: Option<&str>) -> Result<(), AppError> {
+        let settings = self.db.load_settings()?;
+        self.stop_all();
+        if !settings.sound_enabled || in_quiet_hours_at(&settings, self.db.clock().now()) {
+            return Ok(());
+        }
+        let sound_id = sound
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or(settings.sound_default.as_str());
+        if sound_id == SOUND_NONE {
+            return Ok(());
+        }
+        // 自定义声音可能只存在于其他设备（任务配置会同步），找不到时退回默认声音。
+        let path = match self.resolve(sound_id) {
+            Ok(path) => path,
+            Err(_) => self.resolve(DEFAULT_SOUND)?,
+        };
+        self.start(record_id, path, settings, true);
+        Ok(())
+    }
+
+    pub fn preview(&self, sound_id: &str) -> Result<(), AppError> {
+        let mut settings = self.db.load_settings()?;
+        self.stop_all();
+        if sound_id == SOUND_NONE {
+            return Ok(());
+        }
+        let path = self.resolve(sound_id)?;
+        settings.sound_volume_start = settings.sound_volume_max;
+        self.start("preview", path, settings, false);
+        Ok(())
+    }
+
+    pub fn stop(&self, record_id: &str) {
+        let mut current = self.current.lock().unwrap();
+        if current
+            .as_ref()
+            .map(|playback| playback.record_id == record_id)
+            .unwrap_or(false)
+        {
+            if let Some(playback) = current.take() {
+                playback.stop.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+
+    pub fn stop_all(&self) {
+        if let Some(playback) = self.current.lock().unwrap().take() {
+            playback.stop.store(true, Ordering::SeqCst);
+        }
+    }
+
+    fn start(&self, record_id: &str, path: PathBuf, settings: AppSettings, looping: bool) {
+        let stop = Arc::new(AtomicBool::new(false));
+        *self.current.lock().unwrap() = Some(Playback {
+            record_id: record_id.to_string(),
+            stop: stop.clone(),
+        });
+        let looping = looping && settings.sound_loop;
+        thread::spawn(move || {
+            let started = Instant::now();
+            loop {
+                let volume = escalated_volume(
+                    started.elapsed().as_secs_f64(),
+                    settings.sound_volume_start,
+                    settings.sound_volume_max,
+                    settings.sound_escalate_seconds as f64,
+                );
+                match spawn_player(&path, volume) {
+                    Ok(child) => wait_or_kill(child, &stop),
+                    Err(err) => {
+                        eprintln!("[sound] 播放失败: {}", err);
+                        break;
+                    }
+                }
+                if !looping
+                    || stop.load(Ordering::SeqCst)
+                    || started.elapsed().as_secs() >= MAX_LOOP_DURATION_SECS
+                {
+                    break;
+                }
+                thread::sleep(Duration::from_millis(LOOP_GAP_MS));
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+            }
+        });
+    }
+
+    fn resolve(&self, sound_id: &str) -> Result<PathBuf, AppError> {
+        if let Some(name) = sound_id.strip_prefix(BUILTIN_PREFIX) {
+            if !BUILTIN_SOUNDS.iter().any(|(builtin, _)| *builtin == name) {
+                return Err(AppError::Invalid(format!("未知的内置声音: {}", name)));
+            }
+            let dir = self.sounds_dir.join("builtin");
+            fs::create_dir_all(&dir)?;
+            let path = dir.join(format!("{}.wav", name));
+            if !path.exists() {
+                fs::write(&path, builtin_wav(name))?;
+            }
+            return Ok(path);
+        }
+        if let Some(name) = sound_id.strip_prefix(FILE_PREFIX) {
+            // 只允许播放声音目录下的文件，防止通过同步数据指向任意路径。
+            let file_name = Path::new(name)
+                .file_name()
+                .ok_or_else(|| AppError::Invalid("声音文件名不正确".to_string()))?;
+            let path = self.sounds_dir.join(file_name);
+            if !path.is_file() || !is_user_sound(&path) {
+                return Err(AppError::Invalid(format!("找不到声音文件: {}", name)));
+            }
+            return Ok(path);
+        }
+        Err(AppError::Invalid(format!("不支持的声音: {}", sound_id)))
+    }
+}
+
+pub fn normalize_sound_id(sound_id: &str) -> Option<String> {
+    let value = sound_id.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if value == SOUND_NONE
+        || value
+            .strip_prefix(BUILTIN_PREFIX)
+            .map(|name| BUILTIN_SOUNDS.iter().any(|(builtin, _)| *builtin == name))
+            .unwrap_or(false)
+        || value
+            .strip_prefix(FILE_PREFIX)
+            .map(|name| !name.is_empty() && !name.contains(['/', '\\']))
+            .unwrap_or(false)
+    {
+        return Some(value.to_string());
+    }
+    None
+}
+
+/// 从起始音量线性升到最大音量，`ramp_secs` 为 0 时直接使用最大音量。
+pub fn escalated_volume(elapsed_secs: f64, start: f64, max: f64, ramp_secs: f64) -> f64 {
+    let max = max.clamp(0.0, 1.0);
+    let start = start.clamp(0.0, max);
+    if ramp_secs <= 0.0 {
+        return max;
+    }
+    let progress = (elapsed_secs / ramp_secs).clamp(0.0, 1.0);
+    start + (max - start) * progress
+}
+
+/// 免打扰时段，支持跨午夜（例如 22:00 - 07:00）。
+pub fn in_quiet_hours(now: NaiveTime, start: NaiveTime, end: NaiveTime) -> bool {
+    if start == end {
+        return false;
+    }
+    if start < end {
+        now >= start && now < end
+    } else {
+        now >= start || now < end
+    }
+}
+
+pub fn in_quiet_hours_at(settings: &AppSettings, now: NaiveDateTime) -> bool {
+    if !settings.quiet_hours_enabled {
+        return false;
+    }
+    let (Ok(start), Ok(end)) = (
+        NaiveTime::parse_from_str(&settings.quiet_hours_start, "%H:%M"),
+        NaiveTime::parse_from_str(&settings.quiet_hours_end, "%H:%M"),
+    ) else {
+        return false;
+    };
+    in_quiet_hours(now.time(), start, end)
+}
+
+fn is_user_sound(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| USER_SOUND_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+fn wait_or_kill(mut child: Child, stop: &AtomicBool) {
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) | Err(_) => return,
+            Ok(None) => {}
+        }
+        if stop.load(Ordering::SeqCst) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+}
+
+#[cfg(windows)]
+fn spawn_player(path: &Path, volume: f64) -> std::io::Result<Child> {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    let script = format!(
+        "Add-Type -AssemblyName PresentationCore; \
+         $p = New-Object System.Windows.Media.MediaPlayer; \
+         $p.Open([Uri]'{}'); $p.Volume = {:.2}; $p.Play(); \
+         $n = 0; while (-not $p.NaturalDuration.HasTimeSpan -and $n -lt 50) {{ Start-Sleep -Milliseconds 100; $n++ }}; \
+         if ($p.NaturalDuration.HasTimeSpan) {{ Start-Sleep -Milliseconds ([int]$p.NaturalDuration.TimeSpan.TotalMilliseconds) }}; \
+         $p.Close()",
+        path.display().to_string().replace('\'', "''"),
+        volume
+    );
+    Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .spawn()
+}
+
+#[cfg(target_os = "macos")]
+fn spawn_player(path: &Path, volume: f64) -> std::io::Result<Child> {
+    Command::new("afplay")
+        .arg("-v")
+        .arg(format!("{:.2}", volume))
+        .arg(path)
+        .spawn()
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn spawn_player(path: &Path, volume: f64) -> std::io::Result<Child> {
+    // 依次尝试 PulseAudio、PipeWire 与 ALSA 的命令行播放器；aplay 不支持音量参数。
+    let candidates: [(&str, Vec<String>); 3] = [
+        (
+            "paplay",
+            vec![format!("--volume={}", (volume * 65536.0).round() as u32)],
+        ),
+        ("pw-play", vec![format!("--volume={:.2}", volume)]),
+        ("aplay", vec!["-q".to_string()]),
+    ];
+    let mut last_error = None;
+    for (program, args) in candidates {
+        match Command::new(program).args(&args).arg(path).spawn() {
+            Ok(child) => return Ok(child),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => last_error = Some(err),
+            Err(err) => return Err(err),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| std::io::Error::other("没有可用的音频播放器")))
+}
+
+fn builtin_wav(name: &str) -> Vec<u8> {
+    let samples = match name {
+        "bell" => synthesize(1.4, |t| {
+            let envelope = (-3.0 * t).exp();
+            envelope
+                * (0.6 * (2.0 * PI * 660.0 * t).sin()
+                    + 0.3 * (2.0 * PI * 1320.0 * t).sin()
+                    + 0.1 * (2.0 * PI * 1980.0 * t).sin())
+        }),
+        "beep" => synthesize(0.9, |t| {
+            let phase = t % 0.3;
+            if phase < 0.15 {
+                0.5 * (2.0 * PI * 1000.0 * t).sin()
+            } else {
+                0.0
+            }
+        }),
+        "soft" => synthesize(1.2, |t| {
+            let envelope = (PI * t / 1.2).sin();
+            0.5 * envelope * (2.0 * PI * 440.0 * t).sin()
+        }),
+        _ => synthesize(0.9, |t| {
+            let (freq, local) = if t < 0.3 {
+                (880.0, t)
+            } else {
+                (1320.0, t - 0.3)
+            };
+            0.6 * (-5.0 * local).exp() * (2.0 * PI * freq * t).sin()
+        }),
+    };
+    encode_wav(&samples)
+}
+
+fn synthesize(duration_secs: f64, wave: impl Fn(f64) -> f64) -> Vec<i16> {
+    let total = (duration_secs * SAMPLE_RATE as f64) as usize;
+    (0..total)
+        .map(|index| {
+            let t = index as f64 / SAMPLE_RATE as f64;
+            (wave(t).clamp(-1.0, 1.0) * i16::MAX as f64) as i16
+        })
+        .collect()
+}
+
+fn encode_wav(samples: &[i16]) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVE");
+    bytes.extend_from_slice(b"fmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    bytes.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(value: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(value, "%H:%M").unwrap()
+    }
+
+    #[test]
+    fn volume_ramps_from_start_to_max() {
+        assert_eq!(escalated_volume(0.0, 0.2, 1.0, 60.0), 0.2);
+        assert!((escalated_volume(30.0, 0.2, 1.0, 60.0) - 0.6).abs() < 1e-9);
+        assert_eq!(escalated_volume(120.0, 0.2, 1.0, 60.0), 1.0);
+        assert_eq!(escalated_volume(0.0, 0.2, 0.8, 0.0), 0.8);
+        // 起始音量高于最大音量时按最大音量，超出范围的值被截断。
+        assert_eq!(escalated_volume(0.0, 0.9, 0.5, 60.0), 0.5);
+        assert_eq!(escalated_volume(0.0, -1.0, 2.0, 60.0), 0.0);
+    }
+
+    #[test]
+    fn quiet_hours_within_the_same_day() {
+        let (start, end) = (time("12:00"), time("14:00"));
+        assert!(!in_quiet_hours(time("11:59"), start, end));
+        assert!(in_quiet_hours(time("12:00"), start, end));
+        assert!(in_quiet_hours(time("13:30"), start, end));
+        assert!(!in_quiet_hours(time("14:00"), start, end));
+    }
+
+    #[test]
+    fn quiet_hours_crossing_midnight() {
+        let (start, end) = (time("22:00"), time("07:00"));
+        assert!(!in_quiet_hours(time("21:59"), start, end));
+        assert!(in_quiet_hours(time("22:00"), start, end));
+        assert!(in_quiet_hours(time("00:00"), start, end));
+        assert!(in_quiet_hours(time("06:59"), start, end));
+        assert!(!in_quiet_hours(time("07:00"), start, end));
+        assert!(!in_quiet_hours(time("12:00"), start, end));
+    }
+
+    #[test]
+    fn quiet_hours_with_equal_start_and_end_is_off() {
+        let at = time("08:00");
+        assert!(!in_quiet_hours(time("08:00"), at, at));
+        assert!(!in_quiet_hours(time("20:00"), at, at));
+    }
+
+    #[test]
+    fn sound_ids_are_normalized() {
+        assert_eq!(normalize_sound_id(" none "), Some("none".to_string()));
+        assert_eq!(
+            normalize_sound_id("builtin:bell"),
+            Some("builtin:bell".to_string())
+        );
+        assert_eq!(normalize_sound_id("builtin:unknown"), None);
+        assert_eq!(
+            normalize_sound_id("file:alarm.mp3"),
+            Some("file:alarm.mp3".to_string())
+        );
+        assert_eq!(normalize_sound_id("file:../alarm.mp3"), None);
+        assert_eq!(normalize_sound_id(""), None);
+    }
+}
@@ -0,0 +1,345 @@
+use std::process::{Child, Command};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::db::DbManager;
+use crate::errors::AppError;
+use crate::models::{AppSettings, NotifyOptions, ReminderRecord};
+use crate::sound::in_quiet_hours_at;
+
+pub const SPEECH_ENGINE_AUTO: &str = "AUTO";
+/// 等待提示音的第一遍播完再朗读，避免两者叠在一起听不清。
+const SPEAK_DELAY_MS: u64 = 1500;
+const PREVIEW_ID: &str = "preview";
+const MIN_RATE: f64 = 0.5;
+const MAX_RATE: f64 = 2.0;
+
+/// 本地语音引擎的抽象，`rate` 为相对正常语速的倍数（1.0 为正常）。
+pub trait SpeechEngine: Send + Sync {
+    fn name(&self) -> &'static str;
+    /// 朗读的文本作为单独的参数传给引擎，不经过 shell 解析。
+    fn command(&self, text: &str, voice: Option<&str>, rate: f64) -> Command;
+
+    fn speak(&self, text: &str, voice: Option<&str>, rate: f64) -> std::io::Result<Child> {
+        self.command(text, voice, rate).spawn()
+    }
+}
+
+struct EspeakEngine {
+    program: &'static str,
+}
+
+impl SpeechEngine for EspeakEngine {
+    fn name(&self) -> &'static str {
+        "ESPEAK"
+    }
+
+    fn command(&self, text: &str, voice: Option<&str>, rate: f64) -> Command {
+        let mut command = Command::new(self.program);
+        command
+            .arg("-s")
+            .arg(((175.0 * rate).round() as i64).to_string());
+        if let Some(voice) = voice {
+            command.arg("-v").arg(voice);
+        }
+        command.arg("--").arg(text);
+        command
+    }
+}
+
+struct SpeechDispatcherEngine;
+
+impl SpeechEngine for SpeechDispatcherEngine {
+    fn name(&self) -> &'static str {
+        "SPD"
+    }
+
+    fn command(&self, text: &str, voice: Option<&str>, rate: f64) -> Command {
+        // spd-say 的语速范围是 -100..100，0 为正常。
+        let spd_rate = ((rate - 1.0) * 100.0).round().clamp(-100.0, 100.0) as i64;
+        let mut command = Command::new("spd-say");
+        command.arg("-w").arg("-r").arg(spd_rate.to_string());
+        if let Some(voice) = voice {
+            command.arg("-y").arg(voice);
+        }
+        command.arg("--").arg(text);
+        command
+    }
+}
+
+struct MacSayEngine;
+
+impl SpeechEngine for MacSayEngine {
+    fn name(&self) -> &'static str {
+        "SAY"
+    }
+
+    fn command(&self, text: &str, voice: Option<&str>, rate: f64) -> Command {
+        let mut command = Command::new("say");
+        command
+            .arg("-r")
+            .arg(((180.0 * rate).round() as i64).to_string());
+        if let Some(voice) = voice {
+            command.arg("-v").arg(voice);
+        }
+        command.arg("--").arg(text);
+        command
+    }
+}
+
+struct WindowsSapiEngine;
+
+impl SpeechEngine for WindowsSapiEngine {
+    fn name(&self) -> &'static str {
+        "SAPI"
+    }
+
+    fn command(&self, text: &str, voice: Option<&str>, rate: f64) -> Command {
+        // SAPI 的语速范围是 -10..10，0 为正常。
+        let sapi_rate = ((rate - 1.0) * 10.0).round().clamp(-10.0, 10.0) as i64;
+        let select_voice = voice
+            .map(|voice| format!("$s.SelectVoice('{}'); ", voice.replace('\'', "''")))
+            .unwrap_or_default();
+        let script = format!(
+            "Add-Type -AssemblyName System.Speech; \
+             $s = New-Object System.Speech.Synthesis.SpeechSynthesizer; \
+             {}$s.Rate = {}; $s.Speak('{}')",
+            select_voice,
+            sapi_rate,
+            text.replace('\'', "''")
+        );
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-NonInteractive", "-Command", &script]);
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+            command.creation_flags(CREATE_NO_WINDOW);
+        }
+        command
+    }
+}
+
+fn platform_engines() -> Vec<Box<dyn SpeechEngine>> {
+    if cfg!(windows) {
+        vec![Box::new(WindowsSapiEngine)]
+    } else if cfg!(target_os = "macos") {
+        vec![Box::new(MacSayEngine)]
+    } else {
+        vec![
+            Box::new(SpeechDispatcherEngine),
+            Box::new(EspeakEngine {
+                program: "espeak-ng",
+            }),
+            Box::new(EspeakEngine { program: "espeak" }),
+        ]
+    }
+}
+
+struct Utterance {
+    record_id: String,
+    child: Child,
+}
+
+#[derive(Clone)]
+pub struct SpeechService {
+    db: DbManager,
+    current: Arc<Mutex<Option<Utterance>>>,
+}
+
+impl SpeechService {
+    pub fn new(db: DbManager) -> Self {
+        Self {
+            db,
+            current: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn speak_for_reminder(&self, record: &ReminderRecord, options: &NotifyOptions) {
+        if !options.tts {
+            return;
+        }
+        let service = self.clone();
+        let record_id = record.id.clone();
+        let text = format!("任务提醒：{}", record.description.trim());
+        let voice = options.tts_voice.clone();
+        let rate = options.tts_rate;
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(SPEAK_DELAY_MS));
+            let result = service.db.load_settings().and_then(|settings| {
+                if in_quiet_hours_at(&settings, service.db.clock().now()) {
+                    return Ok(());
+                }
+                service.speak(&settings, &record_id, &text, voice.as_deref(), rate)
+            });
+            if let Err(err) = result {
+                eprintln!("[speech] {}", err);
+            }
+        });
+    }
+
+    /// 试听时可传入尚未保存的引擎设置。
+    pub fn preview(
+        &self,
+        engine: Option<&str>,
+        voice: Option<&str>,
+        rate: Option<f64>,
+    ) -> Result<(), AppError> {
+        let mut settings = self.db.load_settings()?;
+        if let Some(engine) = engine {
+            settings.tts_engine = normalize_speech_engine(engine);
+        }
+        self.speak(
+            &settings,
+            PREVIEW_ID,
+            "这是一条任务提醒的朗读试听",
+            voice,
+            rate,
+        )
+    }
+
+    pub fn stop_preview(&self) {
+        self.stop(PREVIEW_ID);
+    }
+
+    pub fn stop(&self, record_id: &str) {
+        let mut current = self.current.lock().unwrap();
+        if current
+            .as_ref()
+            .map(|utterance| utterance.record_id == record_id)
+            .unwrap_or(false)
+        {
+            if let Some(mut utterance) = current.take() {
+                let _ = utterance.child.kill();
+                let _ = utterance.child.wait();
+            }
+        }
+    }
+
+    fn speak(
+        &self,
+        settings: &AppSettings,
+        record_id: &str,
+        text: &str,
+        voice: Option<&str>,
+        rate: Option<f64>,
+    ) -> Result<(), AppError> {
+        let voice = voice
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .or(Some(settings.tts_voice.trim()).filter(|value| !value.is_empty()));
+        let rate = normalize_speech_rate(rate.unwrap_or(settings.tts_rate));
+
+        let mut last_error = None;
+        for engine in platform_engines() {
+            if settings.tts_engine != SPEECH_ENGINE_AUTO && settings.tts_engine != engine.name() {
+                continue;
+            }
+            match engine.speak(text, voice, rate) {
+                Ok(child) => {
+                    let previous = self.current.lock().unwrap().replace(Utterance {
+                        record_id: record_id.to_string(),
+                        child,
+                    });
+                    if let Some(mut previous) = previous {
+                        let _ = previous.child.kill();
+                        let _ = previous.child.wait();
+                    }
+                    return Ok(());
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    last_error = Some(format!("找不到语音引擎 {}", engine.name()));
+                }
+                Err(err) => return Err(AppError::System(format!("朗读失败: {}", err))),
+            }
+        }
+        Err(AppError::System(last_error.unwrap_or_else(|| {
+            "当前系统没有可用的语音引擎".to_string()
+        })))
+    }
+}
+
+pub fn normalize_speech_engine(engine: &str) -> String {
+    match engine.trim().to_uppercase().as_str() {
+        "ESPEAK" => "ESPEAK".to_string(),
+        "SPD" => "SPD".to_string(),
+        "SAY" => "SAY".to_string(),
+        "SAPI" => "SAPI".to_string(),
+        _ => SPEECH_ENGINE_AUTO.to_string(),
+    }
+}
+
+pub fn normalize_speech_rate(rate: f64) -> f64 {
+    if rate.is_finite() {
+        rate.clamp(MIN_RATE, MAX_RATE)
+    } else {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "任务提醒：it's \"done\"; rm -rf ~ && echo $HOME `id` | -v";
+
+    fn program_and_args(command: Command) -> (String, Vec<String>) {
+        let program = command.get_program().to_string_lossy().to_string();
+        let args = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+        (program, args)
+    }
+
+    #[test]
+    fn espeak_passes_text_after_the_option_terminator() {
+        let engine = EspeakEngine {
+            program: "espeak-ng",
+        };
+        let (program, args) = program_and_args(engine.command(TEXT, Some("zh"), 1.2));
+        assert_eq!(program, "espeak-ng");
+        assert_eq!(args, vec!["-s", "210", "-v", "zh", "--", TEXT]);
+    }
+
+    #[test]
+    fn speech_dispatcher_maps_rate_to_its_range() {
+        let (program, args) = program_and_args(SpeechDispatcherEngine.command(TEXT, None, 0.5));
+        assert_eq!(program, "spd-say");
+        assert_eq!(args, vec!["-w", "-r", "-50", "--", TEXT]);
+        let (_, args) = program_and_args(SpeechDispatcherEngine.command(TEXT, None, 3.0));
+        assert_eq!(args[2], "100");
+    }
+
+    #[test]
+    fn mac_say_passes_voice_and_text_as_arguments() {
+        let (program, args) = program_and_args(MacSayEngine.command(TEXT, Some("Ting-Ting"), 1.0));
+        assert_eq!(program, "say");
+        assert_eq!(args, vec!["-r", "180", "-v", "Ting-Ting", "--", TEXT]);
+    }
+
+    #[test]
+    fn sapi_escapes_single_quotes_in_the_script() {
+        let (program, args) = program_and_args(WindowsSapiEngine.command(
+            "it's; $x",
+            Some("Microsoft Huihui's"),
+            2.0,
+        ));
+        assert_eq!(program, "powershell");
+        assert_eq!(&args[..3], ["-NoProfile", "-NonInteractive", "-Command"]);
+        let script = &args[3];
+        assert!(script.contains("$s.SelectVoice('Microsoft Huihui''s'); "));
+        assert!(script.contains("$s.Rate = 10; "));
+        assert!(script.ends_with("$s.Speak('it''s; $x')"));
+    }
+
+    #[test]
+    fn engine_and_rate_are_normalized() {
+        assert_eq!(normalize_speech_engine(" say "), "SAY");
+        assert_eq!(normalize_speech_engine("unknown"), SPEECH_ENGINE_AUTO);
+        assert_eq!(normalize_speech_rate(5.0), MAX_RATE);
+        assert_eq!(normalize_speech_rate(0.1), MIN_RATE);
+        assert_eq!(normalize_speech_rate(f64::NAN), 1.0);
+    }
+}
@@ -1,15 +1,29 @@
 use std::sync::{Arc, Mutex};
 
+use crate::activity::ActivityMonitor;
 use crate::db::DbManager;
+use crate::dispatch::ReminderDispatcher;
+use crate::focus::FocusService;
 use crate::models::{NotificationPayload, UiStatePayload};
+use crate::mqtt::MqttService;
 use crate::scheduler::ReminderScheduler;
+use crate::sound::SoundService;
+use crate::speech::SpeechService;
 use crate::sync::CloudSyncService;
+use crate::webhook::WebhookService;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: DbManager,
     pub scheduler: ReminderScheduler,
     pub sync: CloudSyncService,
+    pub dispatcher: ReminderDispatcher,
+    pub webhooks: WebhookService,
+    pub mqtt: MqttService,
+    pub sound: SoundService,
+    pub speech: SpeechService,
+    pub focus: FocusService,
+    pub activity: ActivityMonitor,
     pub notification_snapshot: Arc<Mutex<Option<NotificationPayload>>>,
     pub ui_state: Arc<Mutex<Option<UiStatePayload>>>,
 }
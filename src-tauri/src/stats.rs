@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime, Timelike};
+use serde::Serialize;
+
+use crate::errors::AppError;
+use crate::models::ReminderRecord;
+
+const DEFAULT_RANGE_DAYS: i64 = 30;
+const BUSIEST_HOURS_LIMIT: usize = 3;
+
+#[derive(Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ReminderStatsSummary {
+    pub total: i64,
+    pub acknowledged: i64,
+    pub snoozed: i64,
+    pub missed: i64,
+    pub pending: i64,
+    /// 已处理提醒（确认、稍后、错过）中被确认的比例，没有已处理提醒时为空。
+    pub ack_rate: Option<f64>,
+    /// 从弹出到用户操作（确认或稍后）的中位耗时（秒）。
+    pub median_response_seconds: Option<f64>,
+    /// 按触发小时统计的次数，下标即小时。
+    pub hour_counts: Vec<i64>,
+    pub busiest_hours: Vec<u32>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskReminderStats {
+    pub reminder_id: String,
+    #[serde(rename = "type")]
+    pub reminder_type: String,
+    pub description: String,
+    #[serde(flatten)]
+    pub summary: ReminderStatsSummary,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReminderStats {
+    pub from: String,
+    pub to: String,
+    pub overall: ReminderStatsSummary,
+    pub tasks: Vec<TaskReminderStats>,
+}
+
+/// 解析统计的日期区间（含首尾两天），未指定时默认截至 today 的最近 30 天。
+pub fn resolve_stats_range(
+    from: Option<&str>,
+    to: Option<&str>,
+    today: NaiveDate,
+) -> Result<(NaiveDate, NaiveDate), AppError> {
+    let parse = |value: &str| {
+        NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+            .map_err(|_| AppError::Invalid(format!("日期格式不正确: {}", value)))
+    };
+    let to = match to.filter(|value| !value.trim().is_empty()) {
+        Some(value) => parse(value)?,
+        None => today,
+    };
+    let from = match from.filter(|value| !value.trim().is_empty()) {
+        Some(value) => parse(value)?,
+        None => to - Duration::days(DEFAULT_RANGE_DAYS - 1),
+    };
+    if from > to {
+        return Err(AppError::Invalid("开始日期不能晚于结束日期".to_string()));
+    }
+    Ok((from, to))
+}
+
+pub fn compute_reminder_stats(
+    from: NaiveDate,
+    to: NaiveDate,
+    records: &[ReminderRecord],
+) -> ReminderStats {
+    // records 按触发时间升序，同一提醒取最后一次的描述，任务改名后统计里显示新名字。
+    let mut groups: Vec<(&ReminderRecord, Vec<&ReminderRecord>)> = Vec::new();
+    let mut index: HashMap<(&str, &str), usize> = HashMap::new();
+    for record in records {
+        let key = (record.reminder_type.as_str(), record.reminder_id.as_str());
+        match index.get(&key) {
+            Some(position) => {
+                let group = &mut groups[*position];
+                group.0 = record;
+                group.1.push(record);
+            }
+            None => {
+                index.insert(key, groups.len());
+                groups.push((record, vec![record]));
+            }
+        }
+    }
+
+    let mut tasks: Vec<TaskReminderStats> = groups
+        .into_iter()
+        .map(|(latest, records)| TaskReminderStats {
+            reminder_id: latest.reminder_id.clone(),
+            reminder_type: latest.reminder_type.clone(),
+            description: latest.description.clone(),
+            summary: summarize(records.into_iter()),
+        })
+        .collect();
+    // 最常被忽略的提醒排在前面：确认率低的优先，其次按错过次数。
+    tasks.sort_by(|a, b| {
+        let rate = |stats: &TaskReminderStats| stats.summary.ack_rate.unwrap_or(1.0);
+        rate(a)
+            .total_cmp(&rate(b))
+            .then(b.summary.missed.cmp(&a.summary.missed))
+            .then(b.summary.total.cmp(&a.summary.total))
+    });
+
+    ReminderStats {
+        from: from.format("%Y-%m-%d").to_string(),
+        to: to.format("%Y-%m-%d").to_string(),
+        overall: summarize(records.iter()),
+        tasks,
+    }
+}
+
+fn summarize<'a>(records: impl Iterator<Item = &'a ReminderRecord>) -> ReminderStatsSummary {
+    let mut summary = ReminderStatsSummary {
+        hour_counts: vec![0; 24],
+        ..Default::default()
+    };
+    let mut response_seconds = Vec::new();
+    for record in records {
+        summary.total += 1;
+        let trigger = parse_time(&record.trigger_time);
+        if let Some(trigger) = trigger {
+            summary.hour_counts[trigger.hour() as usize] += 1;
+        }
+        let responded = match record.action.as_str() {
+            "PENDING" => {
+                summary.pending += 1;
+                false
+            }
+            "MISSED" => {
+                summary.missed += 1;
+                false
+            }
+            "SNOOZED" => {
+                summary.snoozed += 1;
+                true
+            }
+            _ => {
+                summary.acknowledged += 1;
+                true
+            }
+        };
+        if responded {
+            let close = record.close_time.as_deref().and_then(parse_time);
+            if let (Some(trigger), Some(close)) = (trigger, close) {
+                response_seconds.push((close - trigger).num_seconds().max(0) as f64);
+            }
+        }
+    }
+
+    let handled = summary.acknowledged + summary.snoozed + summary.missed;
+    if handled > 0 {
+        summary.ack_rate = Some(summary.acknowledged as f64 / handled as f64);
+    }
+    summary.median_response_seconds = median(&mut response_seconds);
+
+    let mut hours: Vec<u32> = (0..24)
+        .filter(|hour| summary.hour_counts[*hour as usize] > 0)
+        .collect();
+    hours.sort_by(|a, b| summary.hour_counts[*b as usize].cmp(&summary.hour_counts[*a as usize]));
+    hours.truncate(BUSIEST_HOURS_LIMIT);
+    summary.busiest_hours = hours;
+    summary
+}
+
+fn median(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let middle = values.len() / 2;
+    if middle * 2 == values.len() {
+        Some((values[middle - 1] + values[middle]) / 2.0)
+    } else {
+        Some(values[middle])
+    }
+}
+
+fn parse_time(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn record(
+        reminder_id: &str,
+        trigger: &str,
+        close: Option<&str>,
+        action: &str,
+    ) -> ReminderRecord {
+        ReminderRecord {
+            id: format!("{}-{}", reminder_id, trigger),
+            reminder_id: reminder_id.to_string(),
+            description: reminder_id.to_string(),
+            reminder_type: "TASK".to_string(),
+            trigger_time: trigger.to_string(),
+            close_time: close.map(str::to_string),
+            action: action.to_string(),
+            updated_at: None,
+            deleted_at: None,
+            delivery_error: None,
+            habit_state: None,
+            delivery_state: None,
+            delivered_at: None,
+            occurrence_id: None,
+        }
+    }
+
+    #[test]
+    fn default_range_ends_today() {
+        let today = date("2026-01-05");
+        assert_eq!(
+            resolve_stats_range(None, None, today).unwrap(),
+            (date("2025-12-07"), today)
+        );
+        assert_eq!(
+            resolve_stats_range(Some("2026-01-01"), Some(" "), today).unwrap(),
+            (date("2026-01-01"), today)
+        );
+        assert_eq!(
+            resolve_stats_range(None, Some("2026-01-31"), today).unwrap(),
+            (date("2026-01-02"), date("2026-01-31"))
+        );
+    }
+
+    #[test]
+    fn invalid_range_is_rejected() {
+        let today = date("2026-01-05");
+        assert!(resolve_stats_range(Some("2026/01/01"), None, today).is_err());
+        assert!(resolve_stats_range(Some("2026-01-06"), Some("2026-01-05"), today).is_err());
+    }
+
+    #[test]
+    fn median_handles_empty_odd_and_even_input() {
+        assert_eq!(median(&mut []), None);
+        assert_eq!(median(&mut [30.0, 10.0, 20.0]), Some(20.0));
+        assert_eq!(median(&mut [40.0, 10.0, 30.0, 20.0]), Some(25.0));
+    }
+
+    #[test]
+    fn summary_counts_actions_and_busiest_hours() {
+        let records = vec![
+            record(
+                "a",
+                "2026-01-05T09:00:00",
+                Some("2026-01-05T09:00:10"),
+                "CLOSED",
+            ),
+            record(
+                "a",
+                "2026-01-05T09:30:00",
+                Some("2026-01-05T09:30:30"),
+                "SNOOZED",
+            ),
+            record("a", "2026-01-05T14:00:00", None, "MISSED"),
+            record(
+                "b",
+                "2026-01-05T09:15:00",
+                Some("2026-01-05T09:16:00"),
+                "CLOSED",
+            ),
+            record("b", "2026-01-05T14:10:00", None, "MISSED"),
+            record("b", "2026-01-05T20:00:00", None, "PENDING"),
+            record("b", "2026-01-05T21:00:00", None, "MISSED"),
+        ];
+        let stats = compute_reminder_stats(date("2026-01-05"), date("2026-01-05"), &records);
+
+        let overall = &stats.overall;
+        assert_eq!(overall.total, 7);
+        assert_eq!(overall.acknowledged, 2);
+        assert_eq!(overall.snoozed, 1);
+        assert_eq!(overall.missed, 3);
+        assert_eq!(overall.pending, 1);
+        // 待处理的不计入确认率：2 / (2 + 1 + 3)
+        assert_eq!(overall.ack_rate, Some(2.0 / 6.0));
+        assert_eq!(overall.median_response_seconds, Some(30.0));
+        assert_eq!(overall.hour_counts[9], 3);
+        assert_eq!(overall.busiest_hours, vec![9, 14, 20]);
+
+        let order: Vec<&str> = stats
+            .tasks
+            .iter()
+            .map(|task| task.reminder_id.as_str())
+            .collect();
+        assert_eq!(order, vec!["b", "a"]);
+        assert_eq!(stats.tasks[0].summary.ack_rate, Some(1.0 / 3.0));
+    }
+
+    #[test]
+    fn no_handled_records_leaves_rate_empty() {
+        let records = vec![record("a", "2026-01-05T09:00:00", None, "PENDING")];
+        let stats = compute_reminder_stats(date("2026-01-05"), date("2026-01-05"), &records);
+        assert_eq!(stats.overall.ack_rate, None);
+        assert_eq!(stats.overall.median_response_seconds, None);
+    }
+}
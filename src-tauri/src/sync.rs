@@ -5,7 +5,7 @@ use std::sync::{
 };
 
 use base64::Engine;
-use chrono::{Local, NaiveDateTime};
+use chrono::NaiveDateTime;
 use reqwest::StatusCode;
 use rusqlite::{params_from_iter, types::Value, Connection};
 use serde::{Deserialize, Serialize};
@@ -14,6 +14,7 @@ use tokio::time::sleep;
 
 use crate::db::DbManager;
 use crate::errors::AppError;
+use crate::habits::is_acknowledged_action;
 use crate::models::{AppSettings, SyncStatus};
 
 const REMOTE_DB_NAME: &str = "taskreminder.db";
@@ -39,6 +40,8 @@ const TASK_COLUMNS: &[&str] = &[
     "sticky_width",
     "sticky_height",
     "sticky_is_open",
+    "notify_options",
+    "due_at",
     "updated_at",
     "deleted_at",
 ];
@@ -60,6 +63,7 @@ const RECURRING_COLUMNS: &[&str] = &[
     "schedule_weekday",
     "schedule_day",
     "cron_expression",
+    "notify_options",
     "updated_at",
     "deleted_at",
 ];
@@ -73,7 +77,37 @@ const RECORD_COLUMNS: &[&str] = &[
     "action",
     "updated_at",
     "deleted_at",
+    "delivery_error",
+    "habit_state",
+    "delivery_state",
+    "delivered_at",
+    "occurrence_id",
 ];
+const TIME_ENTRY_COLUMNS: &[&str] = &[
+    "id",
+    "task_id",
+    "started_at",
+    "ended_at",
+    "duration_seconds",
+    "source",
+    "updated_at",
+    "deleted_at",
+];
+
+const TASK_REMINDER_COLUMNS: &[&str] = &[
+    "id",
+    "task_id",
+    "remind_at",
+    "fired_at",
+    "created_at",
+    "updated_at",
+    "deleted_at",
+    "is_snooze",
+];
+
+const PAUSE_STATE_COLUMNS: &[&str] = &["id", "paused_at", "resume_at", "updated_at"];
+
+type MergeListener = Arc<dyn Fn() + Send + Sync>;
 
 #[derive(Clone)]
 pub struct CloudSyncService {
@@ -85,6 +119,7 @@ pub struct CloudSyncService {
     next_auto_sync_due: Arc<Mutex<Option<NaiveDateTime>>>,
     local_change_seq: Arc<AtomicU64>,
     dirty: Arc<AtomicBool>,
+    merge_listeners: Arc<Mutex<Vec<MergeListener>>>,
 }
 
 impl CloudSyncService {
@@ -98,9 +133,21 @@ impl CloudSyncService {
             next_auto_sync_due: Arc::new(Mutex::new(None)),
             local_change_seq: Arc::new(AtomicU64::new(0)),
             dirty: Arc::new(AtomicBool::new(false)),
+            merge_listeners: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
+    /// 远端数据合并进本地数据库后回调，供调度器按合并结果重新安排提醒。
+    pub fn on_merged<F>(&self, listener: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.merge_listeners
+            .lock()
+            .unwrap()
+            .push(Arc::new(listener));
+    }
+
     pub fn start(&self) -> Result<(), AppError> {
         self.refresh_dirty_from_settings()?;
         self.schedule_if_needed()?;
@@ -205,7 +252,7 @@ impl CloudSyncService {
         if !settings.webdav_enabled || settings.webdav_url.trim().is_empty() {
             return Ok(());
         }
-        let now = Local::now().naive_local();
+        let now = self.db.clock().now();
         let mut due = now + chrono::Duration::seconds(STARTUP_SYNC_DELAY_SECONDS as i64);
         if let Some(throttle_due) = next_allowed_auto_sync_time(&settings, now) {
             if throttle_due > due {
@@ -221,7 +268,7 @@ impl CloudSyncService {
         if !settings.webdav_enabled || settings.webdav_url.trim().is_empty() {
             return Ok(());
         }
-        let now = Local::now().naive_local();
+        let now = self.db.clock().now();
         let mut due = now + chrono::Duration::seconds(LOCAL_CHANGE_DEBOUNCE_SECONDS as i64);
         if let Some(throttle_due) = next_allowed_auto_sync_time(&settings, now) {
             if throttle_due > due {
@@ -233,7 +280,7 @@ impl CloudSyncService {
     }
 
     fn schedule_auto_sync_at(&self, due: NaiveDateTime, reason: &str) -> Result<(), AppError> {
-        let now = Local::now().naive_local();
+        let now = self.db.clock().now();
         if due <= now {
             return self.request_sync_if_needed(reason);
         }
@@ -268,7 +315,7 @@ impl CloudSyncService {
     }
 
     fn request_sync_on_interval(&self) -> Result<(), AppError> {
-        let now = Local::now().naive_local();
+        let now = self.db.clock().now();
         if let Some(due) = *self.next_auto_sync_due.lock().unwrap() {
             let remaining = due - now;
             // 如果 debounce 很快就会触发，就避免 interval “抢跑”；否则 interval 作为兜底依然可以触发同步。
@@ -289,7 +336,7 @@ impl CloudSyncService {
         }
 
         // throttle：距离上一次同步太近则延后。
-        let now = Local::now().naive_local();
+        let now = self.db.clock().now();
         if let Some(throttle_due) = next_allowed_auto_sync_time(&settings, now) {
             self.schedule_auto_sync_at(throttle_due, "throttle")?;
             return Ok(());
@@ -333,6 +380,10 @@ impl CloudSyncService {
             let downloaded = download_remote(&client)?;
             remote = Some(downloaded.clone());
             merge_databases(&self.db.db_path(), &downloaded)?;
+            let listeners = self.merge_listeners.lock().unwrap().clone();
+            for listener in listeners {
+                listener();
+            }
             let local_snapshot = export_local_snapshot(&self.db.db_path())?;
             client.upload(REMOTE_DB_NAME, &local_snapshot)?;
             snapshot = Some(local_snapshot);
@@ -467,6 +518,35 @@ fn merge_databases(
         RECORD_COLUMNS,
         "trigger_time",
     )?;
+    merge_table(
+        &tx,
+        &remote,
+        "time_entries",
+        TIME_ENTRY_COLUMNS,
+        "started_at",
+    )?;
+    merge_table(
+        &tx,
+        &remote,
+        "task_reminders",
+        TASK_REMINDER_COLUMNS,
+        "created_at",
+    )?;
+    merge_table(
+        &tx,
+        &remote,
+        "pause_state",
+        PAUSE_STATE_COLUMNS,
+        "updated_at",
+    )?;
+    // 旧版本设备新建或改动的任务只有 reminder_time，补成一条提醒时间记录。
+    tx.execute_batch(
+        "INSERT OR IGNORE INTO task_reminders (id, task_id, remind_at, fired_at, created_at, updated_at, deleted_at)
+         SELECT id || '@' || reminder_time, id, reminder_time, NULL, created_at, updated_at, NULL
+         FROM tasks
+         WHERE reminder_time IS NOT NULL AND deleted_at IS NULL
+           AND NOT EXISTS (SELECT 1 FROM task_reminders WHERE task_reminders.task_id = tasks.id);",
+    )?;
     tx.commit()?;
     Ok(())
 }
@@ -513,6 +593,18 @@ fn choose_row<'a>(local: Option<&'a RowData>, remote: Option<&'a RowData>) -> Op
     match (local, remote) {
         (Some(l), None) => Some(l),
         (None, Some(r)) => Some(r),
+        // 同一次触发的提醒记录，已处理的一方优先，避免被另一台设备稍后标记的“错过”覆盖。
+        // 有一方已删除时仍按时间先后，除非两边时间相同。
+        (Some(l), Some(r))
+            if l.acknowledged != r.acknowledged
+                && (!(l.deleted || r.deleted) || l.compare_time == r.compare_time) =>
+        {
+            if r.acknowledged {
+                Some(r)
+            } else {
+                Some(l)
+            }
+        }
         (Some(l), Some(r)) => match (l.compare_time, r.compare_time) {
             (Some(lc), Some(rc)) => {
                 if rc > lc {
@@ -533,6 +625,9 @@ fn choose_row<'a>(local: Option<&'a RowData>, remote: Option<&'a RowData>) -> Op
 struct RowData {
     values: Vec<Value>,
     compare_time: Option<NaiveDateTime>,
+    /// 仅提醒记录：用户是否已处理。
+    acknowledged: bool,
+    deleted: bool,
 }
 
 fn load_rows(
@@ -549,6 +644,7 @@ fn load_rows(
     let updated_index = columns.iter().position(|c| *c == "updated_at");
     let deleted_index = columns.iter().position(|c| *c == "deleted_at");
     let fallback_index = columns.iter().position(|c| *c == fallback_time_column);
+    let action_index = columns.iter().position(|c| *c == "action");
 
     let mut map = HashMap::new();
     while let Some(row) = rows.next()? {
@@ -568,11 +664,16 @@ fn load_rows(
                 values[idx] = Value::Text(value);
             }
             let compare = normalized.and_then(|value| parse_datetime_any(&value));
+            let acknowledged = action_index
+                .and_then(|idx| value_to_string(&values[idx]))
+                .is_some_and(|action| is_acknowledged_action(&action));
             map.insert(
                 id,
                 RowData {
                     values,
                     compare_time: compare,
+                    acknowledged,
+                    deleted: deleted.is_some(),
                 },
             );
         }
@@ -635,6 +736,7 @@ fn ensure_sync_columns(conn: &Connection) -> Result<(), AppError> {
         "sticky_is_open",
         "INTEGER NOT NULL DEFAULT 0",
     )?;
+    ensure_column(conn, "tasks", "due_at", "TEXT")?;
     ensure_column(conn, "recurring_tasks", "updated_at", "TEXT")?;
     ensure_column(conn, "recurring_tasks", "deleted_at", "TEXT")?;
     ensure_column(
@@ -647,8 +749,59 @@ fn ensure_sync_columns(conn: &Connection) -> Result<(), AppError> {
     ensure_column(conn, "recurring_tasks", "schedule_weekday", "INTEGER")?;
     ensure_column(conn, "recurring_tasks", "schedule_day", "INTEGER")?;
     ensure_column(conn, "recurring_tasks", "cron_expression", "TEXT")?;
+    ensure_column(
+        conn,
+        "tasks",
+        "notify_options",
+        "TEXT NOT NULL DEFAULT '{}'",
+    )?;
+    ensure_column(
+        conn,
+        "recurring_tasks",
+        "notify_options",
+        "TEXT NOT NULL DEFAULT '{}'",
+    )?;
     ensure_column(conn, "reminder_records", "updated_at", "TEXT")?;
     ensure_column(conn, "reminder_records", "deleted_at", "TEXT")?;
+    ensure_column(conn, "reminder_records", "delivery_error", "TEXT")?;
+    ensure_column(conn, "reminder_records", "habit_state", "TEXT")?;
+    ensure_column(conn, "reminder_records", "delivery_state", "TEXT")?;
+    ensure_column(conn, "reminder_records", "delivered_at", "TEXT")?;
+    ensure_column(conn, "reminder_records", "occurrence_id", "TEXT")?;
+    // 旧版本上传的远端数据库没有用时记录表、提醒时间表与全局暂停表。
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS time_entries (
+            id TEXT PRIMARY KEY,
+            task_id TEXT NOT NULL,
+            started_at TEXT NOT NULL,
+            ended_at TEXT NOT NULL,
+            duration_seconds INTEGER NOT NULL DEFAULT 0,
+            source TEXT NOT NULL DEFAULT 'FOCUS',
+            updated_at TEXT,
+            deleted_at TEXT
+        );
+        CREATE TABLE IF NOT EXISTS task_reminders (
+            id TEXT PRIMARY KEY,
+            task_id TEXT NOT NULL,
+            remind_at TEXT NOT NULL,
+            fired_at TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT,
+            deleted_at TEXT
+        );
+        CREATE TABLE IF NOT EXISTS pause_state (
+            id TEXT PRIMARY KEY,
+            paused_at TEXT,
+            resume_at TEXT,
+            updated_at TEXT
+        );",
+    )?;
+    ensure_column(
+        conn,
+        "task_reminders",
+        "is_snooze",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
     Ok(())
 }
 
@@ -897,3 +1050,122 @@ fn cleanup_temp_file(path: &Option<std::path::PathBuf>) {
         let _ = std::fs::remove_file(path);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::clock::ManualClock;
+
+    fn at(value: &str) -> NaiveDateTime {
+        parse_datetime_any(value).unwrap()
+    }
+
+    struct TempDb {
+        path: PathBuf,
+        clock: Arc<ManualClock>,
+        db: DbManager,
+    }
+
+    impl TempDb {
+        fn new(now: &str) -> Self {
+            let path = std::env::temp_dir()
+                .join(format!("task-reminder-sync-{}.db", uuid::Uuid::new_v4()));
+            let clock = ManualClock::new(parse_datetime_any(now).unwrap());
+            let db = DbManager::with_clock(path.clone(), clock.clone()).unwrap();
+            Self { path, clock, db }
+        }
+    }
+
+    impl Drop for TempDb {
+        fn drop(&mut self) {
+            for suffix in ["", "-wal", "-shm"] {
+                let mut path = self.path.clone().into_os_string();
+                path.push(suffix);
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+
+    #[test]
+    fn same_occurrence_merges_into_one_record_and_keeps_the_ack() {
+        let local = TempDb::new("2026-01-05T09:00:00");
+        let remote = TempDb::new("2026-01-05T09:00:02");
+        let occurrence = "daily@2026-01-05T09:00:00";
+        for side in [&local, &remote] {
+            side.db
+                .create_occurrence_record("daily", "站会", "RECURRING", occurrence)
+                .unwrap();
+        }
+        // 远端先关闭了提醒，本机稍后才因被新弹窗覆盖而标记为错过。
+        remote.clock.set(at("2026-01-05T09:01:00"));
+        remote
+            .db
+            .update_reminder_record_action(occurrence, "DISMISSED")
+            .unwrap();
+        local.clock.set(at("2026-01-05T09:10:00"));
+        local
+            .db
+            .update_reminder_record_action(occurrence, "MISSED")
+            .unwrap();
+
+        merge_databases(&local.path, &remote.path).unwrap();
+
+        let records = local.db.list_reminder_records().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].action, "DISMISSED");
+        assert_eq!(records[0].occurrence_id.as_deref(), Some(occurrence));
+    }
+
+    #[test]
+    fn later_change_wins_when_both_sides_acknowledged() {
+        let local = TempDb::new("2026-01-05T09:00:00");
+        let remote = TempDb::new("2026-01-05T09:00:00");
+        let occurrence = "task@2026-01-05T09:00:00";
+        for side in [&local, &remote] {
+            side.db
+                .create_occurrence_record("task", "交周报", "TASK", occurrence)
+                .unwrap();
+        }
+        local.clock.set(at("2026-01-05T09:01:00"));
+        local
+            .db
+            .update_reminder_record_action(occurrence, "SNOOZED")
+            .unwrap();
+        remote.clock.set(at("2026-01-05T09:05:00"));
+        remote
+            .db
+            .update_reminder_record_action(occurrence, "COMPLETED")
+            .unwrap();
+
+        merge_databases(&local.path, &remote.path).unwrap();
+
+        let records = local.db.list_reminder_records().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].action, "COMPLETED");
+    }
+
+    #[test]
+    fn later_local_delete_wins_over_an_earlier_remote_ack() {
+        let local = TempDb::new("2026-01-05T09:00:00");
+        let remote = TempDb::new("2026-01-05T09:00:00");
+        let occurrence = "daily@2026-01-05T09:00:00";
+        for side in [&local, &remote] {
+            side.db
+                .create_occurrence_record("daily", "站会", "RECURRING", occurrence)
+                .unwrap();
+        }
+        remote.clock.set(at("2026-01-05T09:01:00"));
+        remote
+            .db
+            .update_reminder_record_action(occurrence, "DISMISSED")
+            .unwrap();
+        local.clock.set(at("2026-01-05T09:10:00"));
+        local.db.delete_reminder_record(occurrence).unwrap();
+
+        merge_databases(&local.path, &remote.path).unwrap();
+
+        assert!(local.db.list_reminder_records().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,57 @@
+use crate::due::parse_time;
+use crate::errors::AppError;
+
+const MAX_TASK_REMINDERS: usize = 10;
+
+/// 校验并整理任务的提醒时间：统一为秒精度，去重后从早到晚排列，空白项忽略。
+pub fn normalize_reminder_times(values: &[String]) -> Result<Vec<String>, AppError> {
+    let mut times = Vec::new();
+    for value in values.iter().map(|value| value.trim()) {
+        if value.is_empty() {
+            continue;
+        }
+        let time = parse_time(value)
+            .ok_or_else(|| AppError::Invalid(format!("无法解析提醒时间: {}", value)))?;
+        times.push(time);
+    }
+    times.sort_unstable();
+    times.dedup();
+    if times.len() > MAX_TASK_REMINDERS {
+        return Err(AppError::Invalid(format!(
+            "每个任务最多 {} 个提醒时间",
+            MAX_TASK_REMINDERS
+        )));
+    }
+    Ok(times
+        .into_iter()
+        .map(|time| time.format("%Y-%m-%dT%H:%M:%S").to_string())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn times_are_deduped_and_ordered() {
+        assert_eq!(
+            normalize_reminder_times(&strings(&[
+                "2026-01-05T09:00",
+                " ",
+                "2026-01-03T09:00:00",
+                "2026-01-05T09:00:00",
+            ]))
+            .unwrap(),
+            strings(&["2026-01-03T09:00:00", "2026-01-05T09:00:00"])
+        );
+        assert!(normalize_reminder_times(&strings(&["明天"])).is_err());
+        let many: Vec<String> = (10..21)
+            .map(|day| format!("2026-01-{}T09:00:00", day))
+            .collect();
+        assert!(normalize_reminder_times(&many).is_err());
+    }
+}
@@ -0,0 +1,220 @@
+use chrono::{Duration, NaiveDateTime};
+use tauri::{AppHandle, Emitter};
+use tokio::time::sleep;
+
+use crate::db::DbManager;
+use crate::errors::AppError;
+use crate::models::Timer;
+use crate::tray;
+
+pub const TIMER_REMINDER_TYPE: &str = "TIMER";
+pub const TIMER_RUNNING: &str = "RUNNING";
+pub const TIMER_PAUSED: &str = "PAUSED";
+/// 托盘菜单里的快速计时选项（分钟）。
+pub const TIMER_PRESET_MINUTES: [i64; 4] = [5, 15, 25, 60];
+pub const TIMER_TICK_EVENT: &str = "timer-tick";
+pub const TIMERS_CHANGED_EVENT: &str = "timers-changed";
+const MAX_TIMER_SECONDS: i64 = 24 * 60 * 60;
+
+pub fn normalize_timer_seconds(seconds: i64) -> Result<i64, AppError> {
+    if seconds <= 0 {
+        return Err(AppError::Invalid("计时时长必须大于 0".to_string()));
+    }
+    if seconds > MAX_TIMER_SECONDS {
+        return Err(AppError::Invalid("计时时长不能超过 24 小时".to_string()));
+    }
+    Ok(seconds)
+}
+
+pub fn timer_label(label: Option<&str>, seconds: i64) -> String {
+    match label.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => value.to_string(),
+        None => format!("{} 计时", format_duration(seconds)),
+    }
+}
+
+/// 运行中的计时器按结束时间实时计算剩余秒数，暂停的计时器直接取保存的值。
+pub fn remaining_seconds(timer: &Timer, now: NaiveDateTime) -> i64 {
+    if timer.status != TIMER_RUNNING {
+        return timer.remaining_seconds.max(0);
+    }
+    timer
+        .ends_at
+        .as_deref()
+        .and_then(parse_time)
+        .map(|ends_at| (ends_at - now).num_seconds().max(0))
+        .unwrap_or(0)
+}
+
+pub fn with_remaining(timers: Vec<Timer>, now: NaiveDateTime) -> Vec<Timer> {
+    timers
+        .into_iter()
+        .map(|mut timer| {
+            timer.remaining_seconds = remaining_seconds(&timer, now);
+            timer
+        })
+        .collect()
+}
+
+pub fn ends_at_after(seconds: i64, now: NaiveDateTime) -> String {
+    (now + Duration::seconds(seconds))
+        .format("%Y-%m-%dT%H:%M:%S")
+        .to_string()
+}
+
+pub fn format_duration(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (hours, minutes, secs) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    match (hours, secs) {
+        (0, 0) => format!("{} 分钟", minutes),
+        (0, _) => format!("{:02}:{:02}", minutes, secs),
+        (_, 0) if minutes == 0 => format!("{} 小时", hours),
+        _ => format!("{}:{:02}:{:02}", hours, minutes, secs),
+    }
+}
+
+/// 托盘菜单中显示的计时器名称。菜单不逐秒刷新，运行中的计时器显示结束时刻。
+pub fn tray_text(timer: &Timer, now: NaiveDateTime) -> String {
+    if timer.status == TIMER_RUNNING {
+        let ends_at = timer
+            .ends_at
+            .as_deref()
+            .and_then(parse_time)
+            .map(|value| value.format("%H:%M").to_string())
+            .unwrap_or_default();
+        format!("{} · {} 结束", timer.label, ends_at)
+    } else {
+        format!(
+            "{} · 已暂停，剩余 {}",
+            timer.label,
+            format_duration(remaining_seconds(timer, now))
+        )
+    }
+}
+
+/// 计时器增删或状态变化后通知前端并重建托盘菜单。
+pub fn notify_timers_changed(app: &AppHandle) {
+    let _ = app.emit(TIMERS_CHANGED_EVENT, ());
+    tray::refresh_tray_menu(app);
+}
+
+/// 有计时器在运行时每秒向前端推送一次剩余时间。
+pub fn start_timer_ticker(app: AppHandle, db: DbManager) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            sleep(std::time::Duration::from_secs(1)).await;
+            let Ok(timers) = db.list_timers() else {
+                continue;
+            };
+            if timers.iter().any(|timer| timer.status == TIMER_RUNNING) {
+                let now = db.clock().now();
+                let _ = app.emit(TIMER_TICK_EVENT, with_remaining(timers, now));
+            }
+        }
+    });
+}
+
+fn parse_time(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::{at, Simulation};
+
+    fn timer(status: &str, ends_at: Option<&str>, remaining_seconds: i64) -> Timer {
+        Timer {
+            id: "timer".to_string(),
+            label: "泡茶".to_string(),
+            duration_seconds: 300,
+            status: status.to_string(),
+            ends_at: ends_at.map(str::to_string),
+            remaining_seconds,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn remaining_seconds_follow_the_clock_only_while_running() {
+        let now = at("2026-01-05T08:00:00");
+        let running = timer(TIMER_RUNNING, Some("2026-01-05T08:01:30"), 300);
+        assert_eq!(remaining_seconds(&running, now), 90);
+        assert_eq!(remaining_seconds(&running, at("2026-01-05T08:05:00")), 0);
+        let paused = timer(TIMER_PAUSED, None, 120);
+        assert_eq!(remaining_seconds(&paused, now), 120);
+        assert_eq!(tray_text(&running, now), "泡茶 · 08:01 结束");
+        assert_eq!(tray_text(&paused, now), "泡茶 · 已暂停，剩余 2 分钟");
+    }
+
+    #[test]
+    fn durations_and_labels_are_formatted() {
+        assert_eq!(format_duration(300), "5 分钟");
+        assert_eq!(format_duration(90), "01:30");
+        assert_eq!(format_duration(7200), "2 小时");
+        assert_eq!(format_duration(3725), "1:02:05");
+        assert_eq!(timer_label(Some("  "), 1500), "25 分钟 计时");
+        assert!(normalize_timer_seconds(0).is_err());
+        assert!(normalize_timer_seconds(MAX_TIMER_SECONDS + 1).is_err());
+    }
+
+    #[test]
+    fn timer_fires_once_after_pause_and_resume() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let started = sim.scheduler.start_timer(Some("泡茶"), 5 * 60).unwrap();
+        sim.advance(Duration::minutes(2));
+        sim.scheduler.pause_timer(&started.id).unwrap();
+        let paused = sim.db.get_timer(&started.id).unwrap().unwrap();
+        assert_eq!(paused.status, TIMER_PAUSED);
+        assert_eq!(paused.remaining_seconds, 180);
+
+        sim.advance(Duration::minutes(10));
+        assert!(sim.shown().is_empty());
+
+        sim.scheduler.resume_timer(&started.id).unwrap();
+        sim.advance(Duration::minutes(3) - Duration::seconds(1));
+        assert!(sim.shown().is_empty());
+        sim.advance(Duration::seconds(1));
+        assert_eq!(sim.shown(), vec!["泡茶"]);
+        assert!(sim.db.list_timers().unwrap().is_empty());
+        let records = sim.records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].reminder_type, TIMER_REMINDER_TYPE);
+
+        sim.advance(Duration::hours(1));
+        assert_eq!(sim.shown().len(), 1);
+    }
+
+    #[test]
+    fn stopped_timer_never_fires() {
+        let sim = Simulation::new("2026-01-05T08:00:00");
+        let started = sim.scheduler.start_timer(None, 60).unwrap();
+        sim.scheduler.stop_timer(&started.id).unwrap();
+        sim.advance(Duration::minutes(5));
+        assert!(sim.shown().is_empty());
+        assert!(sim.db.list_timers().unwrap().is_empty());
+    }
+
+    #[test]
+    fn running_timers_are_restored_after_restart() {
+        let mut sim = Simulation::new("2026-01-05T08:00:00");
+        sim.scheduler.start_timer(Some("短"), 5 * 60).unwrap();
+        sim.scheduler.start_timer(Some("长"), 30 * 60).unwrap();
+        let paused = sim.scheduler.start_timer(Some("暂停"), 60).unwrap();
+        sim.scheduler.pause_timer(&paused.id).unwrap();
+
+        // 关机期间第一个计时器已到点，启动后立即触发；另一个按原来的结束时间触发。
+        sim.clock.set(at("2026-01-05T08:10:00"));
+        sim.restart();
+        sim.scheduler.tick();
+        assert_eq!(sim.shown(), vec!["短"]);
+        sim.advance(Duration::minutes(19));
+        assert_eq!(sim.shown(), vec!["短"]);
+        sim.advance(Duration::minutes(1));
+        assert_eq!(sim.shown(), vec!["短", "长"]);
+        let remaining = sim.db.list_timers().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].status, TIMER_PAUSED);
+    }
+}
@@ -1,14 +1,27 @@
+use chrono::{Duration, NaiveDateTime};
 use tauri::{
-    menu::MenuBuilder,
+    menu::{Menu, MenuBuilder, MenuItem, SubmenuBuilder},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
     AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder,
 };
 
 use crate::create_custom_sticky_note_via_app;
+use crate::models::{PauseState, UpcomingReminder};
 use crate::paths;
 use crate::state::AppState;
+use crate::timers::{tray_text, TIMER_PRESET_MINUTES, TIMER_RUNNING};
 
-fn show_main(app: &AppHandle) {
+const TRAY_ID: &str = "main-tray";
+const TIMER_START_PREFIX: &str = "timer_start:";
+const TIMER_PAUSE_PREFIX: &str = "timer_pause:";
+const TIMER_RESUME_PREFIX: &str = "timer_resume:";
+const TIMER_CANCEL_PREFIX: &str = "timer_cancel:";
+const PAUSE_ALL_PREFIX: &str = "pause_all:";
+/// 托盘中可选的全局暂停天数，0 表示直到手动恢复。
+const PAUSE_PRESET_DAYS: [i64; 4] = [0, 1, 3, 7];
+const NEXT_UP_MAX_CHARS: usize = 24;
+
+pub(crate) fn show_main(app: &AppHandle) {
     if let Some(window) = app.get_webview_window("main") {
         let _ = window.show();
         let _ = window.unminimize();
@@ -32,7 +45,9 @@ fn show_main(app: &AppHandle) {
 
 fn create_sticky_note(app: &AppHandle) {
     if let Some(state) = app.try_state::<AppState>() {
-        if let Err(err) = create_custom_sticky_note_via_app(app, state.inner(), "", None, None, None, None, None) {
+        if let Err(err) =
+            create_custom_sticky_note_via_app(app, state.inner(), "", None, None, None, None, None)
+        {
             eprintln!("[tray] 新建便签失败: {}", err);
         }
     } else {
@@ -40,22 +55,168 @@ fn create_sticky_note(app: &AppHandle) {
     }
 }
 
-pub fn setup_tray(app: &AppHandle) -> Result<(), tauri::Error> {
+fn run_timer_command(app: &AppHandle, id: &str) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let scheduler = &state.scheduler;
+    let result = if let Some(minutes) = id.strip_prefix(TIMER_START_PREFIX) {
+        let minutes = minutes.parse::<i64>().unwrap_or(0);
+        scheduler.start_timer(None, minutes * 60).map(|_| ())
+    } else if let Some(timer_id) = id.strip_prefix(TIMER_PAUSE_PREFIX) {
+        scheduler.pause_timer(timer_id)
+    } else if let Some(timer_id) = id.strip_prefix(TIMER_RESUME_PREFIX) {
+        scheduler.resume_timer(timer_id)
+    } else if let Some(timer_id) = id.strip_prefix(TIMER_CANCEL_PREFIX) {
+        scheduler.stop_timer(timer_id)
+    } else {
+        Ok(())
+    };
+    if let Err(err) = result {
+        eprintln!("[tray] 计时器操作失败: {}", err);
+    }
+}
+
+fn run_pause_command(app: &AppHandle, id: &str) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let result = if let Some(days) = id.strip_prefix(PAUSE_ALL_PREFIX) {
+        let days = days.parse::<i64>().unwrap_or(0);
+        let now = state.db.clock().now();
+        let resume_at = (days > 0).then(|| now + Duration::days(days));
+        state.scheduler.pause_all(resume_at).map(|_| ())
+    } else {
+        state.scheduler.resume_all()
+    };
+    if let Err(err) = result {
+        eprintln!("[tray] 全局暂停操作失败: {}", err);
+    }
+}
+
+fn build_menu(app: &AppHandle) -> Result<Menu<tauri::Wry>, tauri::Error> {
     let dev_tag = if paths::is_dev_mode() {
         " [开发]"
     } else {
         ""
     };
-    let menu = MenuBuilder::new(app)
+    let timers = app
+        .try_state::<AppState>()
+        .and_then(|state| state.db.list_timers().ok())
+        .unwrap_or_default();
+    let next_up = app
+        .try_state::<AppState>()
+        .and_then(|state| state.scheduler.upcoming(1).ok())
+        .and_then(|upcoming| upcoming.into_iter().next());
+    let pause = app
+        .try_state::<AppState>()
+        .and_then(|state| state.db.load_pause_state().ok())
+        .unwrap_or_default();
+    let now = app
+        .try_state::<AppState>()
+        .map(|state| state.db.clock().now())
+        .unwrap_or_else(|| crate::clock::system_clock().now());
+
+    let mut timer_menu = SubmenuBuilder::new(app, "计时器");
+    for minutes in TIMER_PRESET_MINUTES {
+        timer_menu = timer_menu.text(
+            format!("{}{}", TIMER_START_PREFIX, minutes),
+            format!("开始 {} 分钟计时", minutes),
+        );
+    }
+    if !timers.is_empty() {
+        timer_menu = timer_menu.separator();
+    }
+    for timer in &timers {
+        let toggle = if timer.status == TIMER_RUNNING {
+            (format!("{}{}", TIMER_PAUSE_PREFIX, timer.id), "暂停")
+        } else {
+            (format!("{}{}", TIMER_RESUME_PREFIX, timer.id), "继续")
+        };
+        let item = SubmenuBuilder::new(app, tray_text(timer, now))
+            .text(toggle.0, toggle.1)
+            .text(format!("{}{}", TIMER_CANCEL_PREFIX, timer.id), "取消")
+            .build()?;
+        timer_menu = timer_menu.item(&item);
+    }
+    let timer_menu = timer_menu.build()?;
+
+    let mut menu = MenuBuilder::new(app);
+    if pause.paused {
+        let item = MenuItem::with_id(app, "paused", paused_text(&pause), false, None::<&str>)?;
+        menu = menu.item(&item).separator();
+    } else if let Some(next) = &next_up {
+        let item = MenuItem::with_id(app, "next_up", next_up_text(next, now), false, None::<&str>)?;
+        menu = menu.item(&item).separator();
+    }
+    menu = menu
         .text("open", format!("打开{}", dev_tag))
         .text("new_note", "新建便签")
-        .text("check_update", "检查更新")
+        .item(&timer_menu);
+    if pause.paused {
+        menu = menu.text("resume_all", "恢复全部提醒");
+    } else {
+        let mut pause_menu = SubmenuBuilder::new(app, "暂停全部提醒");
+        for days in PAUSE_PRESET_DAYS {
+            let label = if days == 0 {
+                "直到手动恢复".to_string()
+            } else {
+                format!("{} 天", days)
+            };
+            pause_menu = pause_menu.text(format!("{}{}", PAUSE_ALL_PREFIX, days), label);
+        }
+        menu = menu.item(&pause_menu.build()?);
+    }
+    menu.text("check_update", "检查更新")
         .text("sync_now", "立即同步")
         .separator()
         .text("quit", "退出")
-        .build()?;
+        .build()
+}
 
-    let mut tray_builder = TrayIconBuilder::with_id("main-tray")
+/// 菜单顶部的下一个提醒，当天的只显示时刻。
+fn next_up_text(next: &UpcomingReminder, now: NaiveDateTime) -> String {
+    let when = NaiveDateTime::parse_from_str(&next.fire_at, "%Y-%m-%dT%H:%M:%S")
+        .map(|at| {
+            if at.date() == now.date() {
+                at.format("%H:%M").to_string()
+            } else {
+                at.format("%m-%d %H:%M").to_string()
+            }
+        })
+        .unwrap_or_else(|_| next.fire_at.clone());
+    let description: String = next.description.chars().take(NEXT_UP_MAX_CHARS).collect();
+    format!("下一个提醒：{} {}", when, description)
+}
+
+fn paused_text(pause: &PauseState) -> String {
+    let resume_at = pause
+        .resume_at
+        .as_deref()
+        .and_then(|value| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").ok());
+    match resume_at {
+        Some(at) => format!("提醒已暂停至 {}", at.format("%m-%d %H:%M")),
+        None => "提醒已暂停".to_string(),
+    }
+}
+
+/// 计时器或调度队列变化后重建托盘菜单。
+pub fn refresh_tray_menu(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_menu(app) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(err) => eprintln!("[tray] 刷新菜单失败: {}", err),
+    }
+}
+
+pub fn setup_tray(app: &AppHandle) -> Result<(), tauri::Error> {
+    let menu = build_menu(app)?;
+
+    let mut tray_builder = TrayIconBuilder::with_id(TRAY_ID)
         .menu(&menu)
         .show_menu_on_left_click(false)
         .on_menu_event(|app, event| match event.id().as_ref() {
@@ -77,6 +238,12 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), tauri::Error> {
             "quit" => {
                 app.exit(0);
             }
+            id if id.starts_with("timer_") => {
+                run_timer_command(app, id);
+            }
+            id if id.starts_with(PAUSE_ALL_PREFIX) || id == "resume_all" => {
+                run_pause_command(app, id);
+            }
             _ => {}
         })
         .on_tray_icon_event(|tray, event| {
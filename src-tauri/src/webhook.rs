@@ -0,0 +1,661 @@
+use std::thread;
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::{Local, NaiveDateTime, TimeZone};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::db::DbManager;
+use crate::errors::AppError;
+use crate::models::{
+    NotificationAction, NotificationPayload, ReminderRecord, WebhookChannel, WebhookDelivery,
+};
+
+pub const WEBHOOK_EVENT_FIRED: &str = "FIRED";
+pub const WEBHOOK_EVENT_ACKED: &str = "ACKED";
+pub const WEBHOOK_EVENT_SNOOZED: &str = "SNOOZED";
+pub const WEBHOOK_EVENT_MISSED: &str = "MISSED";
+pub const WEBHOOK_EVENT_TEST: &str = "TEST";
+
+const WEBHOOK_EVENTS: [&str; 4] = [
+    WEBHOOK_EVENT_FIRED,
+    WEBHOOK_EVENT_ACKED,
+    WEBHOOK_EVENT_SNOOZED,
+    WEBHOOK_EVENT_MISSED,
+];
+const WEBHOOK_PRESETS: [&str; 6] = ["GENERIC", "NTFY", "GOTIFY", "BARK", "DINGTALK", "WECOM"];
+const MAX_ATTEMPTS: i64 = 3;
+const RETRY_BASE_DELAY_MS: u64 = 1000;
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+/// 推送给 Webhook 的提醒事件，GENERIC 预设直接以此结构作为 JSON 请求体。
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookMessage {
+    pub event: String,
+    pub record_id: String,
+    pub reminder_id: String,
+    pub reminder_type: String,
+    pub description: String,
+    pub trigger_time: String,
+    pub close_time: Option<String>,
+    pub action: String,
+    pub snooze_minutes: Option<i64>,
+    pub actions: Vec<NotificationAction>,
+    pub sent_at: String,
+}
+
+impl WebhookMessage {
+    pub fn new(
+        event: &str,
+        record: &ReminderRecord,
+        payload: Option<&NotificationPayload>,
+        snooze_minutes: Option<i64>,
+        now: NaiveDateTime,
+    ) -> Self {
+        Self {
+            event: event.to_string(),
+            record_id: record.id.clone(),
+            reminder_id: record.reminder_id.clone(),
+            reminder_type: record.reminder_type.clone(),
+            description: record.description.clone(),
+            trigger_time: record.trigger_time.clone(),
+            close_time: record.close_time.clone(),
+            action: record.action.clone(),
+            snooze_minutes: snooze_minutes.or(payload.map(|payload| payload.snooze_minutes)),
+            actions: payload
+                .map(|payload| payload.actions.clone())
+                .unwrap_or_default(),
+            sent_at: format_time(now),
+        }
+    }
+
+    fn title(&self) -> String {
+        match self.event.as_str() {
+            WEBHOOK_EVENT_ACKED => "提醒已确认".to_string(),
+            WEBHOOK_EVENT_SNOOZED => match self.snooze_minutes {
+                Some(minutes) => format!("提醒已推迟 {} 分钟", minutes),
+                None => "提醒已推迟".to_string(),
+            },
+            WEBHOOK_EVENT_MISSED => "提醒已错过".to_string(),
+            WEBHOOK_EVENT_TEST => "Webhook 测试".to_string(),
+            _ => "任务提醒".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookRequest {
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+#[derive(Clone)]
+pub struct WebhookService {
+    db: DbManager,
+    client: reqwest::blocking::Client,
+}
+
+impl WebhookService {
+    pub fn new(db: DbManager) -> Result<Self, AppError> {
+        Ok(Self {
+            db,
+            client: reqwest::blocking::Client::builder()
+                .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+                .build()
+                .map_err(|e| AppError::System(e.to_string()))?,
+        })
+    }
+
+    /// 按渠道的事件订阅与任务选择筛选后，在后台线程中逐个投递，不阻塞提醒弹窗。
+    pub fn publish(&self, message: WebhookMessage, selected_channels: &[String]) {
+        let channels = match self.db.list_webhook_channels() {
+            Ok(channels) => channels,
+            Err(err) => {
+                eprintln!("[webhook] 读取渠道失败: {}", err);
+                return;
+            }
+        };
+        for channel in channels {
+            if !channel.enabled || !channel.events.iter().any(|event| event == &message.event) {
+                continue;
+            }
+            if !channel.apply_to_all && !selected_channels.contains(&channel.id) {
+                continue;
+            }
+            let service = self.clone();
+            let message = message.clone();
+            thread::spawn(move || {
+                service.deliver(&channel, &message, MAX_ATTEMPTS);
+            });
+        }
+    }
+
+    pub fn send_test(&self, channel: &WebhookChannel) -> WebhookDelivery {
+        let now = self.db.clock().now();
+        let record = ReminderRecord {
+            id: format!("test-{}", Uuid::new_v4()),
+            reminder_id: String::new(),
+            description: "这是一条来自任务提醒的测试消息".to_string(),
+            reminder_type: "TASK".to_string(),
+            trigger_time: format_time(now),
+            close_time: None,
+            action: "PENDING".to_string(),
+            updated_at: None,
+            deleted_at: None,
+            delivery_error: None,
+            habit_state: None,
+            delivery_state: None,
+            delivered_at: None,
+            occurrence_id: None,
+        };
+        let message = WebhookMessage::new(WEBHOOK_EVENT_TEST, &record, None, None, now);
+        self.deliver(channel, &message, 1)
+    }
+
+    fn deliver(
+        &self,
+        channel: &WebhookChannel,
+        message: &WebhookMessage,
+        max_attempts: i64,
+    ) -> WebhookDelivery {
+        let now = self.db.clock().now();
+        let mut delivery = WebhookDelivery {
+            id: Uuid::new_v4().to_string(),
+            channel_id: channel.id.clone(),
+            record_id: message.record_id.clone(),
+            event: message.event.clone(),
+            success: false,
+            attempts: 0,
+            status_code: None,
+            error: None,
+            created_at: format_time(now),
+        };
+        match build_request(channel, message, timestamp_millis(now)) {
+            Ok(request) => {
+                while delivery.attempts < max_attempts {
+                    if delivery.attempts > 0 {
+                        thread::sleep(Duration::from_millis(
+                            RETRY_BASE_DELAY_MS << (delivery.attempts - 1),
+                        ));
+                    }
+                    delivery.attempts += 1;
+                    let (status_code, result) = self.send(&channel.preset, &request);
+                    delivery.status_code = status_code;
+                    match result {
+                        Ok(()) => {
+                            delivery.success = true;
+                            delivery.error = None;
+                            break;
+                        }
+                        Err(err) => delivery.error = Some(err),
+                    }
+                }
+            }
+            Err(err) => delivery.error = Some(err.to_string()),
+        }
+        if let Err(err) = self.db.insert_webhook_delivery(&delivery) {
+            eprintln!("[webhook] 写入投递日志失败: {}", err);
+        }
+        delivery
+    }
+
+    fn send(&self, preset: &str, request: &WebhookRequest) -> (Option<i64>, Result<(), String>) {
+        let mut builder = self
+            .client
+            .post(&request.url)
+            .header("Content-Type", "application/json; charset=utf-8");
+        for (name, value) in &request.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        let response = match builder.body(request.body.clone()).send() {
+            Ok(response) => response,
+            Err(err) => return (None, Err(err.to_string())),
+        };
+        let status = response.status();
+        let body = response.text().unwrap_or_default();
+        let code = Some(status.as_u16() as i64);
+        if !status.is_success() {
+            return (
+                code,
+                Err(format!("状态码 {}: {}", status, truncate(&body, 200))),
+            );
+        }
+        (code, check_response_body(preset, &body))
+    }
+}
+
+pub fn sanitize_webhook_channel(channel: &mut WebhookChannel) -> Result<(), AppError> {
+    channel.name = channel.name.trim().to_string();
+    if channel.name.is_empty() {
+        return Err(AppError::Invalid("渠道名称不能为空".to_string()));
+    }
+    channel.preset = channel.preset.trim().to_uppercase();
+    if !WEBHOOK_PRESETS.contains(&channel.preset.as_str()) {
+        return Err(AppError::Invalid(format!(
+            "不支持的 Webhook 类型: {}",
+            channel.preset
+        )));
+    }
+    channel.url = channel.url.trim().to_string();
+    let lower = channel.url.to_lowercase();
+    if !lower.starts_with("http://") && !lower.starts_with("https://") {
+        return Err(AppError::Invalid(
+            "Webhook 地址仅支持 http 或 https".to_string(),
+        ));
+    }
+    reqwest::Url::parse(&channel.url)
+        .map_err(|_| AppError::Invalid("Webhook 地址格式不正确".to_string()))?;
+    channel.secret = channel.secret.trim().to_string();
+    let mut events = Vec::new();
+    for event in &channel.events {
+        let event = event.trim().to_uppercase();
+        if !WEBHOOK_EVENTS.contains(&event.as_str()) {
+            return Err(AppError::Invalid(format!("不支持的推送事件: {}", event)));
+        }
+        if !events.contains(&event) {
+            events.push(event);
+        }
+    }
+    if events.is_empty() {
+        return Err(AppError::Invalid("至少选择一个推送事件".to_string()));
+    }
+    channel.events = events;
+    Ok(())
+}
+
+/// 根据渠道预设生成请求，不做任何网络访问；`timestamp_ms` 用于钉钉加签。
+pub fn build_request(
+    channel: &WebhookChannel,
+    message: &WebhookMessage,
+    timestamp_ms: i64,
+) -> Result<WebhookRequest, AppError> {
+    let title = message.title();
+    let text = format!("{}\n{}", title, message.description);
+    let mut url = channel.url.clone();
+    let mut headers = Vec::new();
+    let body = match channel.preset.as_str() {
+        "NTFY" => {
+            // ntfy 的 JSON 发布接口要求 POST 到服务根地址，主题放在请求体中。
+            let mut parsed = reqwest::Url::parse(&channel.url)
+                .map_err(|_| AppError::Invalid("Webhook 地址格式不正确".to_string()))?;
+            let topic = parsed
+                .path_segments()
+                .and_then(|mut segments| segments.next_back().map(str::to_string))
+                .filter(|segment| !segment.is_empty())
+                .ok_or_else(|| AppError::Invalid("ntfy 地址需要包含主题".to_string()))?;
+            parsed.set_path("/");
+            url = parsed.to_string();
+            if !channel.secret.is_empty() {
+                headers.push((
+                    "Authorization".to_string(),
+                    format!("Bearer {}", channel.secret),
+                ));
+            }
+            json!({
+                "topic": topic,
+                "title": title,
+                "message": message.description,
+                "tags": ["alarm_clock"],
+            })
+        }
+        "GOTIFY" => {
+            if !channel.secret.is_empty() {
+                headers.push(("X-Gotify-Key".to_string(), channel.secret.clone()));
+            }
+            json!({
+                "title": title,
+                "message": message.description,
+                "priority": 5,
+            })
+        }
+        "BARK" => json!({
+            "title": title,
+            "body": message.description,
+            "group": "任务提醒",
+        }),
+        "DINGTALK" => {
+            if !channel.secret.is_empty() {
+                let sign = dingtalk_sign(&channel.secret, timestamp_ms)?;
+                let mut parsed = reqwest::Url::parse(&channel.url)
+                    .map_err(|_| AppError::Invalid("Webhook 地址格式不正确".to_string()))?;
+                parsed
+                    .query_pairs_mut()
+                    .append_pair("timestamp", &timestamp_ms.to_string())
+                    .append_pair("sign", &sign);
+                url = parsed.to_string();
+            }
+            json!({
+                "msgtype": "text",
+                "text": { "content": text },
+            })
+        }
+        "WECOM" => json!({
+            "msgtype": "text",
+            "text": { "content": text },
+        }),
+        _ => {
+            let body =
+                serde_json::to_value(message).map_err(|e| AppError::System(e.to_string()))?;
+            if !channel.secret.is_empty() {
+                let raw = body.to_string();
+                headers.push((
+                    "X-Signature".to_string(),
+                    format!("sha256={}", hmac_hex(&channel.secret, raw.as_bytes())?),
+                ));
+                return Ok(WebhookRequest {
+                    url,
+                    headers,
+                    body: raw,
+                });
+            }
+            body
+        }
+    };
+    Ok(WebhookRequest {
+        url,
+        headers,
+        body: body.to_string(),
+    })
+}
+
+/// 钉钉 / 企业微信机器人即使失败也返回 200，需要检查响应中的 errcode。
+fn check_response_body(preset: &str, body: &str) -> Result<(), String> {
+    if preset != "DINGTALK" && preset != "WECOM" {
+        return Ok(());
+    }
+    let Ok(value) = serde_json::from_str::<Value>(body) else {
+        return Ok(());
+    };
+    match value.get("errcode").and_then(Value::as_i64) {
+        Some(0) | None => Ok(()),
+        Some(code) => Err(format!(
+            "机器人返回错误 {}: {}",
+            code,
+            value
+                .get("errmsg")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+        )),
+    }
+}
+
+fn dingtalk_sign(secret: &str, timestamp_ms: i64) -> Result<String, AppError> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|e| AppError::System(e.to_string()))?;
+    mac.update(format!("{}\n{}", timestamp_ms, secret).as_bytes());
+    Ok(BASE64.encode(mac.finalize().into_bytes()))
+}
+
+fn hmac_hex(secret: &str, data: &[u8]) -> Result<String, AppError> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|e| AppError::System(e.to_string()))?;
+    mac.update(data);
+    Ok(mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+fn truncate(value: &str, max_chars: usize) -> String {
+    value.chars().take(max_chars).collect()
+}
+
+fn format_time(value: NaiveDateTime) -> String {
+    value.format("%Y-%m-%dT%H:%M:%S").to_string()
+}
+
+/// 签名用的毫秒时间戳。时钟给出的是本地时间，夏令时跳过的时刻按 UTC 处理。
+fn timestamp_millis(now: NaiveDateTime) -> i64 {
+    Local
+        .from_local_datetime(&now)
+        .earliest()
+        .map(|value| value.timestamp_millis())
+        .unwrap_or_else(|| now.and_utc().timestamp_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    use super::*;
+
+    fn channel(preset: &str, url: &str, secret: &str) -> WebhookChannel {
+        WebhookChannel {
+            id: "channel".to_string(),
+            name: preset.to_string(),
+            preset: preset.to_string(),
+            url: url.to_string(),
+            secret: secret.to_string(),
+            events: vec![WEBHOOK_EVENT_FIRED.to_string()],
+            apply_to_all: true,
+            enabled: true,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn message() -> WebhookMessage {
+        WebhookMessage {
+            event: WEBHOOK_EVENT_FIRED.to_string(),
+            record_id: "record".to_string(),
+            reminder_id: "task".to_string(),
+            reminder_type: "TASK".to_string(),
+            description: "交周报".to_string(),
+            trigger_time: "2026-01-05T09:00:00".to_string(),
+            close_time: None,
+            action: "PENDING".to_string(),
+            snooze_minutes: Some(5),
+            actions: Vec::new(),
+            sent_at: "2026-01-05T09:00:00".to_string(),
+        }
+    }
+
+    fn body(request: &WebhookRequest) -> Value {
+        serde_json::from_str(&request.body).unwrap()
+    }
+
+    /// 依次以 `statuses` 中的状态码应答每个连接，返回收到的请求体。
+    fn serve(statuses: Vec<u16>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut bodies = Vec::new();
+            for status in statuses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                bodies.push(String::from_utf8(body).unwrap());
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                )
+                .unwrap();
+            }
+            bodies
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn generic_signs_the_exact_body() {
+        let request = build_request(
+            &channel("GENERIC", "https://example.com/hook", "secret"),
+            &message(),
+            0,
+        )
+        .unwrap();
+        assert_eq!(request.url, "https://example.com/hook");
+        assert_eq!(body(&request)["recordId"], "record");
+        assert_eq!(body(&request)["snoozeMinutes"], 5);
+        let signature = Hmac::<Sha256>::new_from_slice(b"secret")
+            .unwrap()
+            .chain_update(request.body.as_bytes())
+            .finalize()
+            .into_bytes();
+        let expected: String = signature
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        assert_eq!(
+            request.headers,
+            vec![("X-Signature".to_string(), format!("sha256={}", expected))]
+        );
+
+        let unsigned = build_request(
+            &channel("GENERIC", "https://example.com/hook", ""),
+            &message(),
+            0,
+        )
+        .unwrap();
+        assert!(unsigned.headers.is_empty());
+    }
+
+    #[test]
+    fn dingtalk_appends_timestamp_and_sign() {
+        let request = build_request(
+            &channel(
+                "DINGTALK",
+                "https://oapi.dingtalk.com/robot/send?access_token=abc",
+                "SEC123",
+            ),
+            &message(),
+            1700000000000,
+        )
+        .unwrap();
+        assert_eq!(
+            request.url,
+            "https://oapi.dingtalk.com/robot/send?access_token=abc&timestamp=1700000000000&sign=lkcPI1uoxBY1gUnCnnPH1Kkru0Hqjo7rFpA3haIVhEQ%3D"
+        );
+        assert_eq!(
+            body(&request),
+            json!({ "msgtype": "text", "text": { "content": "任务提醒\n交周报" } })
+        );
+    }
+
+    #[test]
+    fn push_service_presets_use_their_body_shapes() {
+        let ntfy = build_request(
+            &channel("NTFY", "https://ntfy.sh/reminders", "token"),
+            &message(),
+            0,
+        )
+        .unwrap();
+        assert_eq!(ntfy.url, "https://ntfy.sh/");
+        assert_eq!(
+            ntfy.headers,
+            vec![("Authorization".to_string(), "Bearer token".to_string())]
+        );
+        assert_eq!(
+            body(&ntfy),
+            json!({
+                "topic": "reminders",
+                "title": "任务提醒",
+                "message": "交周报",
+                "tags": ["alarm_clock"],
+            })
+        );
+
+        let gotify = build_request(
+            &channel("GOTIFY", "https://gotify.example.com/message", "key"),
+            &message(),
+            0,
+        )
+        .unwrap();
+        assert_eq!(
+            gotify.headers,
+            vec![("X-Gotify-Key".to_string(), "key".to_string())]
+        );
+        assert_eq!(
+            body(&gotify),
+            json!({ "title": "任务提醒", "message": "交周报", "priority": 5 })
+        );
+
+        let bark = build_request(
+            &channel("BARK", "https://api.day.app/device", ""),
+            &message(),
+            0,
+        )
+        .unwrap();
+        assert_eq!(
+            body(&bark),
+            json!({ "title": "任务提醒", "body": "交周报", "group": "任务提醒" })
+        );
+
+        let wecom = build_request(
+            &channel(
+                "WECOM",
+                "https://qyapi.weixin.qq.com/cgi-bin/webhook/send?key=k",
+                "",
+            ),
+            &message(),
+            0,
+        )
+        .unwrap();
+        assert_eq!(
+            wecom.url,
+            "https://qyapi.weixin.qq.com/cgi-bin/webhook/send?key=k"
+        );
+        assert_eq!(
+            body(&wecom),
+            json!({ "msgtype": "text", "text": { "content": "任务提醒\n交周报" } })
+        );
+
+        assert!(build_request(&channel("NTFY", "https://ntfy.sh/", ""), &message(), 0).is_err());
+    }
+
+    #[test]
+    fn failed_delivery_is_retried_and_logged() {
+        let path =
+            std::env::temp_dir().join(format!("task-reminder-webhook-{}.db", Uuid::new_v4()));
+        let db = DbManager::new(path.clone()).unwrap();
+        let service = WebhookService::new(db.clone()).unwrap();
+        let (url, server) = serve(vec![500, 200]);
+
+        let delivery = service.deliver(&channel("GENERIC", &url, ""), &message(), MAX_ATTEMPTS);
+
+        let bodies = server.join().unwrap();
+        assert_eq!(bodies.len(), 2);
+        assert_eq!(bodies[0], bodies[1]);
+        assert!(delivery.success);
+        assert_eq!(delivery.attempts, 2);
+        assert_eq!(delivery.status_code, Some(200));
+        let logged = db.list_webhook_deliveries(10).unwrap();
+        assert_eq!(logged.len(), 1);
+        assert_eq!(logged[0].id, delivery.id);
+        assert_eq!(logged[0].record_id, "record");
+        assert_eq!(logged[0].attempts, 2);
+        assert!(logged[0].success);
+        drop(service);
+        drop(db);
+        for suffix in ["", "-wal", "-shm"] {
+            let mut path = path.clone().into_os_string();
+            path.push(suffix);
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
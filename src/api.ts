@@ -1,19 +1,37 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
   Task,
+  TaskFilter,
+  TaskSort,
   RecurringTask,
   RecurringMode,
   ReminderRecord,
+  ReminderStats,
+  ReportFormat,
+  HabitState,
+  HabitSummary,
   StickyNote,
   AppSettings,
+  MonitorInfo,
   SyncStatus,
   NotificationPayload,
-  UiStatePayload
+  NotifyOptions,
+  UiStatePayload,
+  WebhookChannel,
+  WebhookDelivery,
+  SoundInfo,
+  ActivityStatus,
+  FocusSession,
+  PauseState,
+  TaskReminder,
+  TimeEntry,
+  Timer,
+  UpcomingReminder
 } from "./types";
 
 export const api = {
-  async listActiveTasks(): Promise<Task[]> {
-    return invoke("list_active_tasks");
+  async listActiveTasks(sort?: TaskSort, filter?: TaskFilter): Promise<Task[]> {
+    return invoke("list_active_tasks", { sort, filter });
   },
   async listCompletedTasks(): Promise<Task[]> {
     return invoke("list_completed_tasks");
@@ -24,6 +42,15 @@ export const api = {
   async listReminderRecords(): Promise<ReminderRecord[]> {
     return invoke("list_reminder_records");
   },
+  async getReminderStats(from?: string | null, to?: string | null): Promise<ReminderStats> {
+    return invoke("get_reminder_stats", { from, to });
+  },
+  async getHabitSummaries(reminderId?: string | null, from?: string | null, to?: string | null): Promise<HabitSummary[]> {
+    return invoke("get_habit_summaries", { reminderId, from, to });
+  },
+  async setHabitState(recordId: string, habitState: HabitState | null): Promise<void> {
+    return invoke("set_habit_state", { recordId, habitState });
+  },
   async createTask(payload: { description: string; stickyContent?: string | null }): Promise<Task> {
     return invoke("create_task", { payload });
   },
@@ -32,6 +59,9 @@ export const api = {
     description: string;
     stickyContent?: string | null;
     reminderTime?: string | null;
+    reminderTimes?: string[];
+    dueAt?: string | null;
+    notifyOptions?: NotifyOptions | null;
   }): Promise<void> {
     return invoke("update_task", { task });
   },
@@ -54,6 +84,7 @@ export const api = {
     scheduleWeekday?: number | null;
     scheduleDay?: number | null;
     cronExpression?: string | null;
+    notifyOptions?: NotifyOptions | null;
   }): Promise<RecurringTask> {
     return invoke("create_recurring_task", { payload });
   },
@@ -136,6 +167,9 @@ export const api = {
   async testWebDav(settings: AppSettings): Promise<{ ok: boolean; message: string }> {
     return invoke("test_webdav", { settings });
   },
+  async testSmtp(settings: AppSettings): Promise<{ ok: boolean; message: string }> {
+    return invoke("test_smtp", { settings });
+  },
   async syncNow(reason: string): Promise<void> {
     return invoke("sync_now", { reason });
   },
@@ -148,6 +182,14 @@ export const api = {
   }): Promise<void> {
     return invoke("ack_notification", { payload });
   },
+  async runNotificationAction(payload: {
+    recordId: string;
+    reminderId: string;
+    reminderType: string;
+    actionId: string;
+  }): Promise<void> {
+    return invoke("run_notification_action", { payload });
+  },
   async snoozeNotification(payload: {
     recordId: string;
     reminderId: string;
@@ -162,6 +204,107 @@ export const api = {
   async getNotificationSnapshot(): Promise<NotificationPayload | null> {
     return invoke("get_notification_snapshot");
   },
+  async listMonitors(): Promise<MonitorInfo[]> {
+    return invoke("list_monitors");
+  },
+  async listWebhookChannels(): Promise<WebhookChannel[]> {
+    return invoke("list_webhook_channels");
+  },
+  async saveWebhookChannel(channel: WebhookChannel): Promise<WebhookChannel> {
+    return invoke("save_webhook_channel", { channel });
+  },
+  async deleteWebhookChannel(channelId: string): Promise<void> {
+    return invoke("delete_webhook_channel", { channelId });
+  },
+  async testWebhookChannel(channel: WebhookChannel): Promise<WebhookDelivery> {
+    return invoke("test_webhook_channel", { channel });
+  },
+  async listWebhookDeliveries(limit?: number): Promise<WebhookDelivery[]> {
+    return invoke("list_webhook_deliveries", { limit: limit ?? null });
+  },
+  async listSounds(): Promise<SoundInfo[]> {
+    return invoke("list_sounds");
+  },
+  async getSoundsDir(): Promise<string> {
+    return invoke("get_sounds_dir");
+  },
+  async previewSound(soundId: string): Promise<void> {
+    return invoke("preview_sound", { soundId });
+  },
+  async stopSound(): Promise<void> {
+    return invoke("stop_sound");
+  },
+  async previewSpeech(engine: string, voice: string, rate: number): Promise<void> {
+    return invoke("preview_speech", { engine, voice, rate });
+  },
+  async stopSpeech(): Promise<void> {
+    return invoke("stop_speech");
+  },
+  async getActivityStatus(): Promise<ActivityStatus> {
+    return invoke("get_activity_status");
+  },
+  async getFocusSession(): Promise<FocusSession | null> {
+    return invoke("get_focus_session");
+  },
+  async startFocus(taskId: string): Promise<FocusSession> {
+    return invoke("start_focus", { taskId });
+  },
+  async stopFocus(): Promise<void> {
+    return invoke("stop_focus");
+  },
+  async skipFocusPhase(): Promise<void> {
+    return invoke("skip_focus_phase");
+  },
+  async listTaskReminders(taskId: string): Promise<TaskReminder[]> {
+    return invoke("list_task_reminders", { taskId });
+  },
+  async listTimeEntries(taskId: string): Promise<TimeEntry[]> {
+    return invoke("list_time_entries", { taskId });
+  },
+  async deleteTimeEntry(id: string): Promise<void> {
+    return invoke("delete_time_entry", { id });
+  },
+  async getPauseState(): Promise<PauseState> {
+    return invoke("get_pause_state");
+  },
+  async pauseAllReminders(resumeAt: string | null): Promise<PauseState> {
+    return invoke("pause_all_reminders", { resumeAt });
+  },
+  async resumeAllReminders(): Promise<void> {
+    return invoke("resume_all_reminders");
+  },
+  async listUpcomingReminders(limit?: number): Promise<UpcomingReminder[]> {
+    return invoke("list_upcoming_reminders", { limit });
+  },
+  async listTimers(): Promise<Timer[]> {
+    return invoke("list_timers");
+  },
+  async startTimer(payload: { label?: string | null; seconds: number }): Promise<Timer> {
+    return invoke("start_timer", { payload });
+  },
+  async pauseTimer(id: string): Promise<void> {
+    return invoke("pause_timer", { id });
+  },
+  async resumeTimer(id: string): Promise<void> {
+    return invoke("resume_timer", { id });
+  },
+  async cancelTimer(id: string): Promise<void> {
+    return invoke("cancel_timer", { id });
+  },
+  async generateWeeklyReport(payload: {
+    date?: string | null;
+    format: ReportFormat;
+    target: "FILE" | "CLIPBOARD";
+    path?: string | null;
+  }): Promise<string> {
+    return invoke("generate_weekly_report", payload);
+  },
+  async sendDigestNow(): Promise<void> {
+    return invoke("send_digest_now");
+  },
+  async openDigestItem(kind: string, id: string): Promise<void> {
+    return invoke("open_digest_item", { kind, id });
+  },
   async isDevMode(): Promise<boolean> {
     return invoke("is_dev_mode");
   },
@@ -1,7 +1,19 @@
 export type TaskStatus = "PENDING" | "COMPLETED";
 export type TaskType = "ONE_TIME" | "RECURRING";
-export type ReminderType = "TASK" | "RECURRING";
-export type UserAction = "DISMISSED" | "SNOOZED" | "COMPLETED" | "PENDING";
+export type ReminderType = "TASK" | "RECURRING" | "TIMER" | "BREAK" | "LEAD" | "DUE";
+export type TaskSort = "CREATED" | "DUE" | "REMINDER";
+export type TaskFilter = "ALL" | "OVERDUE" | "DUE_TODAY" | "NO_DUE";
+export type UserAction =
+  | "DISMISSED"
+  | "SNOOZED"
+  | "COMPLETED"
+  | "OPENED_NOTE"
+  | "OPENED_URL"
+  | "MISSED"
+  | "SKIPPED"
+  | "PENDING";
+export type HabitState = "DONE" | "SKIPPED" | "MISSED";
+export type NotificationActionKind = "COMPLETE_TASK" | "OPEN_STICKY_NOTE" | "OPEN_URL";
 export type RecurringMode = "INTERVAL_RANGE" | "DAILY" | "WEEKLY" | "MONTHLY" | "CRON";
 
 export interface Task {
@@ -15,6 +27,37 @@ export interface Task {
   reminderTime?: string | null;
   updatedAt?: string | null;
   deletedAt?: string | null;
+  notifyOptions: NotifyOptions;
+  trackedSeconds?: number;
+  dueAt?: string | null;
+  overdue?: boolean;
+}
+
+export interface NotificationAction {
+  id: string;
+  label: string;
+  kind: NotificationActionKind;
+  url?: string | null;
+}
+
+export interface NotifyOptions {
+  actions: NotificationAction[];
+  webhookChannels: string[];
+  email: boolean;
+  sound?: string | null;
+  tts: boolean;
+  ttsVoice?: string | null;
+  ttsRate?: number | null;
+  leadMinutes?: number[];
+  overdueAlert?: boolean;
+}
+
+export type SpeechEngine = "AUTO" | "ESPEAK" | "SPD" | "SAY" | "SAPI";
+
+export interface SoundInfo {
+  id: string;
+  label: string;
+  builtin: boolean;
 }
 
 export interface RecurringTask {
@@ -38,6 +81,7 @@ export interface RecurringTask {
   scheduleWeekday?: number | null;
   scheduleDay?: number | null;
   cronExpression?: string | null;
+  notifyOptions: NotifyOptions;
 }
 
 export interface ReminderRecord {
@@ -50,6 +94,126 @@ export interface ReminderRecord {
   action: UserAction;
   updatedAt?: string | null;
   deletedAt?: string | null;
+  deliveryError?: string | null;
+  habitState?: HabitState | null;
+  deliveryState?: "DELIVERED_LATE" | null;
+  deliveredAt?: string | null;
+  occurrenceId?: string | null;
+}
+
+export type FocusPhase = "WORK" | "SHORT_BREAK" | "LONG_BREAK" | "BREAK_OVER";
+
+export interface FocusSession {
+  taskId: string;
+  description: string;
+  phase: FocusPhase;
+  round: number;
+  completedRounds: number;
+  phaseStartedAt: string;
+  phaseEndsAt?: string | null;
+}
+
+export interface ActivityStatus {
+  source?: string | null;
+  idleSeconds?: number | null;
+  activeSeconds: number;
+  lockSource?: string | null;
+  locked?: boolean | null;
+  away: boolean;
+}
+
+export interface TaskReminder {
+  id: string;
+  taskId: string;
+  remindAt: string;
+  firedAt?: string | null;
+  createdAt: string;
+  updatedAt?: string | null;
+  deletedAt?: string | null;
+  isSnooze?: boolean;
+}
+
+export interface TimeEntry {
+  id: string;
+  taskId: string;
+  startedAt: string;
+  endedAt: string;
+  durationSeconds: number;
+  source: string;
+  updatedAt?: string | null;
+  deletedAt?: string | null;
+}
+
+export type TimerStatus = "RUNNING" | "PAUSED";
+
+export interface Timer {
+  id: string;
+  label: string;
+  durationSeconds: number;
+  status: TimerStatus;
+  endsAt?: string | null;
+  remainingSeconds: number;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export type UpcomingSource = "TASK" | "RECURRING" | "TIMER" | "LEAD" | "DUE" | "SNOOZE";
+
+export interface UpcomingReminder {
+  reminderId: string;
+  reminderType: ReminderType;
+  source: UpcomingSource;
+  description: string;
+  fireAt: string;
+}
+
+export interface ReconcileSummary {
+  armed: string[];
+  rescheduled: string[];
+  cancelled: string[];
+  unchanged: number;
+}
+
+export interface ReminderStatsSummary {
+  total: number;
+  acknowledged: number;
+  snoozed: number;
+  missed: number;
+  pending: number;
+  ackRate?: number | null;
+  medianResponseSeconds?: number | null;
+  hourCounts: number[];
+  busiestHours: number[];
+}
+
+export interface TaskReminderStats extends ReminderStatsSummary {
+  reminderId: string;
+  type: ReminderType;
+  description: string;
+}
+
+export interface ReminderStats {
+  from: string;
+  to: string;
+  overall: ReminderStatsSummary;
+  tasks: TaskReminderStats[];
+}
+
+export interface HabitDay {
+  date: string;
+  state: HabitState;
+  doneCount: number;
+}
+
+export interface HabitSummary {
+  reminderId: string;
+  description: string;
+  currentStreak: number;
+  longestStreak: number;
+  doneDays: number;
+  skippedDays: number;
+  missedDays: number;
+  days: HabitDay[];
 }
 
 export interface StickyNote {
@@ -91,6 +255,111 @@ export interface AppSettings {
   webdavLastSyncError?: string | null;
   webdavDeviceId: string;
   notificationTheme: "system" | "app" | "light" | "dark";
+  notificationCorner: NotificationCorner;
+  notificationMonitor: NotificationMonitorMode;
+  notificationMonitorName: string;
+  notificationWidth?: number | null;
+  notificationHeight?: number | null;
+  notificationMargin: number;
+  smtpEnabled: boolean;
+  smtpHost: string;
+  smtpPort: number;
+  smtpSecurity: SmtpSecurity;
+  smtpUsername: string;
+  smtpPassword: string;
+  smtpFrom: string;
+  smtpTo: string;
+  mqttEnabled: boolean;
+  mqttHost: string;
+  mqttPort: number;
+  mqttUsername: string;
+  mqttPassword: string;
+  mqttClientId: string;
+  mqttTopicPrefix: string;
+  soundDefault: string;
+  soundLoop: boolean;
+  soundVolumeStart: number;
+  soundVolumeMax: number;
+  soundEscalateSeconds: number;
+  quietHoursEnabled: boolean;
+  quietHoursStart: string;
+  quietHoursEnd: string;
+  ttsEngine: SpeechEngine;
+  ttsVoice: string;
+  ttsRate: number;
+  digestEnabled: boolean;
+  digestTime: string;
+  digestWorkdaysOnly: boolean;
+  digestLastDate?: string | null;
+  reportEnabled: boolean;
+  reportWeekday: number;
+  reportTime: string;
+  reportFormat: ReportFormat;
+  reportDir: string;
+  reportLastDate?: string | null;
+  focusWorkMinutes: number;
+  focusShortBreakMinutes: number;
+  focusLongBreakMinutes: number;
+  focusLongBreakEvery: number;
+  breakEnabled: boolean;
+  breakActiveMinutes: number;
+  breakIdleResetMinutes: number;
+  holdWhenAway: boolean;
+  awayIdleMinutes: number;
+  defaultLeadMinutes: number[];
+  missedPolicy: MissedPolicy;
+}
+
+export type MissedPolicy = "ONCE" | "SKIP";
+
+export interface PauseState {
+  paused: boolean;
+  pausedAt?: string | null;
+  resumeAt?: string | null;
+  updatedAt?: string | null;
+}
+
+export type SmtpSecurity = "STARTTLS" | "TLS" | "NONE";
+
+export type NotificationCorner = "BOTTOM_RIGHT" | "BOTTOM_LEFT" | "TOP_RIGHT" | "TOP_LEFT";
+export type NotificationMonitorMode = "PRIMARY" | "CURSOR" | "NAMED";
+
+export interface MonitorInfo {
+  name: string;
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+  scaleFactor: number;
+  isPrimary: boolean;
+}
+
+export type WebhookPreset = "GENERIC" | "NTFY" | "GOTIFY" | "BARK" | "DINGTALK" | "WECOM";
+export type WebhookEvent = "FIRED" | "ACKED" | "SNOOZED" | "MISSED";
+
+export interface WebhookChannel {
+  id: string;
+  name: string;
+  preset: WebhookPreset;
+  url: string;
+  secret: string;
+  events: WebhookEvent[];
+  applyToAll: boolean;
+  enabled: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface WebhookDelivery {
+  id: string;
+  channelId: string;
+  recordId: string;
+  event: string;
+  success: boolean;
+  attempts: number;
+  statusCode?: number | null;
+  error?: string | null;
+  createdAt: string;
 }
 
 export interface UiStatePayload {
@@ -105,10 +374,23 @@ export interface SyncStatus {
   time?: string | null;
 }
 
+export type ReportFormat = "MARKDOWN" | "HTML";
+
+export type DigestItemKind = "TASK" | "RECURRING" | "OVERDUE" | "NOTE";
+
+export interface DigestItem {
+  kind: DigestItemKind;
+  id: string;
+  title: string;
+  time?: string | null;
+}
+
 export interface NotificationPayload {
   recordId: string;
   reminderId: string;
-  reminderType: ReminderType;
+  reminderType: ReminderType | "DIGEST" | "FOCUS";
   description: string;
   snoozeMinutes: number;
+  actions: NotificationAction[];
+  digestItems?: DigestItem[];
 }